@@ -0,0 +1,12 @@
+function on_load()
+	local counter = world:get_type_by_name("Counter")
+
+	-- `count` will now run every frame, as part of the "update" script system set
+	world:add_system("update", count, world:query(counter))
+end
+
+function count(query)
+	for _, counter in query:iter() do
+		counter.value = counter.value + 1
+	end
+end
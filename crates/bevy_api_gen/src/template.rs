@@ -275,6 +275,11 @@ pub(crate) fn configure_tera_env(tera: &mut Tera, crate_name: &str) {
         Ok(Value::String(format!("Lua{str}")))
     });
 
+    tera.register_filter("prefix_rhai", |val: &Value, _: &HashMap<String, Value>| {
+        let str = expect_str(val)?;
+        Ok(Value::String(format!("Rhai{str}")))
+    });
+
     // otherwise rust complains about the crate name being borrowed
     let crate_name = crate_name.to_owned();
     tera.register_filter(
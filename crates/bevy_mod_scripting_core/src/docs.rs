@@ -1,10 +1,186 @@
-use crate::error::ScriptError;
+use std::{
+    any::Any,
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::Resource};
+
+use crate::{
+    error::ScriptError,
+    hosts::{APIProvider, APIProviders, ScriptHost},
+};
 
 /// A documentation piece exported by an `APIProvider`
 pub trait DocFragment: 'static {
     fn merge(self, o: Self) -> Self;
-    fn gen_docs(self) -> Result<(), ScriptError>;
+
+    /// Generates the documentation into the given directory, which is created beforehand if it does not exist
+    fn gen_docs(self, output_dir: &Path) -> Result<(), ScriptError>;
+
+    /// Generates the documentation for [`DocGenerator::check`], which only compares the contents of the output directory.
+    ///
+    /// Fragments which also update files outside of the output directory, such as the teal declarations in `assets`, should only write into the output directory here.
+    fn gen_checked_docs(self, output_dir: &Path) -> Result<(), ScriptError>
+    where
+        Self: Sized,
+    {
+        self.gen_docs(output_dir)
+    }
 
     /// Retrieves the name of the documentation fragment, most likely the name of your game!
     fn name(&self) -> &'static str;
 }
+
+/// Configures where documentation is generated by [`crate::GenDocumentation::update_documentation`].
+///
+/// Insert this resource before updating documentation to change the defaults.
+#[derive(Resource, Debug, Clone)]
+pub struct DocumentationSettings {
+    /// The directory documentation is generated in, `script_docs` by default.
+    /// It should be outside of `assets`, otherwise the generated definition files are loaded like any other script
+    pub output_dir: PathBuf,
+}
+
+impl Default for DocumentationSettings {
+    fn default() -> Self {
+        Self {
+            output_dir: FileAssetReader::get_base_path().join("script_docs"),
+        }
+    }
+}
+
+/// Generates documentation for API providers without building an `App`,
+/// e.g. from a small binary or build step registering the providers of your game.
+///
+/// Providers are grouped by script host, and the documentation of each host is generated into its own subdirectory.
+/// ```rust,ignore
+/// let mut generator = DocGenerator::default();
+/// generator
+///     .add_api_provider::<LuaScriptHost<()>>("lua", Box::new(LuaBevyAPIProvider))
+///     .add_api_provider::<RhaiScriptHost<()>>("rhai", Box::new(RhaiBevyAPIProvider));
+/// generator.generate(Path::new("script_docs"))?;
+/// ```
+#[derive(Default)]
+pub struct DocGenerator {
+    hosts: Vec<DocHost>,
+}
+
+struct DocHost {
+    subdirectory: PathBuf,
+    /// The `APIProviders` of the host
+    providers: Box<dyn Any>,
+    generate: fn(&dyn Any, &Path, bool) -> Result<(), ScriptError>,
+}
+
+impl DocGenerator {
+    /// Adds a provider to the documentation generated into the given subdirectory of the output directory
+    pub fn add_api_provider<T: ScriptHost>(
+        &mut self,
+        subdirectory: impl Into<PathBuf>,
+        provider: Box<
+            dyn APIProvider<
+                APITarget = T::APITarget,
+                DocTarget = T::DocTarget,
+                ScriptContext = T::ScriptContext,
+            >,
+        >,
+    ) -> &mut Self {
+        let subdirectory = subdirectory.into();
+        let existing = self.hosts.iter_mut().find_map(|host| {
+            (host.subdirectory == subdirectory)
+                .then(|| host.providers.downcast_mut::<APIProviders<T>>())
+                .flatten()
+        });
+
+        match existing {
+            Some(providers) => providers.providers.push(provider),
+            None => self.hosts.push(DocHost {
+                subdirectory,
+                providers: Box::new(APIProviders::<T> {
+                    providers: vec![provider],
+                }),
+                generate: |providers, output_dir, checked| {
+                    let providers = providers
+                        .downcast_ref::<APIProviders<T>>()
+                        .expect("Providers of a different script host");
+                    if checked {
+                        providers.gen_all_checked(output_dir)
+                    } else {
+                        providers.gen_all(output_dir)
+                    }
+                },
+            }),
+        }
+        self
+    }
+
+    /// Generates the documentation of every script host into the output directory
+    pub fn generate(&self, output_dir: &Path) -> Result<(), ScriptError> {
+        self.generate_into(output_dir, false)
+    }
+
+    fn generate_into(&self, output_dir: &Path, checked: bool) -> Result<(), ScriptError> {
+        for host in &self.hosts {
+            (host.generate)(
+                host.providers.as_ref(),
+                &output_dir.join(&host.subdirectory),
+                checked,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Generates the documentation into a temporary directory and compares it with the contents of the output directory.
+    ///
+    /// Returns the paths, relative to the output directory, of generated files which are missing or out of date.
+    /// Files in the output directory which would not be generated are ignored, and nothing is written outside of the temporary directory.
+    pub fn check(&self, output_dir: &Path) -> Result<Vec<PathBuf>, ScriptError> {
+        let temp_dir =
+            env::temp_dir().join(format!("bevy_mod_scripting_doc_check_{}", process::id()));
+        if temp_dir.exists() {
+            fs::remove_dir_all(&temp_dir).map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+        }
+
+        let outdated = self.generate_into(&temp_dir, true).and_then(|_| {
+            let mut outdated = Vec::default();
+            for file in files_in(&temp_dir)? {
+                let relative = file.strip_prefix(&temp_dir).unwrap_or(&file).to_owned();
+                let expected =
+                    fs::read(&file).map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+                if fs::read(output_dir.join(&relative)).ok() != Some(expected) {
+                    outdated.push(relative);
+                }
+            }
+            Ok(outdated)
+        });
+
+        let _ = fs::remove_dir_all(&temp_dir);
+        outdated
+    }
+}
+
+/// Recursively lists the files in the directory, in a stable order
+fn files_in(dir: &Path) -> Result<Vec<PathBuf>, ScriptError> {
+    let mut entries = fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| {
+            ScriptError::DocGenError(format!("Could not read `{}`: {e}", dir.display()))
+        })?;
+    entries.sort();
+
+    let mut files = Vec::default();
+    for entry in entries {
+        if entry.is_dir() {
+            files.extend(files_in(&entry)?);
+        } else {
+            files.push(entry);
+        }
+    }
+    Ok(files)
+}
@@ -16,6 +16,13 @@ pub enum ScriptError {
     },
     #[error("Failed to attach API for script `{script}` {msg}")]
     FailedToAttachAPI { script: String, msg: String },
+    #[error("Warning in script `{script}` at {line}:{column} {msg}")]
+    LintWarning {
+        script: String,
+        line: usize,
+        column: usize,
+        msg: String,
+    },
     #[error("Failed to generate documentation `{0}`")]
     DocGenError(String),
     #[error("{0}")]
@@ -2,7 +2,9 @@
 use bevy::{asset::Asset, ecs::schedule::ScheduleLabel, prelude::*};
 use std::{
     collections::HashMap,
+    fs,
     iter::once,
+    path::Path,
     sync::atomic::{AtomicU32, Ordering},
 };
 
@@ -79,6 +81,19 @@ pub trait ScriptHost: Send + Sync + 'static + Default + Resource {
         providers: &mut APIProviders<Self>,
     ) -> Result<Self::ScriptContext, ScriptError>;
 
+    /// Checks a script against the registered API without running it, e.g. for calls to functions which do not exist.
+    ///
+    /// Findings are returned as [`ScriptError::LintWarning`]s, errors which prevent the script from loading are left to `load_script`.
+    /// Called before every new or re-loaded script is loaded in debug builds, or always with the `lint_always` feature.
+    fn lint_script(
+        &mut self,
+        _script: &[u8],
+        _script_data: &ScriptData,
+        _providers: &mut APIProviders<Self>,
+    ) -> Vec<ScriptError> {
+        Vec::default()
+    }
+
     /// Perform one-off initialization of scripts (happens for every new or re-loaded script)
     fn setup_script(
         &mut self,
@@ -242,7 +257,21 @@ impl<T: ScriptHost> APIProviders<T> {
         Ok(())
     }
 
-    pub fn gen_all(&self) -> Result<(), ScriptError> {
+    /// Merges the documentation of every provider and generates it into the given directory
+    pub fn gen_all(&self, output_dir: &Path) -> Result<(), ScriptError> {
+        self.gen_all_with(output_dir, DocFragment::gen_docs)
+    }
+
+    /// Like [`APIProviders::gen_all`], but only writes into the given directory, see [`DocFragment::gen_checked_docs`]
+    pub(crate) fn gen_all_checked(&self, output_dir: &Path) -> Result<(), ScriptError> {
+        self.gen_all_with(output_dir, DocFragment::gen_checked_docs)
+    }
+
+    fn gen_all_with(
+        &self,
+        output_dir: &Path,
+        gen_docs: fn(T::DocTarget, &Path) -> Result<(), ScriptError>,
+    ) -> Result<(), ScriptError> {
         let mut d: Option<T::DocTarget> = None;
         for p in self.providers.iter() {
             if let Some(f) = p.get_doc_fragment() {
@@ -253,7 +282,17 @@ impl<T: ScriptHost> APIProviders<T> {
                 }
             }
         }
-        d.map(|d| d.gen_docs()).unwrap_or_else(|| Ok(()))
+        let Some(d) = d else {
+            return Ok(());
+        };
+
+        fs::create_dir_all(output_dir).map_err(|e| {
+            ScriptError::DocGenError(format!(
+                "Could not create `{}` directories: {e}",
+                output_dir.display()
+            ))
+        })?;
+        gen_docs(d, output_dir)
     }
 }
 
@@ -384,7 +423,7 @@ impl<T: Asset> Script<T> {
         }
     }
 
-    /// checks if a script has loaded, and if so loads (`ScriptHost::load_script`),
+    /// checks if a script has loaded, and if so lints (`ScriptHost::lint_script`), loads (`ScriptHost::load_script`),
     /// sets up (`ScriptHost::setup_script`) and inserts its new context into the contexts resource
     /// otherwise inserts None. Sends ScriptLoaded event if the script was loaded
     pub(crate) fn insert_new_script_context<H: ScriptHost>(
@@ -414,6 +453,12 @@ impl<T: Asset> Script<T> {
         };
         debug!("Inserted script {:?}", fd);
 
+        #[cfg(any(debug_assertions, feature = "lint_always"))]
+        for error in host.lint_script(script.bytes(), &fd, providers) {
+            warn!("{}", error);
+            error_writer.send(ScriptErrorEvent { error });
+        }
+
         match host.load_script(script.bytes(), &fd, providers) {
             Ok(mut ctx) => {
                 host.setup_script(&fd, &mut ctx, providers)
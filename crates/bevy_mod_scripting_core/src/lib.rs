@@ -5,6 +5,7 @@ use crate::{
 use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
 use event::ScriptLoaded;
 use systems::script_event_handler;
+use world::ScriptCommandQueue;
 
 pub mod asset;
 pub mod docs;
@@ -17,7 +18,7 @@ pub mod prelude {
     // general
     pub use {
         crate::asset::CodeAsset,
-        crate::docs::DocFragment,
+        crate::docs::{DocFragment, DocGenerator, DocumentationSettings},
         crate::error::ScriptError,
         crate::event::{ScriptErrorEvent, ScriptEvent},
         crate::hosts::{
@@ -43,7 +44,8 @@ pub struct ScriptingPlugin;
 
 impl Plugin for ScriptingPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_event::<ScriptErrorEvent>();
+        app.add_event::<ScriptErrorEvent>()
+            .init_resource::<ScriptCommandQueue>();
     }
 }
 
@@ -58,8 +60,12 @@ impl GenDocumentation for App {
         {
             info!("Generating documentation");
             let w = &mut self.world_mut();
+            let settings = w
+                .get_resource::<docs::DocumentationSettings>()
+                .cloned()
+                .unwrap_or_default();
             let providers: &APIProviders<T> = w.resource();
-            if let Err(e) = providers.gen_all() {
+            if let Err(e) = providers.gen_all(&settings.output_dir) {
                 error!("{}", e);
             }
             info!("Documentation generated");
@@ -6,6 +6,7 @@ use bevy_event_priority::PriorityEventReader;
 use crate::{
     event::ScriptLoaded,
     prelude::{APIProviders, Script, ScriptCollection, ScriptContexts, ScriptData, ScriptHost},
+    world::ScriptCommandQueue,
     ScriptErrorEvent,
 };
 
@@ -19,6 +20,7 @@ pub enum ScriptSystemSet {
 /// Handles creating contexts for new/modified scripts
 /// Scripts are likely not loaded instantly at this point, so most of the time
 /// this system simply inserts an empty context
+#[allow(clippy::too_many_arguments)]
 pub fn script_add_synchronizer<H: ScriptHost + 'static>(
     query: Query<
         (
@@ -34,6 +36,7 @@ pub fn script_add_synchronizer<H: ScriptHost + 'static>(
     mut contexts: ResMut<ScriptContexts<H::ScriptContext>>,
     mut event_writer: EventWriter<ScriptLoaded>,
     mut error_writer: EventWriter<ScriptErrorEvent>,
+    mut commands: Commands,
 ) {
     debug!("Handling addition/modification of scripts");
 
@@ -90,7 +93,10 @@ pub fn script_add_synchronizer<H: ScriptHost + 'static>(
                 )
             }
         }
-    })
+    });
+
+    // commands queued up by scripts while they are loaded and set up are applied along with this system's commands
+    commands.queue(ScriptCommandQueue::apply);
 }
 
 /// Handles the removal of script components and their contexts
@@ -115,6 +121,7 @@ pub fn script_remove_synchronizer<H: ScriptHost>(
 }
 
 /// Reloads hot-reloaded scripts, or loads missing contexts for scripts which were added but not loaded
+#[allow(clippy::too_many_arguments)]
 pub fn script_hot_reload_handler<H: ScriptHost>(
     mut events: EventReader<AssetEvent<H::ScriptAsset>>,
     mut host: ResMut<H>,
@@ -124,6 +131,7 @@ pub fn script_hot_reload_handler<H: ScriptHost>(
     mut contexts: ResMut<ScriptContexts<H::ScriptContext>>,
     mut event_writer: EventWriter<ScriptLoaded>,
     mut error_writer: EventWriter<ScriptErrorEvent>,
+    mut commands: Commands,
 ) {
     for e in events.read() {
         let (handle, created) = match e {
@@ -157,6 +165,8 @@ pub fn script_hot_reload_handler<H: ScriptHost>(
             }
         }
     }
+
+    commands.queue(ScriptCommandQueue::apply);
 }
 
 /// Lets the script host handle all script events
@@ -212,6 +222,8 @@ pub fn script_event_handler<H: ScriptHost, const MAX: u32, const MIN: u32>(world
     world.insert_resource(ctxts);
     world.insert_resource(host);
     world.insert_resource(providers);
+
+    ScriptCommandQueue::apply(world);
 }
 
 #[derive(Resource)]
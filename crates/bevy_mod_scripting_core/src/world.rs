@@ -1,9 +1,10 @@
 use std::ops::Deref;
 use std::sync::Arc;
 
-use bevy::prelude::World;
+use bevy::ecs::world::{Command, CommandQueue};
+use bevy::prelude::{Resource, World};
 use parking_lot::{
-    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
 };
 
 /// Pointer to a bevy world, safely allows multiple access via RwLock
@@ -157,3 +158,26 @@ impl WorldPointer {
         ))
     }
 }
+
+/// Commands queued up by scripts, applied once the system running the scripts is done with the world.
+///
+/// Cloning the queue is cheap, all clones push to the same queue.
+#[derive(Resource, Default, Clone)]
+pub struct ScriptCommandQueue(Arc<Mutex<CommandQueue>>);
+
+impl ScriptCommandQueue {
+    /// Queues up a command
+    pub fn push(&self, command: impl Command) {
+        self.0.lock().push(command);
+    }
+
+    /// Applies all commands queued up so far, in order
+    pub fn apply(world: &mut World) {
+        let Some(queue) = world.get_resource::<Self>() else {
+            return;
+        };
+
+        let mut commands = std::mem::take(&mut *queue.0.lock());
+        commands.apply(world);
+    }
+}
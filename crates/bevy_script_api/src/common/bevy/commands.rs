@@ -0,0 +1,207 @@
+use bevy::prelude::{
+    BuildChildren, DespawnRecursive, Entity, ReflectComponent, ReflectResource, World,
+};
+use bevy_mod_scripting_core::{
+    prelude::{ScriptError, ScriptErrorEvent},
+    world::ScriptCommandQueue,
+};
+
+use super::{ScriptTypeRegistration, ScriptWorld};
+
+/// Deferred world access for scripts.
+///
+/// Instead of locking the world, every change is pushed onto the [`ScriptCommandQueue`],
+/// which is applied after the system running the script finishes.
+/// Entities spawned via commands receive their id immediately, so further commands can refer to them.
+///
+/// Failures of queued commands are reported via [`ScriptErrorEvent`]s once they are applied.
+#[derive(Clone)]
+pub struct ScriptCommands {
+    world: ScriptWorld,
+    queue: ScriptCommandQueue,
+}
+
+impl std::fmt::Debug for ScriptCommands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptCommands").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for ScriptCommands {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Commands")
+    }
+}
+
+impl ScriptCommands {
+    pub fn new(world: ScriptWorld, queue: ScriptCommandQueue) -> Self {
+        Self { world, queue }
+    }
+
+    /// Reserves an entity id, the entity is spawned once the commands are applied
+    pub fn spawn(&self) -> Entity {
+        self.world.read().entities().reserve_entity()
+    }
+
+    pub fn despawn(&self, entity: Entity) {
+        self.queue.push(move |world: &mut World| {
+            world.despawn(entity);
+        });
+    }
+
+    pub fn despawn_recursive(&self, entity: Entity) {
+        self.queue.push(DespawnRecursive { entity, warn: true });
+    }
+
+    pub fn add_default_component(
+        &self,
+        entity: Entity,
+        comp_type: ScriptTypeRegistration,
+    ) -> Result<(), ScriptError> {
+        Self::check_component(&comp_type)?;
+
+        self.queue.push(move |world: &mut World| {
+            if let Err(error) = ScriptWorld::insert_default_component(world, entity, &comp_type) {
+                report_command_error(world, error);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn remove_component(
+        &self,
+        entity: Entity,
+        comp_type: ScriptTypeRegistration,
+    ) -> Result<(), ScriptError> {
+        Self::check_component(&comp_type)?;
+
+        self.queue.push(move |world: &mut World| {
+            if let Err(error) = ScriptWorld::remove_component_from(world, entity, &comp_type) {
+                report_command_error(world, error);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn remove_resource(&self, res_type: ScriptTypeRegistration) -> Result<(), ScriptError> {
+        if res_type.script_resource().is_none() && res_type.data::<ReflectResource>().is_none() {
+            return Err(ScriptError::Other(format!(
+                "Not a resource {}",
+                res_type.short_name()
+            )));
+        }
+
+        self.queue.push(move |world: &mut World| {
+            if let Err(error) = ScriptWorld::remove_resource_from(world, &res_type) {
+                report_command_error(world, error);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn push_child(&self, parent: Entity, child: Entity) {
+        self.queue.push(move |world: &mut World| {
+            if let Ok(mut entity) = world.get_entity_mut(parent) {
+                entity.add_children(&[child]);
+            }
+        });
+    }
+
+    pub fn remove_children(&self, parent: Entity, children: Vec<Entity>) {
+        self.queue.push(move |world: &mut World| {
+            if let Ok(mut entity) = world.get_entity_mut(parent) {
+                entity.remove_children(&children);
+            }
+        });
+    }
+
+    fn check_component(comp_type: &ScriptTypeRegistration) -> Result<(), ScriptError> {
+        if comp_type.script_component().is_none() && comp_type.data::<ReflectComponent>().is_none()
+        {
+            return Err(ScriptError::Other(format!(
+                "Not a component {}",
+                comp_type.short_name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn report_command_error(world: &mut World, error: ScriptError) {
+    bevy::log::error!("{}", error);
+    world.send_event(ScriptErrorEvent { error });
+}
+
+#[cfg(all(test, feature = "lua"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use bevy::prelude::*;
+    use bevy_mod_scripting_core::prelude::*;
+    use bevy_mod_scripting_lua::prelude::*;
+
+    use crate::providers::LuaBevyAPIProvider;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, Default)]
+    struct Spawned;
+
+    const SCRIPT: &[u8] = br#"
+        function on_update()
+            local commands = world:commands()
+            local entity = commands:spawn()
+            commands:add_default_component(entity, world:get_type_by_name("Spawned"))
+        end
+    "#;
+
+    #[test]
+    fn test_spawned_entity_exists_after_handler() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), ScriptingPlugin))
+            .register_type::<Spawned>()
+            .add_script_host::<LuaScriptHost<()>>(PostUpdate)
+            .add_api_provider::<LuaScriptHost<()>>(Box::new(LuaBevyAPIProvider))
+            .add_script_handler::<LuaScriptHost<()>, 0, 0>(PostUpdate)
+            .add_systems(Update, |mut events: PriorityEventWriter<LuaEvent<()>>| {
+                events.send(
+                    LuaEvent {
+                        hook_name: "on_update".to_owned(),
+                        args: (),
+                        recipients: Recipients::All,
+                    },
+                    0,
+                )
+            });
+
+        let world = app.world_mut();
+        let script_data = ScriptData {
+            sid: 0,
+            entity: Entity::PLACEHOLDER,
+            name: "test.lua",
+        };
+        let ctx = world.resource_scope(|world, mut host: Mut<LuaScriptHost<()>>| {
+            let mut providers = world.resource_mut::<APIProviders<LuaScriptHost<()>>>();
+            let mut ctx = host
+                .load_script(SCRIPT, &script_data, &mut providers)
+                .unwrap();
+            host.setup_script(&script_data, &mut ctx, &mut providers)
+                .unwrap();
+            ctx
+        });
+        world
+            .resource_mut::<ScriptContexts<Mutex<Lua>>>()
+            .insert_context(script_data, Some(ctx));
+
+        app.update();
+
+        let world = app.world_mut();
+        assert!(world.resource::<Events<ScriptErrorEvent>>().is_empty());
+        assert_eq!(
+            world
+                .query_filtered::<Entity, With<Spawned>>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+}
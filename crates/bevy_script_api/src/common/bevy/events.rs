@@ -0,0 +1,220 @@
+use std::{any::TypeId, collections::HashMap, marker::PhantomData, sync::Arc};
+
+use bevy::{
+    ecs::world::DeferredWorld,
+    prelude::*,
+    reflect::{FromReflect, FromType, PartialReflect},
+};
+use bevy_mod_scripting_core::{
+    hosts::ScriptContexts,
+    prelude::{ScriptError, ScriptErrorEvent},
+};
+use parking_lot::RwLock;
+
+/// A type erased value shared with scripts, see [`ReflectReference::new_shared_ref`](crate::ReflectReference::new_shared_ref)
+pub type SharedReflect = Arc<RwLock<Box<dyn Reflect>>>;
+
+/// A copy of an event delivered to an observer created via [`ReflectEvent::observer`]
+pub struct ObservedEvent {
+    /// the observer entity which received the event
+    pub observer: Entity,
+    /// the event
+    pub event: SharedReflect,
+    /// the entity the event was triggered for, [`Entity::PLACEHOLDER`] if it was triggered globally
+    pub target: Entity,
+}
+
+/// The function called by observers created via [`ReflectEvent::observer`]
+pub type ObservedEventHandler = Box<dyn Fn(DeferredWorld, ObservedEvent) + Send + Sync>;
+
+/// Type data allowing scripts to observe, trigger, send and read events of the reflected type.
+///
+/// Register it on your events via `#[reflect(Event)]`:
+/// ```rust,ignore
+/// #[derive(Event, Reflect, Default)]
+/// #[reflect(Event, Default)]
+/// struct Explosion { radius: f32 }
+///
+/// // sending and reading requires the event to be added as usual
+/// app.add_event::<Explosion>().register_type::<Explosion>();
+/// ```
+#[derive(Clone)]
+pub struct ReflectEvent {
+    from_reflect: fn(&dyn PartialReflect) -> Option<SharedReflect>,
+    observer: fn(ObservedEventHandler) -> Observer,
+    trigger: fn(&mut World, &dyn PartialReflect, &[Entity]) -> Result<(), ScriptError>,
+    send: fn(&mut World, &dyn PartialReflect) -> Result<(), ScriptError>,
+    read: fn(&World, &mut usize) -> Result<Vec<SharedReflect>, ScriptError>,
+}
+
+impl ReflectEvent {
+    /// Constructs a new event from the given value, returns `None` if the value does not represent this event
+    pub fn from_reflect(&self, value: &dyn PartialReflect) -> Option<SharedReflect> {
+        (self.from_reflect)(value)
+    }
+
+    /// Creates an observer for this event, which calls the handler with a copy of every observed event.
+    ///
+    /// The observer watches all entities until it is given some via [`Observer::with_entity`].
+    pub fn observer(&self, handler: ObservedEventHandler) -> Observer {
+        (self.observer)(handler)
+    }
+
+    /// Triggers the given value as this event for the given targets, or globally if there are none.
+    pub fn trigger(
+        &self,
+        world: &mut World,
+        event: &dyn PartialReflect,
+        targets: &[Entity],
+    ) -> Result<(), ScriptError> {
+        (self.trigger)(world, event, targets)
+    }
+
+    /// Sends the given value as this event via its `Events` resource
+    pub fn send(&self, world: &mut World, event: &dyn PartialReflect) -> Result<(), ScriptError> {
+        (self.send)(world, event)
+    }
+
+    /// Returns copies of all events sent since the given cursor, and advances the cursor.
+    ///
+    /// A cursor starting at 0 reads all events still stored in the `Events` resource, same as a new `EventReader`.
+    pub fn read(
+        &self,
+        world: &World,
+        cursor: &mut usize,
+    ) -> Result<Vec<SharedReflect>, ScriptError> {
+        (self.read)(world, cursor)
+    }
+}
+
+impl<E: Event + Reflect + FromReflect> FromType<E> for ReflectEvent {
+    fn from_type() -> Self {
+        Self {
+            from_reflect: |value| {
+                E::from_reflect(value).map(|event| Arc::new(RwLock::new(Box::new(event) as _)))
+            },
+            observer: |handler| {
+                Observer::new(move |trigger: Trigger<E>, mut world: DeferredWorld| {
+                    let event = match copy_event(trigger.event()) {
+                        Ok(event) => event,
+                        Err(error) => {
+                            error!("{}", error);
+                            world.send_event(ScriptErrorEvent { error });
+                            return;
+                        }
+                    };
+
+                    handler(
+                        world,
+                        ObservedEvent {
+                            observer: trigger.observer(),
+                            event,
+                            target: trigger.entity(),
+                        },
+                    )
+                })
+            },
+            trigger: |world, event, targets| {
+                let event = E::from_reflect(event).ok_or_else(|| {
+                    ScriptError::Other(format!(
+                        "Value is not a valid `{}` event",
+                        std::any::type_name::<E>()
+                    ))
+                })?;
+
+                if targets.is_empty() {
+                    world.trigger(event);
+                } else {
+                    world.trigger_targets(event, targets.to_vec());
+                }
+
+                Ok(())
+            },
+            send: |world, event| {
+                let event = E::from_reflect(event).ok_or_else(|| {
+                    ScriptError::Other(format!(
+                        "Value is not a valid `{}` event",
+                        std::any::type_name::<E>()
+                    ))
+                })?;
+
+                world
+                    .get_resource_mut::<Events<E>>()
+                    .ok_or_else(|| missing_events::<E>())?
+                    .send(event);
+                Ok(())
+            },
+            read: |world, cursor| {
+                let events = world
+                    .get_resource::<Events<E>>()
+                    .ok_or_else(|| missing_events::<E>())?;
+
+                let newest = events.oldest_event_count() + events.len();
+                let read = ((*cursor).max(events.oldest_event_count())..newest)
+                    .filter_map(|id| events.get_event(id))
+                    .map(|(event, _)| copy_event(event))
+                    .collect();
+
+                // events which could not be copied are skipped by the next read
+                *cursor = newest;
+                read
+            },
+        }
+    }
+}
+
+/// Copies the event so it can be handed to scripts
+fn copy_event<E: Event + Reflect + FromReflect>(event: &E) -> Result<SharedReflect, ScriptError> {
+    E::from_reflect(event)
+        .map(|event| Arc::new(RwLock::new(Box::new(event) as _)))
+        .ok_or_else(|| {
+            ScriptError::Other(format!(
+                "Event `{}` could not be copied, make sure its `FromReflect` implementation is correct",
+                std::any::type_name::<E>()
+            ))
+        })
+}
+
+fn missing_events<E: Event>() -> ScriptError {
+    ScriptError::Other(format!(
+        "No `Events` resource exists for `{}`, did you forget to call `app.add_event`?",
+        std::any::type_name::<E>()
+    ))
+}
+
+/// Tracks the events read by each script running in contexts of type `C`, per event type.
+///
+/// Readers belonging to scripts which were removed are dropped the next time any script reads events.
+#[derive(Resource)]
+pub struct ScriptEventReaders<C> {
+    cursors: HashMap<(u32, TypeId), usize>,
+    _ph: PhantomData<fn() -> C>,
+}
+
+impl<C> Default for ScriptEventReaders<C> {
+    fn default() -> Self {
+        Self {
+            cursors: Default::default(),
+            _ph: PhantomData,
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> ScriptEventReaders<C> {
+    /// Reads the events of the given type sent since the given script last read them
+    pub fn read(
+        &mut self,
+        world: &World,
+        sid: u32,
+        event_type: TypeId,
+        reflect_event: &ReflectEvent,
+    ) -> Result<Vec<SharedReflect>, ScriptError> {
+        if let Some(contexts) = world.get_resource::<ScriptContexts<C>>() {
+            self.cursors
+                .retain(|(sid, _), _| contexts.context_entities.contains_key(sid));
+        }
+
+        let cursor = self.cursors.entry((sid, event_type)).or_default();
+        reflect_event.read(world, cursor)
+    }
+}
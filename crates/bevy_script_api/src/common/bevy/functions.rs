@@ -0,0 +1,232 @@
+use bevy::{
+    ecs::reflect::AppFunctionRegistry,
+    prelude::AppTypeRegistry,
+    reflect::{
+        func::{args::Ownership, ArgList, DynamicFunction, Return},
+        Reflect, TypeRegistry,
+    },
+};
+use bevy_mod_scripting_core::prelude::ScriptError;
+
+use crate::{script_value::from_reflect_with_fallback, ReflectReference, ScriptValue};
+
+use super::ScriptWorld;
+
+impl ScriptWorld {
+    /// Returns true if a function with the given name is registered in the [`AppFunctionRegistry`]
+    pub fn has_function(&self, name: &str) -> bool {
+        self.read()
+            .get_resource::<AppFunctionRegistry>()
+            .is_some_and(|functions| functions.read().contains(name))
+    }
+
+    /// Returns the name of the function implementing the given method of the referenced value's type, if one is registered.
+    ///
+    /// Methods are functions registered under the type path of their type followed by the method name,
+    /// which is the name given to them by [`App::register_function`](bevy::app::App::register_function), e.g. `my_game::Health::heal`.
+    pub fn method_name(
+        &self,
+        receiver: &ReflectReference,
+        method: &str,
+    ) -> Result<Option<String>, ScriptError> {
+        let name = format!(
+            "{}::{method}",
+            receiver
+                .get(|value| value.reflect_type_path().to_owned())
+                .map_err(|e| ScriptError::Other(e.to_string()))?
+        );
+        Ok(self.has_function(&name).then_some(name))
+    }
+
+    /// Calls the function registered under the given name in the [`AppFunctionRegistry`].
+    ///
+    /// Arguments are converted to the types the function expects, see [`ScriptValue::into_reflect`],
+    /// and arguments taken by reference are passed references to the converted values.
+    /// Returns a reference owning the returned value, or `None` if the function returns `()`.
+    pub fn call_function(
+        &self,
+        name: &str,
+        args: Vec<ScriptValue>,
+    ) -> Result<Option<ReflectReference>, ScriptError> {
+        let function = self.function(name)?;
+        let registry = self.read().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut args = convert_args(&function, 0, args, &registry)?;
+        let returned = function
+            .call(push_args(ArgList::new(), &mut args))
+            .map_err(|e| ScriptError::Other(format!("Calling `{name}` failed. {e}")))
+            .and_then(|returned| convert_return(returned, &registry))?;
+
+        Ok(returned.map(|value| ReflectReference::new_owned_ref(value, self.clone().into())))
+    }
+
+    /// Calls a method of the referenced value's type, see [`Self::method_name`] and [`Self::call_function`].
+    ///
+    /// The referenced value is passed as the first argument, methods taking `&mut self` modify it in place.
+    pub fn call_method(
+        &self,
+        receiver: &ReflectReference,
+        method: &str,
+        args: Vec<ScriptValue>,
+    ) -> Result<Option<ReflectReference>, ScriptError> {
+        let name = self.method_name(receiver, method)?.ok_or_else(|| {
+            ScriptError::Other(format!(
+                "No method `{method}` is registered for `{}`",
+                receiver.path
+            ))
+        })?;
+        let function = self.function(&name)?;
+        let registry = self.read().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let mut args = convert_args(&function, 1, args, &registry)?;
+        let mut call = |receiver: ArgList| {
+            function
+                .call(push_args(receiver, &mut args))
+                .map_err(|e| ScriptError::Other(format!("Calling `{name}` failed. {e}")))
+                .and_then(|returned| convert_return(returned, &registry))
+        };
+
+        let ownership = function
+            .info()
+            .args()
+            .first()
+            .map(|arg| arg.ownership())
+            .ok_or_else(|| {
+                ScriptError::Other(format!(
+                    "`{name}` takes no arguments, so it is not a method"
+                ))
+            })?;
+
+        let returned = match ownership {
+            Ownership::Ref => {
+                receiver.get(|value| call(ArgList::new().push_ref(value.as_partial_reflect())))
+            }
+            Ownership::Mut => receiver
+                .clone()
+                .get_mut(|value| call(ArgList::new().push_mut(value.as_partial_reflect_mut()))),
+            Ownership::Owned => receiver.get(|value| {
+                let value = from_reflect_with_fallback(
+                    value.as_partial_reflect(),
+                    registry.get(value.type_id()).ok_or_else(|| {
+                        ScriptError::Other(format!(
+                            "`{}` is not registered",
+                            value.reflect_type_path()
+                        ))
+                    })?,
+                )
+                .map_err(|e| ScriptError::Other(e.to_string()))?;
+                call(ArgList::new().push_boxed(value.into_partial_reflect()))
+            }),
+        }
+        .map_err(|e| ScriptError::Other(e.to_string()))??;
+
+        Ok(returned.map(|value| ReflectReference::new_owned_ref(value, self.clone().into())))
+    }
+
+    fn function(&self, name: &str) -> Result<DynamicFunction<'static>, ScriptError> {
+        self.read()
+            .get_resource::<AppFunctionRegistry>()
+            .and_then(|functions| functions.read().get(name).cloned())
+            .ok_or_else(|| ScriptError::Other(format!("No function `{name}` is registered")))
+    }
+}
+
+/// An argument converted to the type a function expects, owned arguments are taken when passed to the function
+type ConvertedArg = (Ownership, Option<Box<dyn Reflect>>);
+
+/// Converts the arguments given by a script to the argument types of the function, starting at the argument with the given index
+fn convert_args(
+    function: &DynamicFunction,
+    skip: usize,
+    args: Vec<ScriptValue>,
+    registry: &TypeRegistry,
+) -> Result<Vec<ConvertedArg>, ScriptError> {
+    let name = function
+        .name()
+        .map(|name| name.as_ref())
+        .unwrap_or("function");
+    let expected = &function.info().args()[skip.min(function.info().arg_count())..];
+    if expected.len() != args.len() {
+        return Err(ScriptError::Other(format!(
+            "`{name}` expects {} arguments, got {}",
+            expected.len(),
+            args.len()
+        )));
+    }
+
+    expected
+        .iter()
+        .zip(args)
+        .map(|(arg, value)| {
+            // arguments taken by reference have the type path of the reference type
+            let type_path = arg.type_path();
+            let type_path = type_path
+                .strip_prefix("&mut ")
+                .or_else(|| type_path.strip_prefix('&'))
+                .unwrap_or(type_path);
+
+            let registration = registry.get_with_type_path(type_path).ok_or_else(|| {
+                ScriptError::Other(format!(
+                    "The type `{type_path}` of argument {} of `{name}` is not registered",
+                    arg.index()
+                ))
+            })?;
+
+            let value = value.into_reflect(registration, registry).map_err(|e| {
+                ScriptError::Other(format!("Argument {} of `{name}`. {e}", arg.index()))
+            })?;
+            Ok((arg.ownership(), Some(value)))
+        })
+        .collect()
+}
+
+/// Appends the converted arguments to the argument list, owned arguments are moved into the list
+fn push_args<'a>(mut list: ArgList<'a>, args: &'a mut [ConvertedArg]) -> ArgList<'a> {
+    for (ownership, value) in args {
+        list = match (ownership, value) {
+            (Ownership::Owned, value) => match value.take() {
+                Some(value) => list.push_boxed(value.into_partial_reflect()),
+                None => list,
+            },
+            (Ownership::Ref, Some(value)) => list.push_ref(value.as_partial_reflect()),
+            (Ownership::Mut, Some(value)) => list.push_mut(value.as_partial_reflect_mut()),
+            (_, None) => list,
+        };
+    }
+    list
+}
+
+/// Converts the value returned by a function to a value owned by scripts, returned references are cloned
+fn convert_return(
+    returned: Return,
+    registry: &TypeRegistry,
+) -> Result<Option<Box<dyn Reflect>>, ScriptError> {
+    if returned.is_unit() {
+        return Ok(None);
+    }
+
+    let value = match returned {
+        Return::Owned(value) => match value.try_into_reflect() {
+            Ok(value) => return Ok(Some(value)),
+            Err(value) => value,
+        },
+        Return::Ref(value) => value.clone_value(),
+        Return::Mut(value) => value.clone_value(),
+    };
+
+    let registration = value
+        .get_represented_type_info()
+        .and_then(|info| registry.get(info.type_id()))
+        .ok_or_else(|| {
+            ScriptError::Other(format!(
+                "The returned type `{}` is not registered",
+                value.reflect_type_path()
+            ))
+        })?;
+
+    from_reflect_with_fallback(value.as_ref(), registration)
+        .map(Some)
+        .map_err(|e| ScriptError::Other(e.to_string()))
+}
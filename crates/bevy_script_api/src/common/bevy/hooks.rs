@@ -0,0 +1,220 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{component::ComponentId, component::Tick, query::QueryBuilder, schedule::ScheduleLabel},
+    prelude::*,
+};
+use bevy_mod_scripting_core::hosts::ScriptContexts;
+
+use super::{
+    systems::{
+        ScriptCallback, ScriptFunctionCall, ScriptFunctionRef, ScriptSystemArg, ScriptSystemHost,
+    },
+    ScriptTypeRegistration,
+};
+
+/// The system set containing the runners for script change hooks
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScriptChangeHookSet;
+
+/// The kind of component change a script can subscribe to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentChange {
+    /// the component was added to an entity
+    Added,
+    /// the component was added to an entity or mutably accessed, same as Bevy's `Changed` filter
+    Changed,
+    /// the component was removed from an entity or the entity was despawned
+    Removed,
+}
+
+/// A subscription of a script function to changes of a component.
+/// The callback is a function value handed over by the script, it receives the entity whose component changed.
+#[derive(Clone, Debug)]
+pub struct ScriptChangeHook {
+    /// the id of the script owning the callback
+    pub sid: u32,
+    /// the kind of change the hook reacts to
+    pub change: ComponentChange,
+    /// the component to watch
+    pub component: ScriptTypeRegistration,
+    /// the function to call
+    pub callback: ScriptFunctionRef,
+}
+
+struct RegisteredHook {
+    hook: ScriptChangeHook,
+    component_id: ComponentId,
+    state: QueryState<EntityRef<'static>>,
+    last_run: Tick,
+}
+
+/// Stores the change hooks registered by scripts running in contexts of type `C`.
+///
+/// Additions and changes are detected via change ticks, every hook only sees changes made since it last ran.
+/// Changes made by the change hook callbacks themselves do not trigger hooks.
+/// Removals are collected by observers, spawned once per watched component.
+///
+/// Hooks belonging to scripts which were removed are dropped the next time the hooks run.
+#[derive(Resource)]
+pub struct ScriptChangeHooks<C> {
+    hooks: Vec<RegisteredHook>,
+    observed: Vec<ComponentId>,
+    removed: Vec<(ComponentId, Entity)>,
+    _ph: PhantomData<fn() -> C>,
+}
+
+impl<C> Default for ScriptChangeHooks<C> {
+    fn default() -> Self {
+        Self {
+            hooks: Default::default(),
+            observed: Default::default(),
+            removed: Default::default(),
+            _ph: PhantomData,
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> ScriptChangeHooks<C> {
+    /// Registers a new hook for the component with the given id, replacing any identical hook.
+    /// The hook only reacts to changes made after it was registered.
+    ///
+    /// Returns an observer which needs to be spawned into the world if removals of this component are not observed yet.
+    pub fn insert(
+        &mut self,
+        world: &mut World,
+        hook: ScriptChangeHook,
+        component_id: ComponentId,
+    ) -> Option<Observer> {
+        self.hooks.retain(|h| {
+            !(h.hook.sid == hook.sid
+                && h.hook.change == hook.change
+                && h.component_id == component_id
+                && h.hook.callback == hook.callback)
+        });
+
+        let observer = (hook.change == ComponentChange::Removed
+            && !self.observed.contains(&component_id))
+        .then(|| {
+            self.observed.push(component_id);
+            Observer::new(
+                move |trigger: Trigger<OnRemove>, mut hooks: ResMut<ScriptChangeHooks<C>>| {
+                    hooks.removed.push((component_id, trigger.entity()));
+                },
+            )
+            .with_component(component_id)
+        });
+
+        self.hooks.push(RegisteredHook {
+            hook,
+            component_id,
+            state: QueryBuilder::new(world).with_id(component_id).build(),
+            last_run: world.change_tick(),
+        });
+
+        observer
+    }
+
+    /// Removes all hooks of the given script calling the function with the given [id](ScriptFunctionRef::id), returns true if any existed.
+    pub fn remove(&mut self, sid: u32, callback: &str) -> bool {
+        let len = self.hooks.len();
+        self.hooks
+            .retain(|h| !(h.hook.sid == sid && h.hook.callback.id() == callback));
+        len != self.hooks.len()
+    }
+
+    /// Returns all hooks in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ScriptChangeHook> {
+        self.hooks.iter().map(|h| &h.hook)
+    }
+
+    /// Drops the hooks of scripts which no longer exist and collects the calls for all changes since the last run.
+    pub fn prepare_run(&mut self, world: &World) -> Vec<ScriptFunctionCall> {
+        if let Some(contexts) = world.get_resource::<ScriptContexts<C>>() {
+            self.hooks
+                .retain(|h| contexts.context_entities.contains_key(&h.hook.sid));
+        }
+
+        let this_run = world.read_change_tick();
+        let removed = std::mem::take(&mut self.removed);
+        let mut calls = Vec::default();
+
+        for registered in &mut self.hooks {
+            let RegisteredHook {
+                hook,
+                component_id,
+                state,
+                last_run,
+            } = registered;
+
+            let mut call = |entity| {
+                calls.push(ScriptFunctionCall {
+                    sid: hook.sid,
+                    callback: ScriptCallback::Value(hook.callback.clone()),
+                    arg: ScriptSystemArg::Entity(entity),
+                })
+            };
+
+            match hook.change {
+                ComponentChange::Added | ComponentChange::Changed => {
+                    for entity_ref in state.iter(world) {
+                        let Some(ticks) = entity_ref.get_change_ticks_by_id(*component_id) else {
+                            continue;
+                        };
+
+                        let triggered = match hook.change {
+                            ComponentChange::Added => ticks.is_added(*last_run, this_run),
+                            _ => ticks.is_changed(*last_run, this_run),
+                        };
+
+                        if triggered {
+                            call(entity_ref.id());
+                        }
+                    }
+                }
+                ComponentChange::Removed => removed
+                    .iter()
+                    .filter(|(id, _)| *id == *component_id)
+                    .for_each(|(_, entity)| call(*entity)),
+            }
+
+            *last_run = this_run;
+        }
+
+        calls
+    }
+}
+
+/// Runs the change hooks of the given host
+pub fn run_script_change_hooks<H: ScriptSystemHost>(world: &mut World) {
+    let calls = world.resource_scope(
+        |world, mut hooks: Mut<ScriptChangeHooks<H::ScriptContext>>| hooks.prepare_run(world),
+    );
+
+    if !calls.is_empty() {
+        H::call_script_functions(world, calls);
+    }
+}
+
+pub trait AddScriptChangeHooks {
+    /// Enables change hooks for scripts of the given host.
+    ///
+    /// Every frame the hooks are run in the given schedule, in the [`ScriptChangeHookSet`] set.
+    fn add_script_change_hooks<H: ScriptSystemHost>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> &mut Self;
+}
+
+impl AddScriptChangeHooks for App {
+    fn add_script_change_hooks<H: ScriptSystemHost>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> &mut Self {
+        self.init_resource::<ScriptChangeHooks<H::ScriptContext>>()
+            .add_systems(
+                schedule,
+                run_script_change_hooks::<H>.in_set(ScriptChangeHookSet),
+            )
+    }
+}
@@ -1,28 +1,47 @@
-use crate::ReflectReference;
+use crate::{ReflectReference, ScriptValue};
 /// Common functionality for all script hosts
 use bevy::{
     ecs::{
-        component::ComponentId,
-        query::QueryBuilder,
-        world::{Command, EntityRef, World},
+        reflect::ReflectFromWorld,
+        world::{Command, World},
     },
     prelude::{
         AppTypeRegistry, BuildChildren, Children, DespawnChildrenRecursive, DespawnRecursive,
-        Entity, Parent, ReflectComponent, ReflectDefault, ReflectResource,
+        Entity, Mut, Parent, ReflectComponent, ReflectDefault, ReflectResource,
     },
     reflect::{
         DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct,
-        DynamicTuple, DynamicTupleStruct, TypeRegistration,
+        DynamicTuple, DynamicTupleStruct, PartialReflect, Reflect, TypeRegistration,
     },
 };
-use bevy_mod_scripting_core::{prelude::ScriptError, world::WorldPointer};
-use parking_lot::MappedRwLockWriteGuard;
+use bevy_mod_scripting_core::{
+    prelude::ScriptError,
+    world::{ScriptCommandQueue, WorldPointer},
+};
 use std::{
-    any::Any,
     ops::{Deref, DerefMut},
     sync::Arc,
 };
 
+use self::{
+    commands::ScriptCommands,
+    events::{ObservedEvent, ReflectEvent, ScriptEventReaders, SharedReflect},
+    hooks::{ScriptChangeHook, ScriptChangeHooks},
+    observers::{ScriptObserver, ScriptObservers},
+    query::{ScriptPreparedQuery, ScriptQueryCache, ScriptQueryIter},
+    script_types::{ScriptStruct, ScriptTypeDescriptor, ScriptTypeKind, ScriptTypes},
+    systems::{ScriptSystem, ScriptSystems},
+};
+
+pub mod commands;
+pub mod events;
+pub mod functions;
+pub mod hooks;
+pub mod observers;
+pub mod query;
+pub mod script_types;
+pub mod systems;
+
 /// Helper trait for retrieving a world pointer from a script context.
 pub trait GetWorld {
     type Error;
@@ -30,35 +49,72 @@ pub trait GetWorld {
 }
 
 #[derive(Clone)]
-pub struct ScriptTypeRegistration(pub(crate) Arc<TypeRegistration>);
+#[cfg_attr(feature = "rune", derive(bevy_mod_scripting_rune::prelude::rune::Any))]
+#[cfg_attr(
+    feature = "rune",
+    rune(module = ::bevy_mod_scripting_rune::prelude::rune, item = ::bevy, name = TypeRegistration)
+)]
+pub struct ScriptTypeRegistration(
+    pub(crate) Arc<TypeRegistration>,
+    pub(crate) Option<Arc<ScriptTypeDescriptor>>,
+);
 
 impl ScriptTypeRegistration {
     pub fn new(arc: Arc<TypeRegistration>) -> Self {
-        Self(arc)
+        Self(arc, None)
+    }
+
+    /// Creates a registration for a type defined by a script, which is backed by a [`ScriptStruct`]
+    pub fn new_script_type(descriptor: Arc<ScriptTypeDescriptor>) -> Self {
+        Self(
+            Arc::new(TypeRegistration::of::<ScriptStruct>()),
+            Some(descriptor),
+        )
+    }
+
+    /// Returns the script defined type this registration refers to, if any
+    pub fn script_type(&self) -> Option<&Arc<ScriptTypeDescriptor>> {
+        self.1.as_ref()
+    }
+
+    pub(crate) fn script_component(&self) -> Option<&Arc<ScriptTypeDescriptor>> {
+        self.script_type()
+            .filter(|script_type| script_type.kind() == ScriptTypeKind::Component)
+    }
+
+    pub(crate) fn script_resource(&self) -> Option<&Arc<ScriptTypeDescriptor>> {
+        self.script_type()
+            .filter(|script_type| script_type.kind() == ScriptTypeKind::Resource)
     }
 
     #[inline(always)]
     pub fn short_name(&self) -> &str {
-        self.0.type_info().type_path_table().short_path()
+        match &self.1 {
+            Some(descriptor) => descriptor.name(),
+            None => self.0.type_info().type_path_table().short_path(),
+        }
     }
 
     #[inline(always)]
-    pub fn type_name(&self) -> &'static str {
-        self.0.type_info().type_path_table().path()
+    pub fn type_name(&self) -> &str {
+        match &self.1 {
+            Some(descriptor) => descriptor.name(),
+            None => self.0.type_info().type_path_table().path(),
+        }
     }
 }
 
 impl std::fmt::Debug for ScriptTypeRegistration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("ScriptTypeRegistration")
-            .field(&self.0.type_info().type_path())
+            .field(&self.type_name())
             .finish()
     }
 }
 
 impl std::fmt::Display for ScriptTypeRegistration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.0.type_info().type_path())
+        f.write_str(self.type_name())
     }
 }
 
@@ -70,52 +126,179 @@ impl Deref for ScriptTypeRegistration {
     }
 }
 
+/// A description of a query, independent of any world.
+/// Can be stored and run against the world later, e.g. by script systems.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptQuery {
+    pub components: Vec<ScriptTypeRegistration>,
+    pub with: Vec<ScriptTypeRegistration>,
+    pub without: Vec<ScriptTypeRegistration>,
+    /// components fetched if present (`Option<&T>`), returned after the required components
+    pub optional: Vec<ScriptTypeRegistration>,
+    /// components which need to have changed since the script's last run (`Changed<T>`)
+    pub changed: Vec<ScriptTypeRegistration>,
+    /// components which need to have been added since the script's last run (`Added<T>`)
+    pub added: Vec<ScriptTypeRegistration>,
+    /// groups of filters of which at least one needs to match (`Or<...>`)
+    pub or: Vec<ScriptQueryOr>,
+}
+
+impl ScriptQuery {
+    /// The names scripts use for the fetched components in query results, the required components followed by the optional ones
+    pub fn component_names(&self) -> Vec<String> {
+        self.components
+            .iter()
+            .chain(&self.optional)
+            .map(|component| match component.script_type() {
+                Some(script_type) => script_type.name().to_owned(),
+                None => component
+                    .type_info()
+                    .type_path_table()
+                    .ident()
+                    .unwrap_or_else(|| component.short_name())
+                    .to_owned(),
+            })
+            .collect()
+    }
+}
+
+/// A group of query filters of which at least one needs to match, like bevy's `Or` filter
+#[derive(Clone, Debug, Default)]
+pub struct ScriptQueryOr {
+    pub with: Vec<ScriptTypeRegistration>,
+    pub without: Vec<ScriptTypeRegistration>,
+    pub changed: Vec<ScriptTypeRegistration>,
+    pub added: Vec<ScriptTypeRegistration>,
+}
+
+/// Builds a query, the query is compiled the first time it runs and cached until it is modified
 #[derive(Clone)]
+#[cfg_attr(feature = "rune", derive(bevy_mod_scripting_rune::prelude::rune::Any))]
+#[cfg_attr(
+    feature = "rune",
+    rune(module = ::bevy_mod_scripting_rune::prelude::rune, item = ::bevy, name = QueryBuilder)
+)]
 pub struct ScriptQueryBuilder {
     world: ScriptWorld,
-    components: Vec<ScriptTypeRegistration>,
-    with: Vec<ScriptTypeRegistration>,
-    without: Vec<ScriptTypeRegistration>,
+    query: ScriptQuery,
+    sid: Option<u32>,
+    cache: ScriptQueryCache,
 }
 
 impl ScriptQueryBuilder {
     pub fn new(world: ScriptWorld) -> Self {
+        Self::from_query(world, Default::default())
+    }
+
+    /// Creates a builder from an existing query description
+    pub fn from_query(world: ScriptWorld, query: ScriptQuery) -> Self {
         Self {
             world,
-            components: vec![],
-            with: vec![],
-            without: vec![],
+            query,
+            sid: None,
+            cache: Default::default(),
         }
     }
 
+    /// Sets the cache holding the compiled query, e.g. to keep it between runs of a script system
+    pub fn with_cache(&mut self, cache: ScriptQueryCache) -> &mut Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Returns the cache holding the compiled query
+    pub fn cache(&self) -> &ScriptQueryCache {
+        &self.cache
+    }
+
+    /// Sets the script running this query, `Changed` and `Added` filters are relative to its last run
+    pub fn for_script(&mut self, sid: u32) -> &mut Self {
+        self.sid = Some(sid);
+        self
+    }
+
     pub fn components(&mut self, components: Vec<ScriptTypeRegistration>) -> &mut Self {
-        self.components.extend(components);
+        self.query.components.extend(components);
+        self.cache = Default::default();
         self
     }
 
     pub fn with(&mut self, with: Vec<ScriptTypeRegistration>) -> &mut Self {
-        self.with.extend(with);
+        self.query.with.extend(with);
+        self.cache = Default::default();
         self
     }
 
     pub fn without(&mut self, without: Vec<ScriptTypeRegistration>) -> &mut Self {
-        self.without.extend(without);
+        self.query.without.extend(without);
+        self.cache = Default::default();
+        self
+    }
+
+    pub fn optional(&mut self, optional: Vec<ScriptTypeRegistration>) -> &mut Self {
+        self.query.optional.extend(optional);
+        self.cache = Default::default();
+        self
+    }
+
+    pub fn changed(&mut self, changed: Vec<ScriptTypeRegistration>) -> &mut Self {
+        self.query.changed.extend(changed);
+        self.cache = Default::default();
+        self
+    }
+
+    pub fn added(&mut self, added: Vec<ScriptTypeRegistration>) -> &mut Self {
+        self.query.added.extend(added);
+        self.cache = Default::default();
+        self
+    }
+
+    pub fn or(&mut self, or: ScriptQueryOr) -> &mut Self {
+        self.query.or.push(or);
+        self.cache = Default::default();
         self
     }
 
+    /// Returns the query description built so far
+    pub fn query(&self) -> &ScriptQuery {
+        &self.query
+    }
+
+    /// Runs the query and collects its results
     pub fn build(&mut self) -> Result<Vec<ScriptQueryResult>, ScriptError> {
-        self.world.query(
-            std::mem::take(&mut self.components),
-            std::mem::take(&mut self.with),
-            std::mem::take(&mut self.without),
+        Ok(self.iter()?.collect())
+    }
+
+    /// Runs the query and returns an iterator which takes results from the world as it advances
+    pub fn iter(&self) -> Result<ScriptQueryIter, ScriptError> {
+        ScriptQueryIter::new(&self.world, &self.query, self.sid, &self.cache)
+    }
+
+    /// Compiles the query into a handle which can be kept and run repeatedly, sharing this builder's cache
+    pub fn prepare(&self) -> Result<ScriptPreparedQuery, ScriptError> {
+        ScriptPreparedQuery::new(
+            &self.world,
+            self.query.clone(),
+            self.sid,
+            self.cache.clone(),
         )
     }
 }
 
+/// The entity matched by a query, followed by references to its required components and its optional components
 #[derive(Clone)]
-pub struct ScriptQueryResult(pub Entity, pub Vec<ReflectReference>);
+pub struct ScriptQueryResult(
+    pub Entity,
+    pub Vec<ReflectReference>,
+    pub Vec<Option<ReflectReference>>,
+);
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "rune", derive(bevy_mod_scripting_rune::prelude::rune::Any))]
+#[cfg_attr(
+    feature = "rune",
+    rune(module = ::bevy_mod_scripting_rune::prelude::rune, item = ::bevy, name = World)
+)]
 pub struct ScriptWorld(WorldPointer);
 
 impl std::fmt::Display for ScriptWorld {
@@ -155,6 +338,22 @@ impl ScriptWorld {
         Self(ptr)
     }
 
+    /// Returns a handle for queueing up changes to the world, see [`ScriptCommands`]
+    pub fn commands(&self) -> Result<ScriptCommands, ScriptError> {
+        let queue = self
+            .read()
+            .get_resource::<ScriptCommandQueue>()
+            .cloned()
+            .ok_or_else(|| {
+                ScriptError::Other(
+                    "No `ScriptCommandQueue` exists, did you forget to add the `ScriptingPlugin`?"
+                        .to_owned(),
+                )
+            })?;
+
+        Ok(ScriptCommands::new(self.clone(), queue))
+    }
+
     pub fn get_children(&self, parent: Entity) -> Vec<Entity> {
         let w = self.read();
         w.get::<Children>(parent)
@@ -211,6 +410,48 @@ impl ScriptWorld {
             .get_with_short_type_path(type_name)
             .or_else(|| registry.get_with_type_path(type_name))
             .map(|registration| ScriptTypeRegistration::new(Arc::new(registration.clone())))
+            .or_else(|| {
+                w.get_resource::<ScriptTypes>()
+                    .and_then(|types| types.get(type_name))
+                    .cloned()
+            })
+    }
+
+    /// Defines a new component or resource type with the given fields, each field needs to have a rust type.
+    ///
+    /// The type is registered with the world as a dynamic component or resource storing a [`ScriptStruct`],
+    /// and can be used with all component and resource methods and queries like rust types.
+    ///
+    /// Script defined types live in the world only, they are not added to the [`AppTypeRegistry`](bevy::prelude::AppTypeRegistry).
+    /// The registry tells types apart by their `TypeId`, which all script defined types share, so they cannot have
+    /// `ReflectComponent`, `ReflectResource` or `ReflectSerialize` type data. Inspectors, `DynamicScene`s and the reflect serializers skip them,
+    /// data which needs to be saved has to be kept in rust types.
+    ///
+    /// Defining a type again with the same fields returns the existing type, so scripts can define their types every time they are loaded.
+    pub fn register_script_type(
+        &self,
+        name: String,
+        kind: ScriptTypeKind,
+        mut fields: Vec<(String, ScriptTypeRegistration)>,
+    ) -> Result<ScriptTypeRegistration, ScriptError> {
+        let mut w = self.write();
+
+        {
+            let registry = w.resource::<AppTypeRegistry>().read();
+            if registry.get_with_short_type_path(&name).is_some()
+                || registry.get_with_type_path(&name).is_some()
+            {
+                return Err(ScriptError::Other(format!(
+                    "Cannot define type `{name}`, a rust type with this name exists"
+                )));
+            }
+        }
+
+        fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        w.init_resource::<ScriptTypes>();
+        w.resource_scope(|w, mut types: Mut<ScriptTypes>| types.register(name, kind, fields, w))
+            .map_err(|e| ScriptError::Other(e.to_string()))
     }
 
     pub fn add_default_component(
@@ -218,46 +459,121 @@ impl ScriptWorld {
         entity: Entity,
         comp_type: ScriptTypeRegistration,
     ) -> Result<ReflectReference, ScriptError> {
-        let mut w = self.write();
+        Self::insert_default_component(&mut self.write(), entity, &comp_type)?;
+        self.component_ref(&comp_type, entity)
+    }
 
-        // Remove: AppTypeRegistry
-        let registry: AppTypeRegistry = w.remove_resource().unwrap();
+    /// Inserts a default instance of the given component into the entity
+    pub(crate) fn insert_default_component(
+        w: &mut World,
+        entity: Entity,
+        comp_type: &ScriptTypeRegistration,
+    ) -> Result<(), ScriptError> {
+        if let Some(script_type) = comp_type.script_component() {
+            let registry = w.resource::<AppTypeRegistry>().clone();
+            let component = script_type
+                .construct(ScriptValue::Unit, &registry.read())
+                .map_err(|e| ScriptError::Other(e.to_string()))?;
+
+            let mut entity_ref = w.get_entity_mut(entity).map_err(|e| {
+                ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity))
+            })?;
+            script_type.insert(&mut entity_ref, component);
+            return Ok(());
+        }
 
+        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
+        })?;
+
+        // the registry is taken out of the world while we hold a mutable reference to the entity
+        w.resource_scope(|w, registry: Mut<AppTypeRegistry>| {
+            let mut entity_ref = w.get_entity_mut(entity).map_err(|e| {
+                ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity))
+            })?;
+
+            let registry_lock = registry.read();
+
+            // this is just a formality
+            // TODO: maybe get an add_default impl added to ReflectComponent
+            // this means that we don't require ReflectDefault for adding components!
+            match comp_type.0.type_info(){
+                bevy::reflect::TypeInfo::Struct(_) => component_data.insert(&mut entity_ref, &DynamicStruct::default(), &registry_lock),
+                bevy::reflect::TypeInfo::TupleStruct(_) => component_data.insert(&mut entity_ref, &DynamicTupleStruct::default(), &registry_lock),
+                bevy::reflect::TypeInfo::Tuple(_) => component_data.insert(&mut entity_ref, &DynamicTuple::default(), &registry_lock),
+                bevy::reflect::TypeInfo::List(_) => component_data.insert(&mut entity_ref, &DynamicList::default(), &registry_lock),
+                bevy::reflect::TypeInfo::Array(_) => component_data.insert(&mut entity_ref, &DynamicArray::new(Box::new([])), &registry_lock),
+                bevy::reflect::TypeInfo::Map(_) => component_data.insert(&mut entity_ref, &DynamicMap::default(), &registry_lock),
+                bevy::reflect::TypeInfo::Set(_) => component_data.insert(&mut entity_ref, &DynamicSet::default(), &registry_lock),
+                bevy::reflect::TypeInfo::Opaque(_) => component_data.insert(&mut entity_ref,
+                    comp_type.data::<ReflectDefault>().ok_or_else(||
+                        ScriptError::Other(format!("Component {} is a value or dynamic type with no `ReflectDefault` type_data, cannot instantiate sensible value",comp_type.short_name())))?
+                        .default()
+                        .as_partial_reflect(),
+                        &registry_lock),
+                bevy::reflect::TypeInfo::Enum(_) => component_data.insert(&mut entity_ref, &DynamicEnum::default(), &registry_lock),
+            };
+
+            Ok(())
+        })
+    }
+
+    /// Removes the given component from the entity
+    pub(crate) fn remove_component_from(
+        w: &mut World,
+        entity: Entity,
+        comp_type: &ScriptTypeRegistration,
+    ) -> Result<(), ScriptError> {
         let mut entity_ref = w
             .get_entity_mut(entity)
             .map_err(|e| ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity)))?;
 
+        if let Some(script_type) = comp_type.script_component() {
+            script_type.remove(&mut entity_ref);
+            return Ok(());
+        }
+
         let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
             ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
         })?;
+        component_data.remove(&mut entity_ref);
+        Ok(())
+    }
 
-        let registry_lock = registry.read();
-
-        // this is just a formality
-        // TODO: maybe get an add_default impl added to ReflectComponent
-        // this means that we don't require ReflectDefault for adding components!
-        match comp_type.0.type_info(){
-            bevy::reflect::TypeInfo::Struct(_) => component_data.insert(&mut entity_ref, &DynamicStruct::default(), &registry_lock),
-            bevy::reflect::TypeInfo::TupleStruct(_) => component_data.insert(&mut entity_ref, &DynamicTupleStruct::default(), &registry_lock),
-            bevy::reflect::TypeInfo::Tuple(_) => component_data.insert(&mut entity_ref, &DynamicTuple::default(), &registry_lock),
-            bevy::reflect::TypeInfo::List(_) => component_data.insert(&mut entity_ref, &DynamicList::default(), &registry_lock),
-            bevy::reflect::TypeInfo::Array(_) => component_data.insert(&mut entity_ref, &DynamicArray::new(Box::new([])), &registry_lock),
-            bevy::reflect::TypeInfo::Map(_) => component_data.insert(&mut entity_ref, &DynamicMap::default(), &registry_lock),
-            bevy::reflect::TypeInfo::Set(_) => component_data.insert(&mut entity_ref, &DynamicSet::default(), &registry_lock),
-            bevy::reflect::TypeInfo::Opaque(_) => component_data.insert(&mut entity_ref,
-                comp_type.data::<ReflectDefault>().ok_or_else(||
-                    ScriptError::Other(format!("Component {} is a value or dynamic type with no `ReflectDefault` type_data, cannot instantiate sensible value",comp_type.short_name())))?
-                    .default()
-                    .as_partial_reflect(),
-                    &registry_lock),
-            bevy::reflect::TypeInfo::Enum(_) => component_data.insert(&mut entity_ref, &DynamicEnum::default(), &registry_lock),
-        };
-        // if we do not drop the lock here, line below will complain registry is still borrowed at drop
-        drop(registry_lock);
+    /// Removes the given resource from the world
+    pub(crate) fn remove_resource_from(
+        w: &mut World,
+        res_type: &ScriptTypeRegistration,
+    ) -> Result<(), ScriptError> {
+        if let Some(script_type) = res_type.script_resource() {
+            script_type.remove_resource(w);
+            return Ok(());
+        }
 
-        // Insert: AppTypeRegistry
-        w.insert_resource(registry);
+        let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
+        })?;
+        resource_data.remove(w);
+        Ok(())
+    }
 
+    /// Returns a reference to the given component of the entity, without checking that it exists
+    fn component_ref(
+        &self,
+        comp_type: &ScriptTypeRegistration,
+        entity: Entity,
+    ) -> Result<ReflectReference, ScriptError> {
+        if let Some(script_type) = comp_type.script_component() {
+            return Ok(ReflectReference::new_script_component_ref(
+                script_type.clone(),
+                entity,
+                self.clone().into(),
+            ));
+        }
+
+        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
+        })?;
         Ok(ReflectReference::new_component_ref(
             component_data.clone(),
             entity,
@@ -265,6 +581,119 @@ impl ScriptWorld {
         ))
     }
 
+    /// Returns a reference to the given resource, without checking that it exists
+    fn resource_ref(
+        &self,
+        res_type: &ScriptTypeRegistration,
+    ) -> Result<ReflectReference, ScriptError> {
+        if let Some(script_type) = res_type.script_resource() {
+            return Ok(ReflectReference::new_script_resource_ref(
+                script_type.clone(),
+                self.clone().into(),
+            ));
+        }
+
+        let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
+        })?;
+        Ok(ReflectReference::new_resource_ref(
+            resource_data.clone(),
+            self.clone().into(),
+        ))
+    }
+
+    /// Constructs a value of the given type from a script value and inserts it into the entity as a component,
+    /// see [`ScriptValue::into_reflect`].
+    pub fn insert_component(
+        &self,
+        entity: Entity,
+        comp_type: ScriptTypeRegistration,
+        value: ScriptValue,
+    ) -> Result<ReflectReference, ScriptError> {
+        if let Some(script_type) = comp_type.script_component() {
+            let component = self.construct_script_value(script_type, value)?;
+
+            let mut w = self.write();
+            let mut entity_ref = w.get_entity_mut(entity).map_err(|e| {
+                ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity))
+            })?;
+            script_type.insert(&mut entity_ref, component);
+            drop(w);
+
+            return self.component_ref(&comp_type, entity);
+        }
+
+        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
+        })?;
+
+        let component = self.construct_value(&comp_type, value)?;
+
+        self.write()
+            .resource_scope(|w, registry: Mut<AppTypeRegistry>| {
+                let mut entity_ref = w.get_entity_mut(entity).map_err(|e| {
+                    ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity))
+                })?;
+
+                component_data.insert(
+                    &mut entity_ref,
+                    component.as_partial_reflect(),
+                    &registry.read(),
+                );
+                Ok::<_, ScriptError>(())
+            })?;
+
+        Ok(ReflectReference::new_component_ref(
+            component_data.clone(),
+            entity,
+            self.clone().into(),
+        ))
+    }
+
+    /// Constructs a value of the given type from a script value, see [`ScriptValue::into_reflect`].
+    /// The returned reference owns the value.
+    pub fn construct(
+        &self,
+        value_type: &ScriptTypeRegistration,
+        value: ScriptValue,
+    ) -> Result<ReflectReference, ScriptError> {
+        Ok(ReflectReference::new_owned_ref(
+            self.construct_value(value_type, value)?,
+            self.clone().into(),
+        ))
+    }
+
+    fn construct_value(
+        &self,
+        value_type: &ScriptTypeRegistration,
+        value: ScriptValue,
+    ) -> Result<Box<dyn Reflect>, ScriptError> {
+        if let Some(script_type) = value_type.script_type() {
+            return Ok(Box::new(self.construct_script_value(script_type, value)?));
+        }
+
+        // the world is not locked during the conversion, since converting script proxies accesses it
+        let registry = self.read().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        value
+            .into_reflect(value_type, &registry)
+            .map_err(|e| ScriptError::Other(e.to_string()))
+    }
+
+    fn construct_script_value(
+        &self,
+        script_type: &ScriptTypeDescriptor,
+        value: ScriptValue,
+    ) -> Result<ScriptStruct, ScriptError> {
+        let registry = self.read().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        script_type
+            .construct(value, &registry)
+            .map_err(|e| ScriptError::Other(e.to_string()))
+    }
+
     pub fn get_component(
         &self,
         entity: Entity,
@@ -276,6 +705,16 @@ impl ScriptWorld {
             .get_entity(entity)
             .map_err(|e| ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity)))?;
 
+        if let Some(script_type) = comp_type.script_component() {
+            return Ok(script_type.reflect(entity_ref).map(|_component| {
+                ReflectReference::new_script_component_ref(
+                    script_type.clone(),
+                    entity,
+                    self.clone().into(),
+                )
+            }));
+        }
+
         let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
             ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
         })?;
@@ -291,14 +730,19 @@ impl ScriptWorld {
         comp_type: ScriptTypeRegistration,
     ) -> Result<bool, ScriptError> {
         let w = self.read();
-        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
-            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
-        })?;
 
         let entity_ref = w
             .get_entity(entity)
             .map_err(|e| ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity)))?;
 
+        if let Some(script_type) = comp_type.script_component() {
+            return Ok(script_type.reflect(entity_ref).is_some());
+        }
+
+        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
+        })?;
+
         Ok(component_data.reflect(entity_ref).is_some())
     }
 
@@ -307,17 +751,7 @@ impl ScriptWorld {
         entity: Entity,
         comp_type: ScriptTypeRegistration,
     ) -> Result<(), ScriptError> {
-        let mut w = self.write();
-
-        let mut entity_ref = w
-            .get_entity_mut(entity)
-            .map_err(|e| ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity)))?;
-
-        let component_data = comp_type.data::<ReflectComponent>().ok_or_else(|| {
-            ScriptError::Other(format!("Not a component {}", comp_type.short_name()))
-        })?;
-        component_data.remove(&mut entity_ref);
-        Ok(())
+        Self::remove_component_from(&mut self.write(), entity, &comp_type)
     }
 
     pub fn get_resource(
@@ -326,6 +760,12 @@ impl ScriptWorld {
     ) -> Result<Option<ReflectReference>, ScriptError> {
         let w = self.read();
 
+        if let Some(script_type) = res_type.script_resource() {
+            return Ok(script_type.resource(&w).map(|_res| {
+                ReflectReference::new_script_resource_ref(script_type.clone(), self.clone().into())
+            }));
+        }
+
         let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
             ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
         })?;
@@ -338,6 +778,10 @@ impl ScriptWorld {
     pub fn has_resource(&self, res_type: ScriptTypeRegistration) -> Result<bool, ScriptError> {
         let w = self.read();
 
+        if let Some(script_type) = res_type.script_resource() {
+            return Ok(script_type.resource(&w).is_some());
+        }
+
         let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
             ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
         })?;
@@ -346,98 +790,308 @@ impl ScriptWorld {
     }
 
     pub fn remove_resource(&mut self, res_type: ScriptTypeRegistration) -> Result<(), ScriptError> {
+        Self::remove_resource_from(&mut self.write(), &res_type)
+    }
+
+    /// Constructs a resource of the given type from a script value and inserts it into the world,
+    /// replacing any existing resource of this type, see [`ScriptValue::into_reflect`].
+    pub fn insert_resource(
+        &self,
+        res_type: ScriptTypeRegistration,
+        value: ScriptValue,
+    ) -> Result<ReflectReference, ScriptError> {
+        if let Some(script_type) = res_type.script_resource() {
+            let resource = self.construct_script_value(script_type, value)?;
+            script_type.insert_resource(&mut self.write(), resource);
+            return self.resource_ref(&res_type);
+        }
+
+        let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
+            ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
+        })?;
+
+        let resource = self.construct_value(&res_type, value)?;
+
         let mut w = self.write();
+        let registry = w.resource::<AppTypeRegistry>().clone();
+        resource_data.insert(&mut w, resource.as_partial_reflect(), &registry.read());
+
+        Ok(ReflectReference::new_resource_ref(
+            resource_data.clone(),
+            self.clone().into(),
+        ))
+    }
+
+    /// Inserts the given resource into the world unless it already exists, and returns a reference to it.
+    /// The resource is created via its `ReflectFromWorld` type data, or its `ReflectDefault` type data if it has none.
+    pub fn init_resource(
+        &self,
+        res_type: ScriptTypeRegistration,
+    ) -> Result<ReflectReference, ScriptError> {
+        if let Some(script_type) = res_type.script_resource() {
+            if !self.has_resource(res_type.clone())? {
+                let resource = self.construct_script_value(script_type, ScriptValue::Unit)?;
+                script_type.insert_resource(&mut self.write(), resource);
+            }
+            return self.resource_ref(&res_type);
+        }
 
         let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
             ScriptError::Other(format!("Not a resource {}", res_type.short_name()))
         })?;
-        resource_data.remove(&mut w);
-        Ok(())
+
+        let mut w = self.write();
+        if resource_data.reflect(&w).is_none() {
+            let resource = if let Some(from_world) = res_type.data::<ReflectFromWorld>() {
+                from_world.from_world(&mut w)
+            } else if let Some(default) = res_type.data::<ReflectDefault>() {
+                default.default()
+            } else {
+                return Err(ScriptError::Other(format!(
+                    "Resource {} has neither `ReflectFromWorld` nor `ReflectDefault` type_data, cannot instantiate sensible value",
+                    res_type.short_name()
+                )));
+            };
+
+            let registry = w.resource::<AppTypeRegistry>().clone();
+            resource_data.insert(&mut w, resource.as_partial_reflect(), &registry.read());
+        }
+
+        Ok(ReflectReference::new_resource_ref(
+            resource_data.clone(),
+            self.clone().into(),
+        ))
     }
 
-    pub fn query(
-        &mut self,
-        components: Vec<ScriptTypeRegistration>,
-        with: Vec<ScriptTypeRegistration>,
-        without: Vec<ScriptTypeRegistration>,
-    ) -> Result<Vec<ScriptQueryResult>, ScriptError> {
+    /// Registers a script system with the script system set matching its label.
+    /// `C` is the script context type of the host running the system.
+    pub fn add_system<C: 'static>(&self, system: ScriptSystem) -> Result<(), ScriptError> {
+        let mut w = self.write();
+        let mut systems = w.get_resource_mut::<ScriptSystems<C>>().ok_or_else(|| {
+            ScriptError::Other(
+                "No script system sets exist for this script host. Add one with `app.add_script_system_set`".to_owned(),
+            )
+        })?;
+        systems.insert(system)
+    }
+
+    /// Removes the script system with the given owner calling the function with the given id, returns true if it existed.
+    pub fn remove_system<C: 'static>(&self, sid: u32, callback: &str) -> bool {
+        let mut w = self.write();
+        w.get_resource_mut::<ScriptSystems<C>>()
+            .map(|mut systems| systems.remove(sid, callback))
+            .unwrap_or_default()
+    }
+
+    /// Registers a change hook, calling a script function whenever the hook's component changes.
+    /// `C` is the script context type of the host running the hook.
+    pub fn add_change_hook<C: Send + Sync + 'static>(
+        &self,
+        hook: ScriptChangeHook,
+    ) -> Result<(), ScriptError> {
         let mut w = self.write();
 
-        let get_id = |component: &ScriptTypeRegistration,
-                      w: &MappedRwLockWriteGuard<World>|
-         -> Result<ComponentId, ScriptError> {
-            w.components()
-                .get_id(component.type_info().type_id())
+        if !w.contains_resource::<ScriptChangeHooks<C>>() {
+            return Err(ScriptError::Other(
+                "Change hooks are not enabled for this script host. Enable them with `app.add_script_change_hooks`".to_owned(),
+            ));
+        }
+
+        let component_id = match hook.component.script_component() {
+            Some(script_type) => script_type.component_id(),
+            None => hook
+                .component
+                .data::<ReflectComponent>()
                 .ok_or_else(|| {
-                    ScriptError::Other(format!("Not a component {}", component.short_name()))
-                })
+                    ScriptError::Other(format!("Not a component {}", hook.component.short_name()))
+                })?
+                .register_component(&mut w),
         };
 
-        let components: Vec<(ReflectComponent, ComponentId)> = components
-            .into_iter()
-            .map(|component| {
-                let reflect_component = component.data::<ReflectComponent>().ok_or_else(|| {
-                    ScriptError::Other(format!("Not a component {}", component.short_name()))
-                });
+        let observer = w.resource_scope(|w, mut hooks: Mut<ScriptChangeHooks<C>>| {
+            hooks.insert(w, hook, component_id)
+        });
 
-                let component_id = get_id(&component, &w);
-                reflect_component.map(|v1| component_id.map(|v2| (v1.clone(), v2)))?
-            })
-            .collect::<Result<Vec<_>, ScriptError>>()?;
+        if let Some(observer) = observer {
+            w.spawn(observer);
+        }
 
-        let with_ids: Vec<ComponentId> = with
-            .iter()
-            .map(|component| get_id(component, &w))
-            .collect::<Result<Vec<_>, ScriptError>>()?;
+        Ok(())
+    }
 
-        let without_ids: Vec<ComponentId> = without
-            .iter()
-            .map(|component| get_id(component, &w))
-            .collect::<Result<Vec<_>, ScriptError>>()?;
+    /// Removes the change hooks with the given owner calling the function with the given id, returns true if any existed.
+    pub fn remove_change_hooks<C: Send + Sync + 'static>(&self, sid: u32, callback: &str) -> bool {
+        let mut w = self.write();
+        w.get_resource_mut::<ScriptChangeHooks<C>>()
+            .map(|mut hooks| hooks.remove(sid, callback))
+            .unwrap_or_default()
+    }
 
-        let mut q = QueryBuilder::<EntityRef>::new(&mut w);
+    /// Registers a script observer, calling a script function whenever the observer's event is triggered.
+    /// `C` is the script context type of the host running the observer.
+    pub fn add_observer<C: Send + Sync + 'static>(
+        &self,
+        observer: ScriptObserver,
+    ) -> Result<(), ScriptError> {
+        let mut w = self.write();
 
-        for (_, id) in &components {
-            q.ref_id(*id);
+        if !w.contains_resource::<ScriptObservers<C>>() {
+            return Err(ScriptError::Other(
+                "Observers are not enabled for this script host. Enable them with `app.add_script_observers`".to_owned(),
+            ));
         }
 
-        for with_id in with_ids {
-            q.with_id(with_id);
+        // observers only get deferred world access, the events are delivered by a command applied once the world is flushed
+        let mut bevy_observer = Self::reflect_event(&observer.event)?.observer(Box::new(
+            |mut world, event: ObservedEvent| {
+                if let Some(mut observers) = world.get_resource_mut::<ScriptObservers<C>>() {
+                    observers.push(event);
+                    let deliver = observers.deliver();
+                    world.commands().queue(deliver);
+                }
+            },
+        ));
+
+        if let Some(entity) = observer.entity {
+            w.get_entity(entity).map_err(|e| {
+                ScriptError::Other(format!("Entity is not valid {:#?}. {e}", entity))
+            })?;
+            bevy_observer = bevy_observer.with_entity(entity);
         }
 
-        for without_id in without_ids {
-            q.without_id(without_id);
+        let observer_entity = w.spawn(bevy_observer).id();
+        // observers are registered via commands
+        w.flush();
+        let replaced = w
+            .resource_mut::<ScriptObservers<C>>()
+            .insert(observer, observer_entity);
+
+        if let Some(replaced) = replaced {
+            w.despawn(replaced);
         }
 
-        let query_result: Vec<EntityRef<'_>> = q.build().iter_mut(&mut w).collect();
-
-        query_result
-            .into_iter()
-            .map(|filtered_entity| {
-                components
-                    .clone()
-                    .into_iter()
-                    .map(|(reflect_component, _)| {
-                        let type_id = reflect_component.type_id();
-                        reflect_component
-                            .reflect(filtered_entity)
-                            .map(|_component| {
-                                ReflectReference::new_component_ref(
-                                    reflect_component,
-                                    filtered_entity.id(),
-                                    self.clone().into(),
-                                )
-                            })
-                            .ok_or_else(|| {
-                                ScriptError::Other(format!(
-                                    "Failed to reflect component during query: {:?}",
-                                    type_id
-                                ))
-                            })
-                    })
-                    .collect::<Result<Vec<_>, ScriptError>>()
-                    .map(|references| ScriptQueryResult(filtered_entity.id(), references))
+        Ok(())
+    }
+
+    /// Removes the script observers with the given owner calling the function with the given id, returns true if any existed.
+    pub fn remove_observers<C: Send + Sync + 'static>(&self, sid: u32, callback: &str) -> bool {
+        let mut w = self.write();
+        let Some(mut observers) = w.get_resource_mut::<ScriptObservers<C>>() else {
+            return false;
+        };
+
+        let removed = observers.remove(sid, callback);
+        for observer_entity in &removed {
+            w.despawn(*observer_entity);
+        }
+
+        !removed.is_empty()
+    }
+
+    /// Creates a default value of the given event type, which requires `ReflectDefault` type data.
+    /// The value can then be modified before passing it to [`Self::trigger_event`].
+    pub fn default_event(
+        &self,
+        event_type: &ScriptTypeRegistration,
+    ) -> Result<SharedReflect, ScriptError> {
+        let default = event_type.data::<ReflectDefault>().ok_or_else(|| {
+            ScriptError::Other(format!(
+                "Event {} has no `ReflectDefault` type_data, cannot instantiate sensible value",
+                event_type.short_name()
+            ))
+        })?;
+
+        Self::reflect_event(event_type)?
+            .from_reflect(default.default().as_partial_reflect())
+            .ok_or_else(|| {
+                ScriptError::Other(format!("Could not construct {}", event_type.short_name()))
             })
-            .collect::<Result<Vec<_>, ScriptError>>()
+    }
+
+    /// Triggers the given value as an event of the given type for the given targets, or globally if there are none.
+    /// Script observers of other hosts receive the event right away, observers of the host running the calling script once it is done.
+    pub fn trigger_event(
+        &self,
+        event_type: &ScriptTypeRegistration,
+        event: &dyn PartialReflect,
+        targets: &[Entity],
+    ) -> Result<(), ScriptError> {
+        let reflect_event = Self::reflect_event(event_type)?;
+        let mut w = self.write();
+        reflect_event.trigger(&mut w, event, targets)?;
+        // applies the commands delivering the event to script observers
+        w.flush();
+        Ok(())
+    }
+
+    /// Sends the given value as an event of the given type, the event needs to be added to the app via `app.add_event`.
+    pub fn send_event(
+        &self,
+        event_type: &ScriptTypeRegistration,
+        event: &dyn PartialReflect,
+    ) -> Result<(), ScriptError> {
+        let reflect_event = Self::reflect_event(event_type)?;
+        let mut w = self.write();
+        reflect_event.send(&mut w, event)
+    }
+
+    /// Returns references to all events of the given type sent since the given script last read them.
+    /// `C` is the script context type of the host running the script.
+    pub fn read_events<C: Send + Sync + 'static>(
+        &self,
+        sid: u32,
+        event_type: &ScriptTypeRegistration,
+    ) -> Result<Vec<ReflectReference>, ScriptError> {
+        let reflect_event = Self::reflect_event(event_type)?;
+        let mut w = self.write();
+        w.init_resource::<ScriptEventReaders<C>>();
+        w.resource_scope(|w, mut readers: Mut<ScriptEventReaders<C>>| {
+            readers
+                .read(w, sid, event_type.type_info().type_id(), &reflect_event)
+                .map(|events| {
+                    events
+                        .into_iter()
+                        .map(|event| ReflectReference::new_shared_ref(event, self.clone().into()))
+                        .collect()
+                })
+        })
+    }
+
+    fn reflect_event(event_type: &ScriptTypeRegistration) -> Result<ReflectEvent, ScriptError> {
+        event_type.data::<ReflectEvent>().cloned().ok_or_else(|| {
+            ScriptError::Other(format!(
+                "Not an event {}, did you forget to add `#[reflect(Event)]`?",
+                event_type.short_name()
+            ))
+        })
+    }
+
+    pub fn query(
+        &mut self,
+        components: Vec<ScriptTypeRegistration>,
+        with: Vec<ScriptTypeRegistration>,
+        without: Vec<ScriptTypeRegistration>,
+    ) -> Result<Vec<ScriptQueryResult>, ScriptError> {
+        self.run_query(
+            &ScriptQuery {
+                components,
+                with,
+                without,
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    /// Runs the given query and collects its results, see [`ScriptQueryBuilder::iter`] for iterating lazily.
+    ///
+    /// `Changed` and `Added` filters match changes made since the given script last ran a query in an earlier frame,
+    /// or since the currently running system last ran if no script is given.
+    pub fn run_query(
+        &self,
+        query: &ScriptQuery,
+        sid: Option<u32>,
+    ) -> Result<Vec<ScriptQueryResult>, ScriptError> {
+        Ok(ScriptQueryIter::new(self, query, sid, &Default::default())?.collect())
     }
 }
@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy_mod_scripting_core::{hosts::ScriptContexts, world::ScriptCommandQueue};
+
+use super::{
+    events::ObservedEvent,
+    systems::{
+        ScriptCallback, ScriptFunctionCall, ScriptFunctionRef, ScriptSystemArg, ScriptSystemHost,
+    },
+    ScriptTypeRegistration,
+};
+
+/// An observer registered by a script.
+/// The callback receives a reference to the event and the entity it was triggered for, if any.
+#[derive(Clone, Debug)]
+pub struct ScriptObserver {
+    /// the id of the script owning the callback
+    pub sid: u32,
+    /// the event to observe, needs to have [`ReflectEvent`](super::events::ReflectEvent) type data
+    pub event: ScriptTypeRegistration,
+    /// the entity to observe, observes all triggers of the event if `None`
+    pub entity: Option<Entity>,
+    /// the script function to call
+    pub callback: ScriptFunctionRef,
+}
+
+/// Stores the observers registered by scripts running in contexts of type `C`, each backed by an observer entity.
+///
+/// Observed events are copied and queued up, each observer entity then queues a command delivering them to scripts.
+/// Observers belonging to scripts which were removed are despawned the next time events are delivered.
+#[derive(Resource)]
+pub struct ScriptObservers<C> {
+    observers: Vec<(ScriptObserver, Entity)>,
+    observed: Vec<ObservedEvent>,
+    deliver: fn(&mut World),
+    _ph: PhantomData<fn() -> C>,
+}
+
+impl<C: Send + Sync + 'static> ScriptObservers<C> {
+    /// Creates an empty set of observers, delivering observed events via the given function, usually [`run_script_observers`]
+    pub fn new(deliver: fn(&mut World)) -> Self {
+        Self {
+            observers: Default::default(),
+            observed: Default::default(),
+            deliver,
+            _ph: PhantomData,
+        }
+    }
+
+    /// Records a new script observer backed by the given observer entity.
+    ///
+    /// Returns the observer entity of an identical script observer which was replaced, this entity needs to be despawned.
+    pub fn insert(&mut self, observer: ScriptObserver, observer_entity: Entity) -> Option<Entity> {
+        let replaced = self
+            .observers
+            .iter()
+            .position(|(o, _)| {
+                o.sid == observer.sid
+                    && o.entity == observer.entity
+                    && o.callback == observer.callback
+                    && o.event.type_info().type_id() == observer.event.type_info().type_id()
+            })
+            .map(|idx| self.observers.remove(idx).1);
+
+        self.observers.push((observer, observer_entity));
+        replaced
+    }
+
+    /// Removes all observers of the given script calling the function with the given id.
+    ///
+    /// Returns the observer entities of the removed observers, these need to be despawned.
+    pub fn remove(&mut self, sid: u32, callback: &str) -> Vec<Entity> {
+        let (removed, kept) = std::mem::take(&mut self.observers)
+            .into_iter()
+            .partition(|(o, _)| o.sid == sid && o.callback.id() == callback);
+        self.observers = kept;
+        removed.into_iter().map(|(_, entity)| entity).collect()
+    }
+
+    /// Queues up an event received by one of the observer entities
+    pub fn push(&mut self, event: ObservedEvent) {
+        self.observed.push(event);
+    }
+
+    /// The function delivering the queued events
+    pub fn deliver(&self) -> fn(&mut World) {
+        self.deliver
+    }
+
+    /// Returns all observers in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ScriptObserver> {
+        self.observers.iter().map(|(o, _)| o)
+    }
+
+    /// Despawns the observers of scripts which no longer exist and collects the calls for all events queued up since the last delivery.
+    pub fn prepare_run(&mut self, world: &mut World) -> Vec<ScriptFunctionCall> {
+        if let Some(contexts) = world.get_resource::<ScriptContexts<C>>() {
+            let (kept, removed) = std::mem::take(&mut self.observers)
+                .into_iter()
+                .partition(|(o, _)| contexts.context_entities.contains_key(&o.sid));
+            self.observers = kept;
+
+            for (_, observer_entity) in removed {
+                world.despawn(observer_entity);
+            }
+        }
+
+        std::mem::take(&mut self.observed)
+            .into_iter()
+            .filter_map(|observed| {
+                let (observer, _) = self
+                    .observers
+                    .iter()
+                    .find(|(_, e)| *e == observed.observer)?;
+
+                Some(ScriptFunctionCall {
+                    sid: observer.sid,
+                    callback: ScriptCallback::Value(observer.callback.clone()),
+                    arg: ScriptSystemArg::Event {
+                        event: observed.event,
+                        target: (observed.target != Entity::PLACEHOLDER).then_some(observed.target),
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Delivers the events observed by scripts of the given host.
+///
+/// If scripts of the host are running, i.e. the events were triggered by a script, the events are delivered once they are done.
+pub fn run_script_observers<H: ScriptSystemHost>(world: &mut World) {
+    if !world.contains_resource::<ScriptContexts<H::ScriptContext>>() {
+        if let Some(queue) = world.get_resource::<ScriptCommandQueue>() {
+            queue.push(run_script_observers::<H>);
+        }
+        return;
+    }
+
+    let calls = world.resource_scope(
+        |world, mut observers: Mut<ScriptObservers<H::ScriptContext>>| observers.prepare_run(world),
+    );
+
+    if !calls.is_empty() {
+        H::call_script_functions(world, calls);
+    }
+}
+
+pub trait AddScriptObservers {
+    /// Enables observers for scripts of the given host.
+    ///
+    /// Observed events are delivered as soon as the world is flushed after the trigger,
+    /// i.e. right after the command or exclusive system triggering them, or right away for events triggered by scripts of other hosts.
+    fn add_script_observers<H: ScriptSystemHost>(&mut self) -> &mut Self;
+}
+
+impl AddScriptObservers for App {
+    fn add_script_observers<H: ScriptSystemHost>(&mut self) -> &mut Self {
+        if !self
+            .world()
+            .contains_resource::<ScriptObservers<H::ScriptContext>>()
+        {
+            self.insert_resource(ScriptObservers::<H::ScriptContext>::new(
+                run_script_observers::<H>,
+            ));
+        }
+        self
+    }
+}
@@ -0,0 +1,833 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bevy::{
+    ecs::{
+        archetype::ArchetypeId,
+        component::{ComponentId, Tick},
+        query::{QueryBuilder, QueryData, QueryState},
+        world::{EntityRef, World},
+    },
+    prelude::{Entity, ReflectComponent, Resource},
+};
+use bevy_mod_scripting_core::prelude::ScriptError;
+use parking_lot::Mutex;
+
+use crate::ReflectReference;
+
+use super::{
+    script_types::ScriptTypeDescriptor, ScriptQuery, ScriptQueryResult, ScriptTypeRegistration,
+    ScriptWorld,
+};
+
+/// The number of entities [`ScriptQueryIter`] takes from an archetype while holding the world lock
+const CHUNK_SIZE: usize = 256;
+
+/// How a queried component is reflected
+#[derive(Clone)]
+enum QueriedComponent {
+    Reflect(ReflectComponent),
+    Script(Arc<ScriptTypeDescriptor>),
+}
+
+impl QueriedComponent {
+    fn new(component: &ScriptTypeRegistration) -> Result<Self, ScriptError> {
+        match component.script_component() {
+            Some(script_type) => Ok(Self::Script(script_type.clone())),
+            None => component
+                .data::<ReflectComponent>()
+                .map(|reflect_component| Self::Reflect(reflect_component.clone()))
+                .ok_or_else(|| {
+                    ScriptError::Other(format!("Not a component {}", component.short_name()))
+                }),
+        }
+    }
+
+    /// Returns a reference to this component of the given entity, if it has one
+    fn reference(&self, entity: EntityRef, world: &ScriptWorld) -> Option<ReflectReference> {
+        match self {
+            Self::Reflect(reflect_component) => {
+                reflect_component.reflect(entity).map(|_component| {
+                    ReflectReference::new_component_ref(
+                        reflect_component.clone(),
+                        entity.id(),
+                        world.clone().into(),
+                    )
+                })
+            }
+            Self::Script(script_type) => script_type.reflect(entity).map(|_component| {
+                ReflectReference::new_script_component_ref(
+                    script_type.clone(),
+                    entity.id(),
+                    world.clone().into(),
+                )
+            }),
+        }
+    }
+}
+
+/// A filter on a single component
+#[derive(Clone, Copy)]
+enum QueryFilterTerm {
+    With(ComponentId),
+    Without(ComponentId),
+    Changed(ComponentId),
+    Added(ComponentId),
+}
+
+impl QueryFilterTerm {
+    fn add_to<D: QueryData>(self, builder: &mut QueryBuilder<D>) {
+        match self {
+            Self::With(id) | Self::Changed(id) | Self::Added(id) => builder.with_id(id),
+            Self::Without(id) => builder.without_id(id),
+        };
+    }
+
+    fn matches(self, entity: EntityRef, last_run: Tick, this_run: Tick) -> bool {
+        match self {
+            Self::With(id) => entity.contains_id(id),
+            Self::Without(id) => !entity.contains_id(id),
+            Self::Changed(id) => entity
+                .get_change_ticks_by_id(id)
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run)),
+            Self::Added(id) => entity
+                .get_change_ticks_by_id(id)
+                .is_some_and(|ticks| ticks.is_added(last_run, this_run)),
+        }
+    }
+}
+
+/// The ticks `Changed` and `Added` query filters of each script are relative to
+#[derive(Resource, Default)]
+struct ScriptQueryTicks(HashMap<u32, (Tick, Tick)>);
+
+impl ScriptQueryTicks {
+    /// Returns the tick of the previous run of the given script, all queries made during the same change tick belong to one run
+    fn last_run(&mut self, sid: u32, this_run: Tick) -> Tick {
+        let (last_run, current_run) = self.0.entry(sid).or_insert((
+            Tick::new(this_run.get().wrapping_sub(Tick::MAX.get())),
+            this_run,
+        ));
+
+        if *current_run != this_run {
+            *last_run = *current_run;
+            *current_run = this_run;
+        }
+        *last_run
+    }
+}
+
+/// A query compiled against a world
+struct CompiledQuery {
+    state: QueryState<EntityRef<'static>>,
+    components: Vec<QueriedComponent>,
+    optional: Vec<QueriedComponent>,
+    /// filters which cannot be checked per archetype, i.e. `Changed`, `Added` and `Or` groups containing them
+    filters: Vec<QueryFilterTerm>,
+    or_groups: Vec<Vec<QueryFilterTerm>>,
+}
+
+impl CompiledQuery {
+    fn new(w: &mut World, query: &ScriptQuery) -> Result<Self, ScriptError> {
+        let get_id =
+            |component: &ScriptTypeRegistration, w: &World| -> Result<ComponentId, ScriptError> {
+                component
+                    .script_component()
+                    .map(|script_type| script_type.component_id())
+                    .or_else(|| w.components().get_id(component.type_info().type_id()))
+                    .ok_or_else(|| {
+                        ScriptError::Other(format!("Not a component {}", component.short_name()))
+                    })
+            };
+
+        let fetch = |components: &[ScriptTypeRegistration],
+                     w: &World|
+         -> Result<Vec<(QueriedComponent, ComponentId)>, ScriptError> {
+            components
+                .iter()
+                .map(|component| Ok((QueriedComponent::new(component)?, get_id(component, w)?)))
+                .collect()
+        };
+
+        let filter = |with: &[ScriptTypeRegistration],
+                      without: &[ScriptTypeRegistration],
+                      changed: &[ScriptTypeRegistration],
+                      added: &[ScriptTypeRegistration],
+                      w: &World|
+         -> Result<Vec<QueryFilterTerm>, ScriptError> {
+            let mut terms = Vec::default();
+            for (components, term) in [
+                (
+                    with,
+                    QueryFilterTerm::With as fn(ComponentId) -> QueryFilterTerm,
+                ),
+                (without, QueryFilterTerm::Without),
+                (changed, QueryFilterTerm::Changed),
+                (added, QueryFilterTerm::Added),
+            ] {
+                for component in components {
+                    terms.push(term(get_id(component, w)?));
+                }
+            }
+            Ok(terms)
+        };
+
+        let components = fetch(&query.components, w)?;
+        let optional = fetch(&query.optional, w)?;
+        let filters = filter(&query.with, &query.without, &query.changed, &query.added, w)?;
+        let or_groups = query
+            .or
+            .iter()
+            .map(|or| filter(&or.with, &or.without, &or.changed, &or.added, w))
+            .filter(|group| !matches!(group, Ok(group) if group.is_empty()))
+            .collect::<Result<Vec<_>, ScriptError>>()?;
+
+        let mut q = QueryBuilder::<EntityRef>::new(w);
+
+        for (_, id) in &components {
+            q.ref_id(*id);
+        }
+
+        for (_, id) in &optional {
+            q.optional(|q| {
+                q.ref_id(*id);
+            });
+        }
+
+        for term in &filters {
+            term.add_to(&mut q);
+        }
+
+        for group in &or_groups {
+            q.or(|q| {
+                for term in group {
+                    term.add_to(q);
+                }
+            });
+        }
+
+        let state = q.build();
+
+        let needs_ticks = |term: &QueryFilterTerm| {
+            matches!(
+                term,
+                QueryFilterTerm::Changed(_) | QueryFilterTerm::Added(_)
+            )
+        };
+
+        Ok(Self {
+            state,
+            components: components.into_iter().map(|(c, _)| c).collect(),
+            optional: optional.into_iter().map(|(c, _)| c).collect(),
+            filters: filters.into_iter().filter(needs_ticks).collect(),
+            or_groups: or_groups
+                .into_iter()
+                .filter(|group| group.iter().any(needs_ticks))
+                .collect(),
+        })
+    }
+
+    /// Returns the entity along with references to its components, if it has all required components
+    fn result(&self, entity: EntityRef, world: &ScriptWorld) -> Option<ScriptQueryResult> {
+        let components = self
+            .components
+            .iter()
+            .map(|component| component.reference(entity, world))
+            .collect::<Option<Vec<_>>>()?;
+
+        let optional = self
+            .optional
+            .iter()
+            .map(|component| component.reference(entity, world))
+            .collect();
+
+        Some(ScriptQueryResult(entity.id(), components, optional))
+    }
+
+    fn matches(&self, entity: EntityRef, last_run: Tick, this_run: Tick) -> bool {
+        self.filters
+            .iter()
+            .all(|term| term.matches(entity, last_run, this_run))
+            && self.or_groups.iter().all(|group| {
+                group
+                    .iter()
+                    .any(|term| term.matches(entity, last_run, this_run))
+            })
+    }
+}
+
+/// Caches the compiled query of a [`ScriptQueryBuilder`](super::ScriptQueryBuilder) between runs.
+///
+/// Clones share the same cache, the cache is reset whenever the query is modified.
+#[derive(Clone, Default)]
+pub struct ScriptQueryCache(Arc<Mutex<Option<Arc<Mutex<CompiledQuery>>>>>);
+
+impl ScriptQueryCache {
+    /// Compiles the query unless it is cached and updates it to the archetypes currently in the world.
+    /// Returns the compiled query along with the ticks its `Changed` and `Added` filters are relative to.
+    fn prepare(
+        &self,
+        world: &ScriptWorld,
+        query: &ScriptQuery,
+        sid: Option<u32>,
+    ) -> Result<(Arc<Mutex<CompiledQuery>>, Tick, Tick), ScriptError> {
+        let mut w = world.write();
+
+        let this_run = w.read_change_tick();
+        let last_run = match sid {
+            Some(sid) => {
+                w.init_resource::<ScriptQueryTicks>();
+                w.resource_mut::<ScriptQueryTicks>().last_run(sid, this_run)
+            }
+            None => w.last_change_tick(),
+        };
+
+        let compiled = {
+            let mut cache = self.0.lock();
+            match &*cache {
+                Some(compiled) => compiled.clone(),
+                None => cache
+                    .insert(Arc::new(Mutex::new(CompiledQuery::new(&mut w, query)?)))
+                    .clone(),
+            }
+        };
+
+        // only visits the archetypes created since the last update
+        compiled.lock().state.update_archetypes(&w);
+
+        Ok((compiled, last_run, this_run))
+    }
+}
+
+impl std::fmt::Debug for ScriptQueryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ScriptQueryCache")
+            .field(&self.0.lock().is_some())
+            .finish()
+    }
+}
+
+/// Lazily iterates over the results of a query.
+///
+/// Entities are taken from the world in chunks of matching archetypes, and the references to their components are only created once they are reached.
+/// Entities which stop matching the query before they are reached are skipped,
+/// entities created or moved to a new archetype during iteration may be missed.
+#[derive(Clone)]
+pub struct ScriptQueryIter {
+    world: ScriptWorld,
+    query: Arc<Mutex<CompiledQuery>>,
+    archetypes: Vec<ArchetypeId>,
+    archetype: usize,
+    /// the position in the current archetype
+    row: usize,
+    chunk: Vec<Entity>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl ScriptQueryIter {
+    /// Starts iterating over the given query.
+    /// `Changed` and `Added` filters match changes since the given script last ran a query in an earlier frame,
+    /// or since the currently running system last ran if no script is given.
+    pub(crate) fn new(
+        world: &ScriptWorld,
+        query: &ScriptQuery,
+        sid: Option<u32>,
+        cache: &ScriptQueryCache,
+    ) -> Result<Self, ScriptError> {
+        let (compiled, last_run, this_run) = cache.prepare(world, query, sid)?;
+        let archetypes = compiled.lock().state.matched_archetypes().collect();
+
+        Ok(Self {
+            world: world.clone(),
+            query: compiled,
+            archetypes,
+            archetype: 0,
+            row: 0,
+            chunk: Vec::default(),
+            last_run,
+            this_run,
+        })
+    }
+
+    /// Takes the next chunk of matching entities from the world, returns false once all archetypes were visited
+    fn next_chunk(&mut self) -> bool {
+        let w = self.world.read();
+        let query = self.query.lock();
+
+        while self.chunk.is_empty() {
+            let Some(archetype) = self
+                .archetypes
+                .get(self.archetype)
+                .and_then(|id| w.archetypes().get(*id))
+            else {
+                if self.archetype >= self.archetypes.len() {
+                    return false;
+                }
+                self.archetype += 1;
+                continue;
+            };
+
+            let entities = archetype.entities();
+            let end = (self.row + CHUNK_SIZE).min(entities.len());
+            self.chunk.extend(
+                entities[self.row.min(end)..end]
+                    .iter()
+                    .map(|entity| entity.id())
+                    .filter(|entity| {
+                        w.get_entity(*entity)
+                            .is_ok_and(|entity| query.matches(entity, self.last_run, self.this_run))
+                    }),
+            );
+
+            if end >= entities.len() {
+                self.archetype += 1;
+                self.row = 0;
+            } else {
+                self.row = end;
+            }
+        }
+
+        // entities are popped from the back
+        self.chunk.reverse();
+        true
+    }
+}
+
+impl Iterator for ScriptQueryIter {
+    type Item = ScriptQueryResult;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.chunk.is_empty() && !self.next_chunk() {
+                return None;
+            }
+
+            let entity = self.chunk.pop()?;
+            let w = self.world.read();
+            let query = self.query.lock();
+            // the entity may have stopped matching since its chunk was taken
+            if !query
+                .state
+                .contains(entity, &w, self.last_run, self.this_run)
+            {
+                continue;
+            }
+            let Ok(entity_ref) = w.get_entity(entity) else {
+                continue;
+            };
+            if let Some(result) = query.result(entity_ref, &self.world) {
+                return Some(result);
+            }
+        }
+    }
+}
+
+/// A query compiled once and kept by a script, e.g. in a global, to be run repeatedly across frames.
+///
+/// Unlike a [`ScriptQueryBuilder`](super::ScriptQueryBuilder) it cannot be modified,
+/// each run only needs to match the archetypes created since the previous run.
+/// It does not keep the world it was prepared in, since that is only valid while the script which prepared it runs,
+/// instead every run is given the world of the script currently running it.
+#[derive(Clone, Debug)]
+pub struct ScriptPreparedQuery {
+    query: ScriptQuery,
+    sid: Option<u32>,
+    cache: ScriptQueryCache,
+}
+
+impl ScriptPreparedQuery {
+    /// Compiles the given query, `Changed` and `Added` filters are relative to the given script's last run
+    pub fn new(
+        world: &ScriptWorld,
+        query: ScriptQuery,
+        sid: Option<u32>,
+        cache: ScriptQueryCache,
+    ) -> Result<Self, ScriptError> {
+        cache.prepare(world, &query, sid)?;
+        Ok(Self { query, sid, cache })
+    }
+
+    /// Returns the description of the prepared query
+    pub fn query(&self) -> &ScriptQuery {
+        &self.query
+    }
+
+    /// Runs the query and returns an iterator which takes results from the world as it advances
+    pub fn iter(&self, world: &ScriptWorld) -> Result<ScriptQueryIter, ScriptError> {
+        ScriptQueryIter::new(world, &self.query, self.sid, &self.cache)
+    }
+
+    /// Returns the only entity matching the query, fails if there are none or more than one
+    pub fn single(&self, world: &ScriptWorld) -> Result<ScriptQueryResult, ScriptError> {
+        let mut iter = self.iter(world)?;
+        match (iter.next(), iter.next()) {
+            (Some(result), None) => Ok(result),
+            (None, _) => Err(ScriptError::Other(
+                "Expected a single entity matching the query, found none".to_owned(),
+            )),
+            (Some(_), Some(_)) => Err(ScriptError::Other(
+                "Expected a single entity matching the query, found more than one".to_owned(),
+            )),
+        }
+    }
+
+    /// Returns the given entity along with its components if it matches the query
+    pub fn get(
+        &self,
+        world: &ScriptWorld,
+        entity: Entity,
+    ) -> Result<Option<ScriptQueryResult>, ScriptError> {
+        let (compiled, last_run, this_run) = self.cache.prepare(world, &self.query, self.sid)?;
+        let w = world.read();
+        let mut query = compiled.lock();
+
+        if query.state.get(&w, entity).is_err() {
+            return Ok(None);
+        }
+
+        Ok(w.get_entity(entity)
+            .ok()
+            .filter(|entity_ref| query.matches(*entity_ref, last_run, this_run))
+            .and_then(|entity_ref| query.result(entity_ref, world)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use bevy::{
+        prelude::{AppTypeRegistry, Component},
+        reflect::Reflect,
+    };
+    use bevy_mod_scripting_core::world::WorldPointerGuard;
+
+    use super::{
+        super::{ScriptQueryBuilder, ScriptQueryOr},
+        *,
+    };
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct A(u32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct B;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct C;
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<A>();
+            registry.register::<B>();
+            registry.register::<C>();
+        }
+        world.register_component::<A>();
+        world.register_component::<B>();
+        world.register_component::<C>();
+        world
+    }
+
+    fn registration<T: 'static>(world: &World) -> ScriptTypeRegistration {
+        ScriptTypeRegistration::new(Arc::new(
+            world
+                .resource::<AppTypeRegistry>()
+                .read()
+                .get(TypeId::of::<T>())
+                .unwrap()
+                .clone(),
+        ))
+    }
+
+    /// Runs the closure with a script world, like a script running in a frame of its own
+    fn frame<R>(world: &mut World, f: impl FnOnce(ScriptWorld) -> R) -> R {
+        world.increment_change_tick();
+        // SAFETY: the world is not accessed otherwise while the guard is alive
+        let guard = unsafe { WorldPointerGuard::new(world) };
+        f(ScriptWorld::new(guard.clone()))
+    }
+
+    fn entities(results: impl IntoIterator<Item = ScriptQueryResult>) -> Vec<Entity> {
+        let mut entities = results
+            .into_iter()
+            .map(|result| result.0)
+            .collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn test_changed_and_added_since_last_run() {
+        let mut world = setup();
+        let (a, b) = (registration::<A>(&world), registration::<B>(&world));
+        let unchanged = world.spawn(A(0)).id();
+        let changed = world.spawn(A(1)).id();
+        let added = world.spawn(B).id();
+
+        frame(&mut world, |world| {
+            // on the first run everything counts as changed
+            let changed_entities = ScriptQueryBuilder::new(world.clone())
+                .changed(vec![a.clone()])
+                .for_script(0)
+                .build()
+                .unwrap();
+            assert_eq!(entities(changed_entities), vec![unchanged, changed]);
+
+            // queries made later in the same run see the same changes
+            let added_entities = ScriptQueryBuilder::new(world)
+                .components(vec![a.clone()])
+                .added(vec![a.clone()])
+                .for_script(0)
+                .build()
+                .unwrap();
+            assert_eq!(entities(added_entities), vec![unchanged, changed]);
+        });
+
+        world.increment_change_tick();
+        world.get_mut::<A>(changed).unwrap().0 = 2;
+        world.entity_mut(added).insert(A(3));
+
+        frame(&mut world, |world| {
+            let mut query = ScriptQueryBuilder::new(world.clone());
+            query.components(vec![a.clone()]).for_script(0);
+
+            let changed_entities = query.clone().changed(vec![a.clone()]).build().unwrap();
+            assert_eq!(entities(changed_entities), vec![changed, added]);
+
+            let added_entities = query.clone().added(vec![a.clone()]).build().unwrap();
+            assert_eq!(entities(added_entities), vec![added]);
+
+            // other scripts have their own last run
+            let other_script = query
+                .clone()
+                .changed(vec![a.clone()])
+                .for_script(1)
+                .build()
+                .unwrap();
+            assert_eq!(entities(other_script), vec![unchanged, changed, added]);
+
+            let with_b = query.with(vec![b.clone()]).build().unwrap();
+            assert_eq!(entities(with_b), vec![added]);
+        });
+
+        frame(&mut world, |world| {
+            // nothing changed since the previous run
+            let changed_entities = ScriptQueryBuilder::new(world)
+                .changed(vec![a.clone()])
+                .for_script(0)
+                .build()
+                .unwrap();
+            assert_eq!(entities(changed_entities), vec![]);
+        });
+    }
+
+    #[test]
+    fn test_or_and_optional_archetypes() {
+        let mut world = setup();
+        let (a, b, c) = (
+            registration::<A>(&world),
+            registration::<B>(&world),
+            registration::<C>(&world),
+        );
+        let a_only = world.spawn(A(0)).id();
+        let a_b = world.spawn((A(1), B)).id();
+        let b_c = world.spawn((B, C)).id();
+        let c_only = world.spawn(C).id();
+
+        frame(&mut world, |world| {
+            let optional = ScriptQueryBuilder::new(world.clone())
+                .components(vec![a.clone()])
+                .optional(vec![b.clone(), c.clone()])
+                .build()
+                .unwrap();
+            let mut optional = optional
+                .iter()
+                .map(|result| {
+                    (
+                        result.0,
+                        result.1.len(),
+                        result.2.iter().map(Option::is_some).collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            optional.sort_by_key(|(entity, ..)| *entity);
+            assert_eq!(
+                optional,
+                vec![(a_only, 1, vec![false, false]), (a_b, 1, vec![true, false])]
+            );
+
+            let a_or_c = ScriptQueryBuilder::new(world.clone())
+                .or(ScriptQueryOr {
+                    with: vec![a.clone(), c.clone()],
+                    ..Default::default()
+                })
+                .build()
+                .unwrap();
+            assert_eq!(entities(a_or_c), vec![a_only, a_b, b_c, c_only]);
+
+            let b_or_not_c = ScriptQueryBuilder::new(world.clone())
+                .or(ScriptQueryOr {
+                    with: vec![b.clone()],
+                    without: vec![c.clone()],
+                    ..Default::default()
+                })
+                .build()
+                .unwrap();
+            assert_eq!(entities(b_or_not_c), vec![a_only, a_b, b_c]);
+
+            // every group has to match
+            let two_groups = ScriptQueryBuilder::new(world.clone())
+                .or(ScriptQueryOr {
+                    with: vec![a.clone()],
+                    without: vec![b.clone()],
+                    ..Default::default()
+                })
+                .or(ScriptQueryOr {
+                    with: vec![c.clone()],
+                    ..Default::default()
+                })
+                .build()
+                .unwrap();
+            assert_eq!(entities(two_groups), vec![c_only]);
+
+            let empty_group = ScriptQueryBuilder::new(world)
+                .components(vec![c.clone()])
+                .or(ScriptQueryOr::default())
+                .build()
+                .unwrap();
+            assert_eq!(entities(empty_group), vec![b_c, c_only]);
+        });
+    }
+
+    #[test]
+    fn test_or_with_added() {
+        let mut world = setup();
+        let (a, b) = (registration::<A>(&world), registration::<B>(&world));
+        let old_with_b = world.spawn((A(0), B)).id();
+        world.spawn(A(1));
+
+        frame(&mut world, |world| {
+            ScriptQueryBuilder::new(world)
+                .components(vec![a.clone()])
+                .for_script(0)
+                .build()
+                .unwrap();
+        });
+
+        world.increment_change_tick();
+        let new = world.spawn(A(2)).id();
+
+        frame(&mut world, |world| {
+            let added_or_b = ScriptQueryBuilder::new(world)
+                .components(vec![a.clone()])
+                .or(ScriptQueryOr {
+                    added: vec![a.clone()],
+                    with: vec![b.clone()],
+                    ..Default::default()
+                })
+                .for_script(0)
+                .build()
+                .unwrap();
+            assert_eq!(entities(added_or_b), vec![old_with_b, new]);
+        });
+    }
+
+    #[test]
+    fn test_iter_chunks() {
+        let mut world = setup();
+        let (a, b) = (registration::<A>(&world), registration::<B>(&world));
+        let count = CHUNK_SIZE as u32 * 3 + 7;
+        for i in 0..count {
+            match i % 3 {
+                0 => world.spawn(A(i)),
+                1 => world.spawn((A(i), B)),
+                _ => world.spawn((A(i), C)),
+            };
+        }
+
+        frame(&mut world, |mut world| {
+            let mut query = ScriptQueryBuilder::new(world.clone());
+            query.components(vec![a.clone()]);
+
+            let mut values = query
+                .iter()
+                .unwrap()
+                .map(|result| {
+                    result.1[0]
+                        .get(|component| component.downcast_ref::<A>().unwrap().0)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            values.sort();
+            assert_eq!(values, (0..count).collect::<Vec<_>>());
+
+            // entities which stop matching before they are reached are skipped
+            let with_b = query.clone().with(vec![b.clone()]).build().unwrap();
+            let mut iter = query.clone().with(vec![b.clone()]).iter().unwrap();
+            let first = iter.next().unwrap().0;
+            for result in &with_b {
+                if result.0 != first {
+                    world.remove_component(result.0, b.clone()).unwrap();
+                }
+            }
+            assert_eq!(iter.count(), 0);
+
+            // the cached query matches archetypes created after it was compiled
+            let entity = world.write().spawn(B).id();
+            assert_eq!(query.iter().unwrap().count(), count as usize);
+            world.write().entity_mut(entity).insert(A(count));
+            assert_eq!(query.iter().unwrap().count(), count as usize + 1);
+        });
+    }
+
+    #[test]
+    fn test_prepared_query_across_frames() {
+        let mut world = setup();
+        let a = registration::<A>(&world);
+        let first = world.spawn(A(0)).id();
+
+        let prepared = frame(&mut world, |world| {
+            let prepared = ScriptQueryBuilder::new(world.clone())
+                .components(vec![a.clone()])
+                .changed(vec![a.clone()])
+                .for_script(0)
+                .prepare()
+                .unwrap();
+            assert_eq!(entities(prepared.iter(&world).unwrap()), vec![first]);
+            prepared
+        });
+
+        world.increment_change_tick();
+        let second = world.spawn((A(1), B)).id();
+
+        frame(&mut world, |world| {
+            // the new archetype is matched and only the new entity changed since the previous run
+            assert_eq!(entities(prepared.iter(&world).unwrap()), vec![second]);
+            assert_eq!(prepared.single(&world).unwrap().0, second);
+            assert!(prepared.get(&world, first).unwrap().is_none());
+
+            let result = prepared.get(&world, second).unwrap().unwrap();
+            assert_eq!(
+                result.1[0]
+                    .get(|component| component.downcast_ref::<A>().unwrap().0)
+                    .unwrap(),
+                1
+            );
+            assert_eq!(prepared.query().components.len(), 1);
+        });
+
+        world.increment_change_tick();
+        world.get_mut::<A>(first).unwrap().0 = 2;
+
+        frame(&mut world, |world| {
+            assert_eq!(prepared.single(&world).unwrap().0, first);
+            assert!(prepared.get(&world, second).unwrap().is_none());
+        });
+    }
+}
@@ -0,0 +1,548 @@
+use std::{alloc::Layout, any::Any, collections::HashMap, sync::Arc};
+
+use bevy::{
+    ecs::{
+        component::{ComponentDescriptor, ComponentId, StorageType},
+        world::{EntityRef, EntityWorldMut},
+    },
+    prelude::{Mut, ReflectDefault, Resource, World},
+    ptr::OwningPtr,
+    reflect::{
+        utility::NonGenericTypeInfoCell, ApplyError, DynamicStruct, OpaqueInfo, PartialReflect,
+        Reflect, ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypePath, TypeRegistry, Typed,
+    },
+};
+
+use crate::{error::ReflectionError, script_value::from_reflect_with_fallback, ScriptValue};
+
+use super::ScriptTypeRegistration;
+
+/// The value of a component or resource of a type defined by a script.
+///
+/// Reflects as a struct with the fields declared by the script, each field holds a value of a rust type.
+/// Values are not serializable, see [`ScriptWorld::register_script_type`](super::ScriptWorld::register_script_type).
+#[derive(TypePath, Default)]
+pub struct ScriptStruct(DynamicStruct);
+
+impl ScriptStruct {
+    /// Returns the fields of this value
+    pub fn fields(&self) -> &DynamicStruct {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for ScriptStruct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Like bevy's own dynamic types, the static type info is opaque, the fields are only known per value
+impl Typed for ScriptStruct {
+    fn type_info() -> &'static TypeInfo {
+        static CELL: NonGenericTypeInfoCell = NonGenericTypeInfoCell::new();
+        CELL.get_or_set(|| TypeInfo::Opaque(OpaqueInfo::new::<Self>()))
+    }
+}
+
+impl PartialReflect for ScriptStruct {
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        self.0.get_represented_type_info()
+    }
+
+    fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self
+    }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        self
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        self
+    }
+
+    fn try_into_reflect(self: Box<Self>) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+        Ok(self)
+    }
+
+    fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+        Some(self)
+    }
+
+    fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        Some(self)
+    }
+
+    fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+        self.0.try_apply(value)
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Struct(&self.0)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Struct(&mut self.0)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Struct(Box::new(self.0))
+    }
+
+    fn clone_value(&self) -> Box<dyn PartialReflect> {
+        self.0.clone_value()
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+        self.0.reflect_partial_eq(value)
+    }
+
+    fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.debug(f)
+    }
+}
+
+impl Reflect for ScriptStruct {
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+}
+
+/// Whether a script defined type is a component or a resource
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptTypeKind {
+    Component,
+    Resource,
+}
+
+/// A component or resource type defined by a script, see [`ScriptWorld::register_script_type`](super::ScriptWorld::register_script_type).
+///
+/// Components are registered with the world via [`World::register_component_with_descriptor`](bevy::prelude::World::register_component_with_descriptor)
+/// and resources via [`World::register_resource_with_descriptor`](bevy::prelude::World::register_resource_with_descriptor),
+/// both store a [`ScriptStruct`] and live in the world like any other component or resource.
+/// Neither has a type registration, so scenes and other users of the type registry do not see them.
+#[derive(Debug)]
+pub struct ScriptTypeDescriptor {
+    name: String,
+    kind: ScriptTypeKind,
+    fields: Vec<(String, ScriptTypeRegistration)>,
+    component_id: ComponentId,
+}
+
+impl ScriptTypeDescriptor {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> ScriptTypeKind {
+        self.kind
+    }
+
+    /// The names and types of the fields of this type, sorted by name
+    pub fn fields(&self) -> &[(String, ScriptTypeRegistration)] {
+        &self.fields
+    }
+
+    /// The id of this component or resource in the world
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Constructs a value of this type from a script value.
+    ///
+    /// The value needs to be a map from field names to field values, or a reflected struct with the fields of this type.
+    /// Missing fields are set to the default value of their type, which needs to have `ReflectDefault` type data.
+    pub fn construct(
+        &self,
+        value: ScriptValue,
+        registry: &TypeRegistry,
+    ) -> Result<ScriptStruct, ReflectionError> {
+        let mut given: HashMap<String, Box<dyn Reflect>> = HashMap::default();
+
+        match value {
+            ScriptValue::Unit => {}
+            ScriptValue::List(items) if items.is_empty() => {}
+            ScriptValue::Map(entries) => {
+                for (name, value) in entries {
+                    let ScriptValue::String(name) = name else {
+                        return Err(ReflectionError::Other(format!(
+                            "Field names of `{}` must be strings",
+                            self.name
+                        )));
+                    };
+                    let field_type = self.field_type(&name)?;
+                    given.insert(name, value.into_reflect(field_type, registry)?);
+                }
+            }
+            ScriptValue::Reflect(value) => {
+                let ReflectRef::Struct(value) = value.reflect_ref() else {
+                    return Err(ReflectionError::Other(format!(
+                        "Cannot convert `{}` to `{}`",
+                        value.reflect_type_path(),
+                        self.name
+                    )));
+                };
+                for (idx, field) in value.iter_fields().enumerate() {
+                    let name = value.name_at(idx).unwrap_or_default().to_owned();
+                    let field_type = self.field_type(&name)?;
+                    given.insert(name, from_reflect_with_fallback(field, field_type)?);
+                }
+            }
+            value => {
+                return Err(ReflectionError::Other(format!(
+                    "Cannot convert {} to `{}`, expected a map of fields",
+                    value.kind(),
+                    self.name
+                )))
+            }
+        }
+
+        let mut value = DynamicStruct::default();
+        for (name, field_type) in &self.fields {
+            let field = match given.remove(name) {
+                Some(field) => field,
+                None => field_type
+                    .data::<ReflectDefault>()
+                    .ok_or_else(|| {
+                        ReflectionError::Other(format!(
+                            "Field `{name}` of `{}` has no default value and needs to be given",
+                            self.name
+                        ))
+                    })?
+                    .default(),
+            };
+            value.insert_boxed(name.clone(), field.into_partial_reflect());
+        }
+
+        Ok(ScriptStruct(value))
+    }
+
+    fn field_type(&self, name: &str) -> Result<&ScriptTypeRegistration, ReflectionError> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, field_type)| field_type)
+            .ok_or_else(|| ReflectionError::Other(format!("`{}` has no field `{name}`", self.name)))
+    }
+
+    fn expect_component_id(&self) -> ComponentId {
+        assert!(
+            self.kind == ScriptTypeKind::Component,
+            "`{}` is not a component",
+            self.name
+        );
+        self.component_id
+    }
+
+    fn expect_resource_id(&self) -> ComponentId {
+        assert!(
+            self.kind == ScriptTypeKind::Resource,
+            "`{}` is not a resource",
+            self.name
+        );
+        self.component_id
+    }
+
+    /// Inserts the given value as this component into the entity, panics if this is not a component
+    pub fn insert(&self, entity: &mut EntityWorldMut, value: ScriptStruct) {
+        let component_id = self.expect_component_id();
+        OwningPtr::make(value, |ptr| {
+            // SAFETY: the component was registered with the layout and drop function of `ScriptStruct`
+            unsafe {
+                entity.insert_by_id(component_id, ptr);
+            }
+        });
+    }
+
+    /// Returns this component of the given entity, panics if this is not a component
+    pub fn reflect<'w>(&self, entity: EntityRef<'w>) -> Option<&'w ScriptStruct> {
+        entity
+            .get_by_id(self.expect_component_id())
+            .ok()
+            // SAFETY: the component is always a `ScriptStruct`
+            .map(|ptr| unsafe { ptr.deref::<ScriptStruct>() })
+    }
+
+    /// Returns this component of the given entity mutably, panics if this is not a component
+    pub fn reflect_mut<'a>(&self, entity: &'a mut EntityWorldMut) -> Option<Mut<'a, ScriptStruct>> {
+        entity
+            .get_mut_by_id(self.expect_component_id())
+            .ok()
+            // SAFETY: the component is always a `ScriptStruct`
+            .map(|ptr| ptr.map_unchanged(|ptr| unsafe { ptr.deref_mut::<ScriptStruct>() }))
+    }
+
+    /// Removes this component from the entity, panics if this is not a component
+    pub fn remove(&self, entity: &mut EntityWorldMut) {
+        entity.remove_by_id(self.expect_component_id());
+    }
+
+    /// Returns the value of this resource, panics if this is not a resource
+    pub fn resource<'w>(&self, world: &'w World) -> Option<&'w ScriptStruct> {
+        world
+            .get_resource_by_id(self.expect_resource_id())
+            // SAFETY: the resource is always a `ScriptStruct`
+            .map(|ptr| unsafe { ptr.deref::<ScriptStruct>() })
+    }
+
+    /// Returns the value of this resource mutably, panics if this is not a resource
+    pub fn resource_mut<'w>(&self, world: &'w mut World) -> Option<Mut<'w, ScriptStruct>> {
+        world
+            .get_resource_mut_by_id(self.expect_resource_id())
+            // SAFETY: the resource is always a `ScriptStruct`
+            .map(|ptr| ptr.map_unchanged(|ptr| unsafe { ptr.deref_mut::<ScriptStruct>() }))
+    }
+
+    /// Sets the value of this resource, panics if this is not a resource
+    pub fn insert_resource(&self, world: &mut World, value: ScriptStruct) {
+        let component_id = self.expect_resource_id();
+        OwningPtr::make(value, |ptr| {
+            // SAFETY: the resource was registered with the layout and drop function of `ScriptStruct`
+            unsafe {
+                world.insert_resource_by_id(component_id, ptr);
+            }
+        });
+    }
+
+    /// Removes this resource from the world, panics if this is not a resource
+    pub fn remove_resource(&self, world: &mut World) {
+        world.remove_resource_by_id(self.expect_resource_id());
+    }
+}
+
+unsafe fn drop_script_struct(ptr: OwningPtr<'_>) {
+    ptr.drop_as::<ScriptStruct>();
+}
+
+/// Stores the types defined by scripts
+#[derive(Resource, Default)]
+pub struct ScriptTypes {
+    types: HashMap<String, ScriptTypeRegistration>,
+}
+
+impl ScriptTypes {
+    /// Returns the script defined type with the given name
+    pub fn get(&self, name: &str) -> Option<&ScriptTypeRegistration> {
+        self.types.get(name)
+    }
+
+    /// Returns all script defined types
+    pub fn iter(&self) -> impl Iterator<Item = &ScriptTypeRegistration> {
+        self.types.values()
+    }
+
+    /// Defines a new type, returns the existing type if an identical one was defined before (e.g. by a reloaded script).
+    ///
+    /// Fails if a type with this name but a different kind or different fields exists, since components cannot be unregistered.
+    pub(crate) fn register(
+        &mut self,
+        name: String,
+        kind: ScriptTypeKind,
+        fields: Vec<(String, ScriptTypeRegistration)>,
+        world: &mut World,
+    ) -> Result<ScriptTypeRegistration, ReflectionError> {
+        if let Some(existing) = self.types.get(&name) {
+            let descriptor = existing
+                .script_type()
+                .expect("script types have descriptors");
+            let same_fields = descriptor.fields.len() == fields.len()
+                && descriptor
+                    .fields
+                    .iter()
+                    .zip(&fields)
+                    .all(|((a, a_ty), (b, b_ty))| {
+                        a == b && a_ty.type_info().type_id() == b_ty.type_info().type_id()
+                    });
+
+            return if descriptor.kind == kind && same_fields {
+                Ok(existing.clone())
+            } else {
+                Err(ReflectionError::Other(format!(
+                    "A different type named `{name}` was already defined"
+                )))
+            };
+        }
+
+        if let Some((field, _)) = fields.iter().find(|(_, ty)| ty.script_type().is_some()) {
+            return Err(ReflectionError::Other(format!(
+                "Field `{field}` of `{name}` has a script defined type, only rust types are supported"
+            )));
+        }
+
+        // SAFETY: `ScriptStruct` is `Send + Sync` and the layout and drop function belong to it
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                name.clone(),
+                StorageType::Table,
+                Layout::new::<ScriptStruct>(),
+                Some(drop_script_struct),
+            )
+        };
+        let component_id = match kind {
+            ScriptTypeKind::Component => world.register_component_with_descriptor(descriptor),
+            ScriptTypeKind::Resource => world.register_resource_with_descriptor(descriptor),
+        };
+
+        let registration =
+            ScriptTypeRegistration::new_script_type(Arc::new(ScriptTypeDescriptor {
+                name: name.clone(),
+                kind,
+                fields,
+                component_id,
+            }));
+        self.types.insert(name, registration.clone());
+        Ok(registration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use bevy::{
+        prelude::{AppTypeRegistry, Name},
+        reflect::Struct,
+        scene::DynamicSceneBuilder,
+    };
+
+    use super::*;
+
+    fn health(world: &mut World, kind: ScriptTypeKind) -> ScriptTypeRegistration {
+        world.init_resource::<AppTypeRegistry>();
+        let f32_type = ScriptTypeRegistration::new(Arc::new(
+            world
+                .resource::<AppTypeRegistry>()
+                .read()
+                .get(TypeId::of::<f32>())
+                .unwrap()
+                .clone(),
+        ));
+        ScriptTypes::default()
+            .register(
+                "Health".into(),
+                kind,
+                vec![("value".into(), f32_type)],
+                world,
+            )
+            .unwrap()
+    }
+
+    fn value(world: &World, health: &ScriptTypeDescriptor, value: f64) -> ScriptStruct {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        health
+            .construct(
+                ScriptValue::Map(vec![(
+                    ScriptValue::String("value".into()),
+                    ScriptValue::Float(value),
+                )]),
+                &registry,
+            )
+            .unwrap()
+    }
+
+    fn field(value: &ScriptStruct) -> f32 {
+        *value
+            .fields()
+            .field("value")
+            .unwrap()
+            .try_downcast_ref::<f32>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_component_lives_in_world() {
+        let mut world = World::new();
+        let health = health(&mut world, ScriptTypeKind::Component);
+        let health = health.script_type().unwrap();
+        let value = value(&world, health, 5.0);
+
+        let entity = world.spawn_empty().id();
+        health.insert(&mut world.entity_mut(entity), value);
+
+        let component_id = health.component_id();
+        assert!(world.entity(entity).contains_id(component_id));
+        assert_eq!(
+            world.components().get_info(component_id).unwrap().name(),
+            "Health"
+        );
+        assert_eq!(field(health.reflect(world.entity(entity)).unwrap()), 5.0);
+
+        health.remove(&mut world.entity_mut(entity));
+        assert!(!world.entity(entity).contains_id(component_id));
+    }
+
+    #[test]
+    fn test_resource_lives_in_world() {
+        let mut world = World::new();
+        let health = health(&mut world, ScriptTypeKind::Resource);
+        let health = health.script_type().unwrap();
+        let value = value(&world, health, 5.0);
+
+        assert!(health.resource(&world).is_none());
+        health.insert_resource(&mut world, value);
+
+        let component_id = health.component_id();
+        assert!(world.get_resource_by_id(component_id).is_some());
+        assert_eq!(
+            world.components().get_info(component_id).unwrap().name(),
+            "Health"
+        );
+        assert_eq!(field(health.resource(&world).unwrap()), 5.0);
+
+        health.remove_resource(&mut world);
+        assert!(world.get_resource_by_id(component_id).is_none());
+    }
+
+    #[test]
+    fn test_component_is_left_out_of_scenes() {
+        let mut world = World::new();
+        let health = health(&mut world, ScriptTypeKind::Component);
+        let health = health.script_type().unwrap();
+        let value = value(&world, health, 5.0);
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Name>();
+
+        let entity = world.spawn(Name::new("Player")).id();
+        health.insert(&mut world.entity_mut(entity), value);
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entity(entity)
+            .build();
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].components.len(), 1);
+
+        let serialized = scene
+            .serialize(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        assert!(serialized.contains("Player"));
+        assert!(!serialized.contains("Health"));
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    any::Any, borrow::Cow, collections::HashSet, fmt::Debug, marker::PhantomData, sync::Arc,
+};
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use bevy_mod_scripting_core::{
+    hosts::{ScriptContexts, ScriptHost},
+    prelude::{ScriptError, ScriptErrorEvent},
+    world::WorldPointer,
+};
+
+use super::{events::SharedReflect, query::ScriptQueryCache, ScriptQuery};
+
+/// The system set containing the runner for script systems registered under the given label.
+///
+/// Use it to order script systems relative to your own systems:
+/// ```rust,ignore
+/// app.add_script_system_set::<LuaScriptHost<()>>(Update, "update")
+///     .configure_sets(Update, ScriptSystemLabel::new("update").after(my_physics_system));
+/// ```
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScriptSystemLabel(pub Cow<'static, str>);
+
+impl ScriptSystemLabel {
+    pub fn new(label: impl Into<Cow<'static, str>>) -> Self {
+        Self(label.into())
+    }
+}
+
+/// A system registered by a script, i.e. a script function paired with a query.
+/// The callback receives a query builder for the given query every time the system runs.
+#[derive(Clone, Debug)]
+pub struct ScriptSystem {
+    /// the id of the script owning the callback
+    pub sid: u32,
+    /// the label of the script system set this system runs in
+    pub label: String,
+    /// the script function to call
+    pub callback: ScriptFunctionRef,
+    /// the query handed to the callback
+    pub query: ScriptQuery,
+    /// the compiled query, kept between runs
+    pub cache: ScriptQueryCache,
+}
+
+/// Stores the systems registered by scripts running in contexts of type `C`.
+///
+/// Systems are identified by their owning script and callback function, registering the same function twice replaces the old system.
+/// Systems belonging to scripts which were removed are dropped the next time their set runs.
+#[derive(Resource)]
+pub struct ScriptSystems<C> {
+    systems: Vec<ScriptSystem>,
+    labels: HashSet<String>,
+    _ph: PhantomData<fn() -> C>,
+}
+
+impl<C> Default for ScriptSystems<C> {
+    fn default() -> Self {
+        Self {
+            systems: Default::default(),
+            labels: Default::default(),
+            _ph: PhantomData,
+        }
+    }
+}
+
+impl<C> ScriptSystems<C> {
+    /// Registers a new script system, replacing any system with the same script and callback.
+    pub fn insert(&mut self, system: ScriptSystem) -> Result<(), ScriptError> {
+        if !self.labels.contains(&system.label) {
+            return Err(ScriptError::Other(format!(
+                "No script system set with label `{}` exists. Add one with `app.add_script_system_set`",
+                system.label
+            )));
+        }
+
+        self.remove(system.sid, system.callback.id());
+        self.systems.push(system);
+        Ok(())
+    }
+
+    /// Removes the system with the given script and callback function id, returns true if one existed.
+    pub fn remove(&mut self, sid: u32, callback: &str) -> bool {
+        let len = self.systems.len();
+        self.systems
+            .retain(|s| !(s.sid == sid && s.callback.id() == callback));
+        len != self.systems.len()
+    }
+
+    /// Returns the labels of all script system sets known to this host
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.labels.iter().map(String::as_str)
+    }
+
+    /// Returns all systems in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ScriptSystem> {
+        self.systems.iter()
+    }
+
+    /// Drops the systems of scripts which no longer exist and returns the systems registered under the given label
+    pub fn prepare_run(&mut self, label: &str, contexts: &ScriptContexts<C>) -> Vec<ScriptSystem> {
+        self.systems
+            .retain(|s| contexts.context_entities.contains_key(&s.sid));
+        self.systems
+            .iter()
+            .filter(|s| s.label == label)
+            .cloned()
+            .collect()
+    }
+}
+
+/// The argument a script function is called with by [`ScriptSystemHost::call_script_functions`]
+#[derive(Clone, Debug)]
+pub enum ScriptSystemArg {
+    /// a query builder for the given query, using the given cache for the compiled query
+    Query(ScriptQuery, ScriptQueryCache),
+    /// a single entity
+    Entity(Entity),
+    /// a copy of an observed event followed by the entity it was triggered for, if any
+    Event {
+        event: SharedReflect,
+        target: Option<Entity>,
+    },
+}
+
+/// A function value handed over by a script, e.g. a Lua function kept in the registry or a Rhai function pointer.
+///
+/// The value is stored by the script host, which is the only one able to call it.
+/// Two references are equal if their ids are, the host picks ids identifying the function.
+#[derive(Clone)]
+pub struct ScriptFunctionRef {
+    id: String,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl ScriptFunctionRef {
+    pub fn new<T: Any + Send + Sync>(id: impl Into<String>, value: T) -> Self {
+        Self {
+            id: id.into(),
+            value: Arc::new(value),
+        }
+    }
+
+    /// The id identifying the function
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the stored value, if it is of the given type
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+impl Debug for ScriptFunctionRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ScriptFunctionRef").field(&self.id).finish()
+    }
+}
+
+impl PartialEq for ScriptFunctionRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// The function called by a [`ScriptFunctionCall`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptCallback {
+    /// a global function defined by the script, by name
+    Named(String),
+    /// a function value handed over by the script
+    Value(ScriptFunctionRef),
+}
+
+/// A call to a function defined by a script
+#[derive(Clone, Debug)]
+pub struct ScriptFunctionCall {
+    /// the id of the script defining the function
+    pub sid: u32,
+    /// the function to call
+    pub callback: ScriptCallback,
+    /// the argument passed to the function
+    pub arg: ScriptSystemArg,
+}
+
+impl From<ScriptSystem> for ScriptFunctionCall {
+    fn from(system: ScriptSystem) -> Self {
+        Self {
+            sid: system.sid,
+            callback: ScriptCallback::Value(system.callback),
+            arg: ScriptSystemArg::Query(system.query, system.cache),
+        }
+    }
+}
+
+/// Implemented by script hosts which are able to run script systems.
+pub trait ScriptSystemHost: ScriptHost {
+    /// Calls the given script functions in order, each in the context of the script defining it.
+    /// Calls to scripts which are not loaded are skipped, errors are reported via [`ScriptErrorEvent`]s.
+    fn call_script_functions(world: &mut World, calls: Vec<ScriptFunctionCall>);
+
+    /// Runs every script system registered under the given label, in registration order.
+    fn run_script_systems(world: &mut World, label: &str) {
+        let calls = world
+            .resource_scope(
+                |world, mut systems: Mut<ScriptSystems<Self::ScriptContext>>| {
+                    systems.prepare_run(label, world.resource())
+                },
+            )
+            .into_iter()
+            .map(ScriptFunctionCall::from)
+            .collect::<Vec<_>>();
+
+        if !calls.is_empty() {
+            Self::call_script_functions(world, calls);
+        }
+    }
+}
+
+/// Reports an error produced by a script function called by a runner via a [`ScriptErrorEvent`].
+pub fn report_script_system_error(world: &WorldPointer, error: ScriptError) {
+    let mut world = world.write();
+    error!("{}", error);
+    world.send_event(ScriptErrorEvent { error });
+}
+
+pub trait AddScriptSystemSet {
+    /// Adds a set in which scripts can register systems under the given label.
+    ///
+    /// Every frame the set runs each script system registered under this label, in the given schedule.
+    /// The set can be ordered relative to other systems via [`ScriptSystemLabel`].
+    fn add_script_system_set<H: ScriptSystemHost>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        label: impl Into<Cow<'static, str>>,
+    ) -> &mut Self;
+}
+
+impl AddScriptSystemSet for App {
+    fn add_script_system_set<H: ScriptSystemHost>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        label: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        let label = ScriptSystemLabel::new(label);
+        let name = label.0.to_string();
+
+        self.init_resource::<ScriptSystems<H::ScriptContext>>();
+        self.world_mut()
+            .resource_mut::<ScriptSystems<H::ScriptContext>>()
+            .labels
+            .insert(name.clone());
+
+        self.add_systems(
+            schedule,
+            (move |world: &mut World| H::run_script_systems(world, &name)).in_set(label),
+        )
+    }
+}
@@ -57,6 +57,8 @@ impl bevy_mod_scripting_core::hosts::APIProvider for LuaCoreBevyAPIProvider {
 			.process_type::<crate::lua::bevy::LuaTypeRegistration>()
 			.process_type::<crate::lua::std::LuaVec<T>>()
             .process_type::<crate::lua::bevy::LuaQueryBuilder>()
+            .process_type::<crate::lua::bevy::LuaPreparedQuery>()
+            .process_type::<crate::lua::bevy::LuaScriptCommands>()
             },
         ))
     }
@@ -5,20 +5,23 @@ pub mod error;
 pub mod lua;
 #[cfg(feature = "rhai")]
 pub mod rhai;
+#[cfg(feature = "rune")]
+pub mod rune;
 
 pub mod common;
 
 #[cfg(feature = "lua")]
 pub mod core_providers;
-// for now providers do not support any other lang so just remove this whole module if they are not needed
-#[cfg(feature = "lua")]
+// providers are generated for Lua and Rhai, remove this whole module if they are not needed
+#[cfg(any(feature = "lua", feature = "rhai"))]
 pub mod providers;
 
 pub mod script_ref;
+pub mod script_value;
 pub mod sub_reflect;
 pub mod wrappers;
 
-pub use {script_ref::*, sub_reflect::*};
+pub use {script_ref::*, script_value::*, sub_reflect::*};
 
 pub mod prelude {
     #[cfg(feature = "lua")]
@@ -30,16 +33,35 @@ pub mod prelude {
     };
 
     #[cfg(feature = "rhai")]
-    pub use crate::rhai::{
-        bevy::RhaiBevyAPIProvider,
-        std::{RhaiCopy, RhaiVec},
-        FromRhaiProxy, ReflectRhaiProxyable, RhaiProxyable, ToRhaiProxy,
+    pub use crate::{
+        providers::RhaiBevyTypesAPIProvider,
+        rhai::{
+            bevy::RhaiBevyAPIProvider,
+            std::{RhaiCopy, RhaiVec},
+            FromRhaiProxy, ReflectRhaiProxyable, RegisterRhaiProxy, RhaiProxyable, ToRhaiProxy,
+        },
+        RhaiProxy,
     };
 
-    pub use crate::{common::bevy::GetWorld, ValueIndex};
+    #[cfg(feature = "rune")]
+    pub use crate::rune::{bevy::RuneBevyAPIProvider, ApplyRune, RuneEntity, ToRuneValue};
+
+    pub use crate::{
+        common::bevy::{
+            events::ReflectEvent,
+            hooks::{AddScriptChangeHooks, ScriptChangeHookSet},
+            observers::AddScriptObservers,
+            systems::{AddScriptSystemSet, ScriptSystemLabel},
+            GetWorld,
+        },
+        ValueIndex,
+    };
 }
 
 #[cfg(feature = "lua")]
 pub use bevy_mod_scripting_lua_derive::LuaProxy;
 
+#[cfg(feature = "rhai")]
+pub use bevy_mod_scripting_rhai_derive::RhaiProxy;
+
 pub use parking_lot;
@@ -1,19 +1,34 @@
 use crate::common::bevy::{
-    ScriptQueryBuilder, ScriptQueryResult, ScriptTypeRegistration, ScriptWorld,
+    commands::ScriptCommands,
+    events::SharedReflect,
+    hooks::{ComponentChange, ScriptChangeHook},
+    observers::ScriptObserver,
+    query::ScriptPreparedQuery,
+    script_types::ScriptTypeKind,
+    systems::{
+        report_script_system_error, ScriptCallback, ScriptFunctionCall, ScriptFunctionRef,
+        ScriptSystem, ScriptSystemArg, ScriptSystemHost,
+    },
+    GetWorld, ScriptQueryBuilder, ScriptQueryOr, ScriptQueryResult, ScriptTypeRegistration,
+    ScriptWorld,
 };
 use crate::lua::{
+    lua_to_script_value,
     mlua::prelude::{IntoLuaMulti, LuaError, LuaMultiValue},
     tealr::{mlu::TypedFunction, ToTypename},
-    util::{VariadicComponents, VariadicQueryResult},
-    Lua,
+    util::{VariadicComponents, VariadicFunctionCall, VariadicQueryResult},
+    ApplyLua, Lua,
 };
 use crate::providers::bevy_ecs::LuaEntity;
-use crate::{impl_from_lua_with_clone, impl_tealr_type};
+use crate::{impl_from_lua_with_clone, impl_tealr_type, ReflectReference};
 use bevy::hierarchy::BuildChildren;
-use bevy::prelude::{AppTypeRegistry, ReflectResource};
-use bevy_mod_scripting_core::prelude::*;
-use bevy_mod_scripting_lua::{prelude::IntoLua, tealr};
-use std::sync::Arc;
+use bevy::prelude::{Entity, World};
+use bevy_mod_scripting_core::{
+    prelude::*,
+    world::{ScriptCommandQueue, WorldPointerGuard},
+};
+use bevy_mod_scripting_lua::{prelude::IntoLua, tealr, LuaArg, LuaScriptHost};
+use std::sync::Mutex;
 
 use tealr::mlu::{
     mlua::{self},
@@ -23,12 +38,19 @@ use tealr::mlu::{
 use super::util::LuaIndex;
 
 pub type LuaTypeRegistration = ScriptTypeRegistration;
+
+/// A function called by a change hook with the entity whose component changed
+pub type LuaChangeHookCallback<'lua> = TypedFunction<'lua, LuaEntity, ()>;
+/// A function called by a script system with a query builder for the system's query
+pub type LuaSystemCallback<'lua> = TypedFunction<'lua, LuaQueryBuilder, ()>;
+/// A function called by an observer with a copy of the event and the entity it was triggered for, if any
+pub type LuaObserverCallback<'lua> = TypedFunction<'lua, (ReflectReference, Option<LuaEntity>), ()>;
 impl_tealr_type!(LuaTypeRegistration);
 impl_from_lua_with_clone!(LuaTypeRegistration);
 
 impl TealData for LuaTypeRegistration {
     fn add_methods<'lua, T: TealDataMethods<'lua, Self>>(methods: &mut T) {
-        methods.document_type("An object representing an existing and registered rust type.");
+        methods.document_type("An object representing an existing and registered rust type, or a type defined by a script.");
         methods.document_type("Can be obtained via [`LuaWorld::get_type_by_name`].");
     }
 
@@ -37,7 +59,22 @@ impl TealData for LuaTypeRegistration {
         fields.add_field_method_get("short_name", |_, s| Ok(s.short_name().to_string()));
 
         fields.document("The full name of the type");
-        fields.add_field_method_get("type_name", |_, s| Ok(s.type_name()));
+        fields.add_field_method_get("type_name", |_, s| Ok(s.type_name().to_owned()));
+
+        fields.document("A function constructing a value of this type from the given value, i.e. `Type.new{ field = 1 }`.");
+        fields.document("See [`LuaWorld::insert_component`] for how tables are converted.");
+        fields.add_field_method_get("new", |ctx, s| {
+            let value_type = s.clone();
+            TypedFunction::from_rust(
+                move |ctx, value: mlua::Value| {
+                    let value = lua_to_script_value(ctx, value)?;
+                    ScriptWorld::new(ctx.get_world()?)
+                        .construct(&value_type, value)
+                        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+                },
+                ctx,
+            )
+        });
     }
 }
 
@@ -46,6 +83,16 @@ pub struct LuaScriptData {
     sid: u32,
 }
 
+impl LuaScriptData {
+    /// Retrieves the id of the script owning the given context from its `script` global
+    pub fn current_sid(ctx: &Lua) -> mlua::Result<u32> {
+        ctx.globals()
+            .get::<_, mlua::AnyUserData>("script")?
+            .borrow::<LuaScriptData>()
+            .map(|s| s.sid)
+    }
+}
+
 impl From<&ScriptData<'_>> for LuaScriptData {
     fn from(sd: &ScriptData) -> Self {
         Self { sid: sd.sid }
@@ -116,37 +163,169 @@ impl TealData for LuaQueryBuilder {
             Ok(s.clone())
         });
 
+        methods.document("Fetches the components passed if they exist, like `Option<&T>`.");
+        methods.document("Optional components are returned after the required components, as `nil` for entities without them.");
+        methods.add_method_mut("optional", |_, s, components: VariadicComponents| {
+            s.optional(components.0);
+            Ok(s.clone())
+        });
+
+        methods.document("Filters out entities whose components passed did not change since the script last ran, like `Changed<T>`.");
+        methods.add_method_mut("changed", |_, s, components: VariadicComponents| {
+            s.changed(components.0);
+            Ok(s.clone())
+        });
+
+        methods.document("Filters out entities whose components passed were not added since the script last ran, like `Added<T>`.");
+        methods.add_method_mut("added", |_, s, components: VariadicComponents| {
+            s.added(components.0);
+            Ok(s.clone())
+        });
+
+        methods
+            .document("Filters out entities matching none of the given filters, like `Or<...>`.");
+        methods.document("The filters are given as a table of component lists, e.g. `query:any_of{ with = { A }, changed = { B } }`, valid keys are `with`, `without`, `changed` and `added`.");
+        methods.add_method_mut("any_of", |_, s, group: mlua::Table| {
+            let mut or = ScriptQueryOr::default();
+            for pair in group.pairs::<String, Vec<LuaTypeRegistration>>() {
+                let (key, components) = pair?;
+                match key.as_str() {
+                    "with" => or.with.extend(components),
+                    "without" => or.without.extend(components),
+                    "changed" => or.changed.extend(components),
+                    "added" => or.added.extend(components),
+                    _ => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "Unknown filter `{key}`, expected one of `with`, `without`, `changed` or `added`"
+                        )))
+                    }
+                }
+            }
+            s.or(or);
+            Ok(s.clone())
+        });
+
         methods
             .document("Queries the world and returns an iterator over the entity and components.");
+        methods.document("Queries without components iterate over the matching entities only.");
+        methods.document("Results are taken from the world as the iterator advances, the query itself is compiled once and reused by later calls on the same builder.");
         methods.add_method_mut("iter", |ctx, s, _: ()| {
-            let query_result = s
-                .build()
+            let mut query_iter = s
+                .iter()
                 .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
 
-            let len = query_result.len();
-            let mut curr_idx = 0;
             TypedFunction::from_rust_mut(
                 move |_, ()| {
-                    let o = if curr_idx < len {
-                        let query_result = query_result.get(curr_idx).unwrap();
-                        VariadicQueryResult::Some(
-                            LuaEntity::new(query_result.0),
-                            query_result.1.clone(),
-                        )
-                    } else {
-                        VariadicQueryResult::None
-                    };
-                    curr_idx += 1;
-                    Ok(o)
+                    Ok(match query_iter.next() {
+                        Some(result) => {
+                            VariadicQueryResult::Some(LuaEntity::new(result.0), result.1, result.2)
+                        }
+                        None => VariadicQueryResult::None,
+                    })
+                },
+                ctx,
+            )
+        });
+
+        methods.document("Compiles the query into a `LuaPreparedQuery` which can be stored and run again in later frames.");
+        methods.add_method("prepare", |_, s, ()| {
+            s.prepare()
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        });
+    }
+}
+
+pub type LuaPreparedQuery = ScriptPreparedQuery;
+
+impl_tealr_type!(LuaPreparedQuery);
+impl_from_lua_with_clone!(LuaPreparedQuery);
+
+impl TealData for LuaPreparedQuery {
+    fn add_fields<'lua, F: tealr::mlu::TealDataFields<'lua, Self>>(fields: &mut F) {
+        fields.document("A compiled query which can be kept, e.g. in a global, and run again every frame without being rebuilt.");
+        fields.document("It always runs in the `world` of the script calling its methods.");
+    }
+
+    fn add_methods<'lua, T: TealDataMethods<'lua, Self>>(methods: &mut T) {
+        methods
+            .document("Queries the world and returns an iterator over the entity and components.");
+        methods.add_method("iter", |ctx, s, ()| {
+            let mut query_iter = s
+                .iter(&ScriptWorld::new(ctx.get_world()?))
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+
+            TypedFunction::from_rust_mut(
+                move |_, ()| {
+                    Ok(match query_iter.next() {
+                        Some(result) => {
+                            VariadicQueryResult::Some(LuaEntity::new(result.0), result.1, result.2)
+                        }
+                        None => VariadicQueryResult::None,
+                    })
                 },
                 ctx,
             )
         });
+
+        methods
+            .document("Returns the entity and components of the only entity matching the query.");
+        methods.document("Errors if no entity or more than one entity matches.");
+        methods.add_method("single", |ctx, s, ()| {
+            s.single(&ScriptWorld::new(ctx.get_world()?))
+                .map(|result| {
+                    VariadicQueryResult::Some(LuaEntity::new(result.0), result.1, result.2)
+                })
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        });
+
+        methods.document(
+            "Returns the given entity and its components, or `nil` if it does not match the query.",
+        );
+        methods.add_method("get", |ctx, s, entity: LuaEntity| {
+            s.get(&ScriptWorld::new(ctx.get_world()?), entity.inner()?)
+                .map(|result| match result {
+                    Some(result) => {
+                        VariadicQueryResult::Some(LuaEntity::new(result.0), result.1, result.2)
+                    }
+                    None => VariadicQueryResult::None,
+                })
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        });
     }
 }
 
 pub type LuaWorld = ScriptWorld;
 
+/// Converts a table from field names to type names or type registrations into the fields of a script defined type
+fn script_type_fields<'lua>(
+    ctx: &'lua Lua,
+    world: &ScriptWorld,
+    fields: Option<mlua::Table<'lua>>,
+) -> mlua::Result<Vec<(String, ScriptTypeRegistration)>> {
+    let Some(fields) = fields else {
+        return Ok(Vec::default());
+    };
+
+    fields
+        .pairs::<String, mlua::Value>()
+        .map(|pair| {
+            let (name, field_type) = pair?;
+            let field_type = match field_type {
+                mlua::Value::String(type_name) => {
+                    let type_name = type_name.to_str()?;
+                    world.get_type_by_name(type_name).ok_or_else(|| {
+                        mlua::Error::RuntimeError(format!(
+                            "Field `{name}` has unknown type `{type_name}`"
+                        ))
+                    })?
+                }
+                field_type => mlua::FromLua::from_lua(field_type, ctx)?,
+            };
+            Ok((name, field_type))
+        })
+        .collect()
+}
+
 impl_tealr_type!(LuaWorld);
 impl_from_lua_with_clone!(LuaWorld);
 
@@ -161,22 +340,38 @@ impl TealData for LuaWorld {
 
         methods.document("Retrieves type information given either a short (`MyType`) or fully qualified rust type name (`MyModule::MyType`).");
         methods.document(
-            "Returns `nil` if no such type exists or if one wasn't registered on the rust side.",
+            "Returns `nil` if no such type exists or if one wasn't registered on the rust side or defined by a script.",
         );
         methods.document("\n");
         methods.document("This is used extensively in [`LuaWorld`]");
         methods.add_method("get_type_by_name", |_, world, type_name: String| {
-            let w = world.read();
-
-            let registry: &AppTypeRegistry = w.get_resource().unwrap();
+            Ok(world.get_type_by_name(&type_name))
+        });
 
-            let registry = registry.read();
+        methods.document("Defines a new component type with the given fields, e.g. `world:register_component(\"Health\", { value = \"f32\" })`.");
+        methods.document("Field types are given as rust type names or type registrations. The component can be used like any other component, including in queries.");
+        methods.document("Defining the same type again (e.g. when the script is reloaded) returns the existing type, defining it with different fields is an error.");
+        methods.document("Script defined types have no rust type registration, so they are left out of scenes and other reflection based serialization.");
+        methods.add_method(
+            "register_component",
+            |ctx, world, (name, fields): (String, Option<mlua::Table>)| {
+                let fields = script_type_fields(ctx, world, fields)?;
+                world
+                    .register_script_type(name, ScriptTypeKind::Component, fields)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
 
-            Ok(registry
-                .get_with_short_type_path(&type_name)
-                .or_else(|| registry.get_with_type_path(&type_name))
-                .map(|registration| LuaTypeRegistration::new(Arc::new(registration.clone()))))
-        });
+        methods.document("Defines a new resource type with the given fields, see [`LuaWorld::register_component`].");
+        methods.add_method(
+            "register_resource",
+            |ctx, world, (name, fields): (String, Option<mlua::Table>)| {
+                let fields = script_type_fields(ctx, world, fields)?;
+                world
+                    .register_script_type(name, ScriptTypeKind::Resource, fields)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
 
         methods.document("Inserts a component of the given type to the given entity by instantiating a default version of it.");
         methods.document("The component can then be modified using field access.");
@@ -189,6 +384,31 @@ impl TealData for LuaWorld {
             },
         );
 
+        methods.document("Constructs a component of the given type from the given value and inserts it into the given entity, returns a reference to it.");
+        methods.document("Tables are converted recursively: structs and maps are given as tables with keys, lists, tuples and tuple structs as sequences.");
+        methods.document("Enums are given as the name of a unit variant or as a table from the variant name to its fields, e.g. `{ Moving = { speed = 2.0 } }`.");
+        methods.document("Fields missing from the table are filled in from the default value of the component, if it has `ReflectDefault` type data.");
+        methods.add_method(
+            "insert_component",
+            |ctx, world, (entity, comp_type, value): (LuaEntity, LuaTypeRegistration, mlua::Value)| {
+                let value = lua_to_script_value(ctx, value)?;
+                world
+                    .insert_component(entity.inner()?, comp_type, value)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Constructs a value of the given type from the given value, see [`LuaWorld::insert_component`] for how tables are converted.");
+        methods.add_method(
+            "construct",
+            |ctx, world, (value_type, value): (LuaTypeRegistration, mlua::Value)| {
+                let value = lua_to_script_value(ctx, value)?;
+                world
+                    .construct(&value_type, value)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
         methods.document("Retrieves a component of the given type from the given entity.");
         methods.document("If such a component does not exist returns `nil`.");
         methods.add_method(
@@ -200,14 +420,50 @@ impl TealData for LuaWorld {
             },
         );
 
+        methods.document("Calls the function registered in the `AppFunctionRegistry` under the given name, e.g. `world:call_function(\"my_game::spawn_wave\", 3)`.");
+        methods.document("Arguments are converted to the types the function expects, `nil` is returned if the function returns `()`.");
+        methods.document("Methods registered this way can also be called on reflected values directly, i.e. `health:heal(5)`.");
+        methods.add_method("call_function", |ctx, world, call: VariadicFunctionCall| {
+            let args = call
+                .args
+                .into_iter()
+                .map(|arg| lua_to_script_value(ctx, arg))
+                .collect::<mlua::Result<Vec<_>>>()?;
+
+            world
+                .call_function(&call.name, args)
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        });
+
         methods.document("Creates a LuaQueryBuilder, querying for the passed components types.");
         methods.document("Can be iterated over using `LuaQueryBuilder:iter()`");
-        methods.add_method_mut("query", |_, world, components: VariadicComponents| {
-            Ok(LuaQueryBuilder::new(world.clone())
-                .components(components.0)
-                .clone())
+        methods.add_method_mut("query", |ctx, world, components: VariadicComponents| {
+            let mut query = LuaQueryBuilder::new(world.clone());
+            query.components(components.0);
+            if let Ok(sid) = LuaScriptData::current_sid(ctx) {
+                query.for_script(sid);
+            }
+            Ok(query)
         });
 
+        methods
+            .document("Compiles a query for the passed component types into a `LuaPreparedQuery`.");
+        methods.document("The query can be stored and run every frame via `LuaPreparedQuery:iter()`, `LuaPreparedQuery:single()` or `LuaPreparedQuery:get(entity)`.");
+        methods.document("Use `LuaQueryBuilder:prepare()` to prepare queries with filters.");
+        methods.add_method(
+            "prepare_query",
+            |ctx, world, components: VariadicComponents| {
+                let mut query = LuaQueryBuilder::new(world.clone());
+                query.components(components.0);
+                if let Ok(sid) = LuaScriptData::current_sid(ctx) {
+                    query.for_script(sid);
+                }
+                query
+                    .prepare()
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
         methods
             .document("Returns `true` if the given entity contains a component of the given type.");
         methods.add_method(
@@ -237,31 +493,48 @@ impl TealData for LuaWorld {
                 .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
         });
 
+        methods.document("Constructs a resource of the given type from the given value and inserts it into the world, replacing any existing one.");
+        methods.document("See [`LuaWorld::insert_component`] for how tables are converted. Returns a reference to the resource.");
+        methods.add_method(
+            "insert_resource",
+            |ctx, world, (res_type, value): (LuaTypeRegistration, mlua::Value)| {
+                let value = lua_to_script_value(ctx, value)?;
+                world
+                    .insert_resource(res_type, value)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Inserts the default value of the given resource into the world unless it already exists, returns a reference to the resource.");
         methods.document(
-            "Removes the given resource from the world, if one doesn't exist it does nothing.",
+            "The resource needs to have either `ReflectFromWorld` or `ReflectDefault` type data.",
         );
         methods.add_method(
-            "remove_resource",
+            "init_resource",
             |_, world, res_type: LuaTypeRegistration| {
-                let mut w = world.write();
+                world
+                    .init_resource(res_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
 
-                let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
-                    mlua::Error::RuntimeError(format!("Not a resource {}", res_type.short_name()))
-                })?;
-                resource_data.remove(&mut w);
-                Ok(())
+        methods.document(
+            "Removes the given resource from the world, if one doesn't exist it does nothing.",
+        );
+        methods.add_method_mut(
+            "remove_resource",
+            |_, world, res_type: LuaTypeRegistration| {
+                world
+                    .remove_resource(res_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
             },
         );
 
         methods.document("Returns `true` if the world contains a resource of the given type.");
         methods.add_method("has_resource", |_, world, res_type: LuaTypeRegistration| {
-            let w = world.read();
-
-            let resource_data = res_type.data::<ReflectResource>().ok_or_else(|| {
-                mlua::Error::RuntimeError(format!("Not a resource {}", res_type.short_name()))
-            })?;
-
-            Ok(resource_data.reflect(&w).is_some())
+            world
+                .has_resource(res_type)
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
         });
 
         methods.document("Retrieves children entities of the parent entity if it has any.");
@@ -367,6 +640,154 @@ impl TealData for LuaWorld {
             Ok(())
         });
 
+        methods.document("Registers the given function as a system running every frame in the script system set with the given label.");
+        methods.document("The function is called with a `LuaQueryBuilder` for the given query, registering the same function twice replaces the previous system.");
+        methods.add_method(
+            "add_system",
+            |ctx, world, (label, callback, query): (String, LuaSystemCallback, LuaQueryBuilder)| {
+                add_system(ctx, world, label, callback, query)
+            },
+        );
+
+        methods.document("Removes the system calling the given function registered by this script, returns true if it existed.");
+        methods.add_method(
+            "remove_system",
+            |ctx, world, callback: LuaSystemCallback| {
+                Ok(world.remove_system::<Mutex<Lua>>(
+                    LuaScriptData::current_sid(ctx)?,
+                    &lua_function_id(&callback.into()),
+                ))
+            },
+        );
+
+        methods.document(
+            "Calls the given function whenever the given component is added to an entity.",
+        );
+        methods.document("The function receives the entity, only additions made after the hook was registered are reported.");
+        methods.add_method(
+            "on_added",
+            |ctx, world, (comp_type, callback): (LuaTypeRegistration, LuaChangeHookCallback)| {
+                add_change_hook(ctx, world, ComponentChange::Added, comp_type, callback)
+            },
+        );
+
+        methods.document("Calls the given function whenever the given component is added to or mutated on an entity.");
+        methods.document("The function receives the entity, changes made by change hooks themselves are not reported.");
+        methods.add_method(
+            "on_changed",
+            |ctx, world, (comp_type, callback): (LuaTypeRegistration, LuaChangeHookCallback)| {
+                add_change_hook(ctx, world, ComponentChange::Changed, comp_type, callback)
+            },
+        );
+
+        methods.document("Calls the given function whenever the given component is removed from an entity, or its entity is despawned.");
+        methods.document("The function receives the entity.");
+        methods.add_method(
+            "on_removed",
+            |ctx, world, (comp_type, callback): (LuaTypeRegistration, LuaChangeHookCallback)| {
+                add_change_hook(ctx, world, ComponentChange::Removed, comp_type, callback)
+            },
+        );
+
+        methods.document("Removes all change hooks calling the given function registered by this script, returns true if any existed.");
+        methods.add_method(
+            "remove_change_hooks",
+            |ctx, world, callback: LuaChangeHookCallback| {
+                Ok(world.remove_change_hooks::<Mutex<Lua>>(
+                    LuaScriptData::current_sid(ctx)?,
+                    &lua_function_id(&callback.into()),
+                ))
+            },
+        );
+
+        methods
+            .document("Calls the given function whenever an event of the given type is triggered.");
+        methods.document("The function receives a copy of the event, followed by the entity the event was triggered for, or nil.");
+        methods.document("Events triggered by Lua scripts are received once the triggering script is done, other events as soon as Bevy flushes the world after triggering them.");
+        methods.add_method(
+            "observe",
+            |ctx, world, (event_type, callback): (LuaTypeRegistration, LuaObserverCallback)| {
+                add_observer(ctx, world, event_type, None, callback)
+            },
+        );
+
+        methods.document("Calls the given function whenever an event of the given type is triggered for the given entity.");
+        methods.document("The function receives a copy of the event, followed by the entity.");
+        methods.add_method(
+            "observe_entity",
+            |ctx,
+             world,
+             (entity, event_type, callback): (
+                LuaEntity,
+                LuaTypeRegistration,
+                LuaObserverCallback,
+            )| {
+                add_observer(ctx, world, event_type, Some(entity.inner()?), callback)
+            },
+        );
+
+        methods.document("Removes all observers calling the given function registered by this script, returns true if any existed.");
+        methods.add_method(
+            "remove_observers",
+            |ctx, world, callback: LuaObserverCallback| {
+                Ok(world.remove_observers::<Mutex<Lua>>(
+                    LuaScriptData::current_sid(ctx)?,
+                    &lua_function_id(&callback.into()),
+                ))
+            },
+        );
+
+        methods.document("Triggers an event of the given type, for the given list of entities or globally if none are given.");
+        methods.document("The event starts out as the default value of its type, the given value if any is assigned to it before triggering.");
+        methods.add_method(
+            "trigger",
+            |ctx,
+             world,
+             (event_type, value, targets): (
+                LuaTypeRegistration,
+                Option<mlua::Value>,
+                Option<Vec<LuaEntity>>,
+            )| {
+                let event = new_event(ctx, world, &event_type, value)?;
+                let targets = targets
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|e| e.inner())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let event = event.read();
+                world
+                    .trigger_event(&event_type, event.as_partial_reflect(), &targets)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Sends an event of the given type, which needs to be added to the app.");
+        methods.document("The event starts out as the default value of its type, the given value if any is assigned to it before sending.");
+        methods.add_method(
+            "send_event",
+            |ctx, world, (event_type, value): (LuaTypeRegistration, Option<mlua::Value>)| {
+                let event = new_event(ctx, world, &event_type, value)?;
+                let event = event.read();
+                world
+                    .send_event(&event_type, event.as_partial_reflect())
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document(
+            "Returns a list of the events of the given type sent since this script last read them.",
+        );
+        methods.document("The returned references own copies of the events.");
+        methods.add_method(
+            "read_events",
+            |ctx, world, event_type: LuaTypeRegistration| {
+                world
+                    .read_events::<Mutex<Lua>>(LuaScriptData::current_sid(ctx)?, &event_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
         methods.document("Spawns a new entity and returns its Entity ID");
         methods.add_method("spawn", |_, world, ()| {
             let mut w = world.write();
@@ -382,5 +803,274 @@ impl TealData for LuaWorld {
 
             Ok(w.despawn(entity.inner()?))
         });
+
+        methods.document("Returns a `LuaScriptCommands` object, which queues up changes to the world instead of applying them immediately.");
+        methods.document("Queued changes are applied once the script handler or runner which called this script finishes.");
+        methods.add_method("commands", |_, world, ()| {
+            world
+                .commands()
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+        });
+    }
+}
+
+pub type LuaScriptCommands = ScriptCommands;
+
+impl_tealr_type!(LuaScriptCommands);
+impl_from_lua_with_clone!(LuaScriptCommands);
+
+impl TealData for LuaScriptCommands {
+    fn add_methods<'lua, T: TealDataMethods<'lua, Self>>(methods: &mut T) {
+        methods.document_type("Queues up changes to the world, applied once the current script handler or runner finishes.");
+        methods.document_type("Can be obtained via [`LuaWorld::commands`].");
+
+        methods.add_meta_method(tealr::mlu::mlua::MetaMethod::ToString, |_, s, ()| {
+            Ok(s.to_string())
+        });
+
+        methods.document("Reserves a new entity and returns its Entity ID, the entity is spawned once the commands are applied.");
+        methods.add_method("spawn", |_, commands, ()| {
+            Ok(LuaEntity::new(commands.spawn()))
+        });
+
+        methods.document("Despawns the given entity.");
+        methods.add_method("despawn", |_, commands, entity: LuaEntity| {
+            commands.despawn(entity.inner()?);
+            Ok(())
+        });
+
+        methods.document("Despawns the given entity and the entity's children recursively.");
+        methods.add_method("despawn_recursive", |_, commands, entity: LuaEntity| {
+            commands.despawn_recursive(entity.inner()?);
+            Ok(())
+        });
+
+        methods.document(
+            "Inserts a default instance of the given component type into the given entity.",
+        );
+        methods.add_method(
+            "add_default_component",
+            |_, commands, (entity, comp_type): (LuaEntity, LuaTypeRegistration)| {
+                commands
+                    .add_default_component(entity.inner()?, comp_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Removes the given component from the given entity.");
+        methods.add_method(
+            "remove_component",
+            |_, commands, (entity, comp_type): (LuaEntity, LuaTypeRegistration)| {
+                commands
+                    .remove_component(entity.inner()?, comp_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Removes the given resource from the world.");
+        methods.add_method(
+            "remove_resource",
+            |_, commands, res_type: LuaTypeRegistration| {
+                commands
+                    .remove_resource(res_type)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            },
+        );
+
+        methods.document("Attaches child entity to the given parent entity.");
+        methods.add_method(
+            "push_child",
+            |_, commands, (parent, child): (LuaEntity, LuaEntity)| {
+                commands.push_child(parent.inner()?, child.inner()?);
+                Ok(())
+            },
+        );
+
+        methods.document("Removes children entities from the given parent entity.");
+        methods.add_method(
+            "remove_children",
+            |_, commands, (parent, children): (LuaEntity, Vec<LuaEntity>)| {
+                let children = children
+                    .iter()
+                    .map(|e| e.inner())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                commands.remove_children(parent.inner()?, children);
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Identifies a function by its address, which stays the same as long as the function is referenced from the registry
+fn lua_function_id(function: &mlua::Function) -> String {
+    format!("{:p}", function.to_pointer())
+}
+
+fn add_system<'lua>(
+    ctx: &'lua Lua,
+    world: &LuaWorld,
+    label: String,
+    callback: LuaSystemCallback<'lua>,
+    query: LuaQueryBuilder,
+) -> mlua::Result<()> {
+    let callback: mlua::Function = callback.into();
+    let callback = ScriptFunctionRef::new(
+        lua_function_id(&callback),
+        ctx.create_registry_value(callback)?,
+    );
+
+    world
+        .add_system::<Mutex<Lua>>(ScriptSystem {
+            sid: LuaScriptData::current_sid(ctx)?,
+            label,
+            callback,
+            query: query.query().clone(),
+            cache: query.cache().clone(),
+        })
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+}
+
+fn add_change_hook<'lua>(
+    ctx: &'lua Lua,
+    world: &LuaWorld,
+    change: ComponentChange,
+    component: LuaTypeRegistration,
+    callback: LuaChangeHookCallback<'lua>,
+) -> mlua::Result<()> {
+    let callback: mlua::Function = callback.into();
+    let callback = ScriptFunctionRef::new(
+        lua_function_id(&callback),
+        ctx.create_registry_value(callback)?,
+    );
+
+    world
+        .add_change_hook::<Mutex<Lua>>(ScriptChangeHook {
+            sid: LuaScriptData::current_sid(ctx)?,
+            change,
+            component,
+            callback,
+        })
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+}
+
+fn add_observer<'lua>(
+    ctx: &'lua Lua,
+    world: &LuaWorld,
+    event: LuaTypeRegistration,
+    entity: Option<Entity>,
+    callback: LuaObserverCallback<'lua>,
+) -> mlua::Result<()> {
+    let callback: mlua::Function = callback.into();
+    let callback = ScriptFunctionRef::new(
+        lua_function_id(&callback),
+        ctx.create_registry_value(callback)?,
+    );
+
+    world
+        .add_observer::<Mutex<Lua>>(ScriptObserver {
+            sid: LuaScriptData::current_sid(ctx)?,
+            event,
+            entity,
+            callback,
+        })
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+}
+
+/// Creates a default event of the given type, with the given lua value assigned to it
+fn new_event(
+    ctx: &Lua,
+    world: &LuaWorld,
+    event_type: &LuaTypeRegistration,
+    value: Option<mlua::Value>,
+) -> mlua::Result<SharedReflect> {
+    let event = world
+        .default_event(event_type)
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+
+    if let Some(value) = value {
+        ReflectReference::new_shared_ref(event.clone(), world.clone().into())
+            .apply_lua(ctx, value)?;
+    }
+
+    Ok(event)
+}
+
+impl<A: LuaArg> ScriptSystemHost for LuaScriptHost<A> {
+    fn call_script_functions(world: &mut World, calls: Vec<ScriptFunctionCall>) {
+        let mut contexts: ScriptContexts<Mutex<Lua>> = world.remove_resource().unwrap();
+        let mut providers: APIProviders<Self> = world.remove_resource().unwrap();
+
+        {
+            // safety:
+            // - we have &mut World access
+            // - we do not use the original reference again anywhere in this block
+            let world = unsafe { WorldPointerGuard::new(world) };
+
+            for call in calls {
+                let Some((entity, Some(ctx), name)) = contexts.context_entities.get_mut(&call.sid)
+                else {
+                    continue;
+                };
+
+                let script_data = ScriptData {
+                    sid: call.sid,
+                    entity: *entity,
+                    name,
+                };
+
+                providers
+                    .setup_runtime_all(world.clone(), &script_data, ctx)
+                    .expect("Could not setup script runtime");
+
+                let lua = ctx.get_mut().expect("Poison error in context");
+
+                let result = match &call.arg {
+                    ScriptSystemArg::Query(query, cache) => {
+                        LuaQueryBuilder::from_query(ScriptWorld::new(world.clone()), query.clone())
+                            .with_cache(cache.clone())
+                            .for_script(call.sid)
+                            .clone()
+                            .into_lua_multi(lua)
+                    }
+                    ScriptSystemArg::Entity(entity) => LuaEntity::new(*entity).into_lua_multi(lua),
+                    ScriptSystemArg::Event { event, target } => (
+                        ReflectReference::new_shared_ref(event.clone(), world.clone()),
+                        target.map(LuaEntity::new),
+                    )
+                        .into_lua_multi(lua),
+                }
+                .and_then(|args| {
+                    let function = match &call.callback {
+                        ScriptCallback::Named(name) => {
+                            lua.globals().raw_get::<_, mlua::Function>(name.as_str())?
+                        }
+                        ScriptCallback::Value(function) => {
+                            let key = function.get::<mlua::RegistryKey>().ok_or_else(|| {
+                                mlua::Error::RuntimeError(format!(
+                                    "Not a Lua function: {function:?}"
+                                ))
+                            })?;
+                            lua.registry_value::<mlua::Function>(key)?
+                        }
+                    };
+                    function.call::<_, ()>(args)
+                });
+
+                if let Err(error) = result {
+                    report_script_system_error(
+                        &world,
+                        ScriptError::RuntimeError {
+                            script: script_data.name.to_owned(),
+                            msg: error.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+
+        world.insert_resource(contexts);
+        world.insert_resource(providers);
+        ScriptCommandQueue::apply(world);
     }
 }
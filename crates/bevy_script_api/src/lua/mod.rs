@@ -3,9 +3,12 @@ use ::std::borrow::Cow;
 
 use crate::common::bevy::GetWorld;
 use crate::{impl_from_lua_with_clone, impl_tealr_type};
-use ::bevy::prelude::{App, AppTypeRegistry};
+use ::bevy::prelude::{App, AppTypeRegistry, ReflectDefault};
 
-use ::bevy::reflect::{FromType, GetTypeRegistration, Reflect};
+use ::bevy::reflect::{
+    FromType, GetTypeRegistration, PartialReflect, Reflect, ReflectKind, ReflectRef,
+    TypeRegistration,
+};
 
 use bevy_mod_scripting_core::world::WorldPointer;
 use bevy_mod_scripting_lua::tealr::{self, ToTypename};
@@ -13,13 +16,16 @@ use bevy_mod_scripting_lua::tealr::{self, ToTypename};
 use tealr::mlu::mlua::MetaMethod;
 use tealr::mlu::{
     mlua::{self, FromLua, IntoLua, Lua, UserData, Value},
-    TealData, TealDataMethods,
+    TealData, TealDataMethods, TypedFunction,
 };
 
-use crate::script_ref::{ReflectReference, ReflectedValue, ValueIndex};
+use crate::error::ReflectionError;
+use crate::providers::bevy_ecs::LuaEntity;
+use crate::script_ref::{EntryKey, ReflectReference, ReflectedValue, ValueIndex};
+use crate::script_value::ScriptValue;
 
 use self::bevy::LuaWorld;
-use self::util::to_host_idx;
+use self::util::{to_host_idx, to_lua_idx};
 
 pub mod bevy;
 pub mod std;
@@ -83,6 +89,149 @@ impl ValueIndex<Value<'_>> for ReflectReference {
     }
 }
 
+/// Converts a lua value into a reflected value, used for map keys and map or set entries.
+///
+/// Supports integers, numbers, booleans, strings, entities and reflected values.
+pub(crate) fn lua_value_to_reflect(value: Value) -> mlua::Result<Box<dyn PartialReflect>> {
+    match value {
+        #[allow(clippy::unnecessary_cast)]
+        Value::Integer(i) => Ok(Box::new(i as i64)),
+        Value::Number(n) => Ok(Box::new(n)),
+        Value::Boolean(b) => Ok(Box::new(b)),
+        Value::String(s) => Ok(Box::new(s.to_str()?.to_owned())),
+        Value::UserData(ud) if ud.is::<LuaEntity>() => {
+            Ok(Box::new(ud.borrow::<LuaEntity>()?.inner()?))
+        }
+        Value::UserData(ud) if ud.is::<ReflectedValue>() => Ok(ud
+            .borrow::<ReflectedValue>()?
+            .ref_
+            .get(|s| s.clone_value())?),
+        _ => Err(mlua::Error::RuntimeError(format!(
+            "Cannot convert {:?} to a rust value, expected a number, boolean, string, entity or reflected value",
+            value
+        ))),
+    }
+}
+
+/// Converts a lua value into a [`ScriptValue`], which can be converted to any registered type.
+///
+/// Tables are converted recursively, tables with exactly the keys `1..n` become lists and all others maps.
+/// Userdata other than entities and reflected values is converted via [`ApplyLua`] once the type it needs to be converted to is known.
+pub(crate) fn lua_to_script_value<'lua>(
+    ctx: &'lua Lua,
+    value: Value<'lua>,
+) -> mlua::Result<ScriptValue<'lua>> {
+    Ok(match value {
+        Value::Nil => ScriptValue::Unit,
+        Value::Boolean(b) => ScriptValue::Bool(b),
+        #[allow(clippy::unnecessary_cast)]
+        Value::Integer(i) => ScriptValue::Integer(i as i64),
+        Value::Number(n) => ScriptValue::Float(n),
+        Value::String(s) => ScriptValue::String(s.to_str()?.to_owned()),
+        Value::Table(table) => {
+            let mut entries = table
+                .pairs::<Value, Value>()
+                .map(|pair| {
+                    let (key, value) = pair?;
+                    Ok((
+                        lua_to_script_value(ctx, key)?,
+                        lua_to_script_value(ctx, value)?,
+                    ))
+                })
+                .collect::<mlua::Result<Vec<_>>>()?;
+
+            // keys are unique, so if all of them lie in `1..=n` this is a sequence
+            let len = entries.len();
+            let index = |key: &ScriptValue| match key {
+                ScriptValue::Integer(i) if *i >= 1 && (*i as usize) <= len => Some(*i),
+                _ => None,
+            };
+
+            if entries.iter().all(|(key, _)| index(key).is_some()) {
+                entries.sort_by_key(|(key, _)| index(key));
+                ScriptValue::List(entries.into_iter().map(|(_, value)| value).collect())
+            } else {
+                ScriptValue::Map(entries)
+            }
+        }
+        Value::UserData(ref ud) if ud.is::<LuaEntity>() || ud.is::<ReflectedValue>() => {
+            ScriptValue::Reflect(lua_value_to_reflect(value)?)
+        }
+        Value::UserData(_) => {
+            let world = ctx.get_world()?;
+            ScriptValue::Deferred(Box::new(move |registration: &TypeRegistration| {
+                let default = registration.data::<ReflectDefault>().ok_or_else(|| {
+                    ReflectionError::Other(format!(
+                        "Cannot convert {:?} to `{}` since it has no `ReflectDefault` type data",
+                        value,
+                        registration.type_info().type_path()
+                    ))
+                })?;
+
+                let mut ref_ = ReflectReference::new_owned_ref(default.default(), world);
+                ref_.apply_lua(ctx, value)
+                    .map_err(|e| ReflectionError::Other(e.to_string()))?;
+                ref_.get(|v| v.clone_value())
+            }))
+        }
+        _ => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Cannot convert {:?} to a rust value",
+                value
+            )))
+        }
+    })
+}
+
+/// Converts the key of a container entry to lua, indices are converted to lua indices
+fn entry_key_to_lua(key: EntryKey, ctx: &Lua) -> mlua::Result<Value> {
+    match key {
+        EntryKey::Index(i) => to_lua_idx(i).into_lua(ctx),
+        EntryKey::Field(name) => name.as_ref().into_lua(ctx),
+        EntryKey::Key(key) => key.into_lua(ctx),
+    }
+}
+
+/// Returns a function calling the given method registered in the [`AppFunctionRegistry`](::bevy::ecs::reflect::AppFunctionRegistry),
+/// if the referenced value has no field of the same name, see [`ScriptWorld::call_method`](crate::common::bevy::ScriptWorld::call_method)
+fn reflected_method<'lua>(
+    ctx: &'lua Lua,
+    ref_: &ReflectReference,
+    name: &str,
+) -> mlua::Result<Option<mlua::Function<'lua>>> {
+    let is_field = ref_.get(|value| match value.reflect_ref() {
+        ReflectRef::Struct(s) => s.field(name).is_some(),
+        ReflectRef::Enum(e) => e.field(name).is_some(),
+        _ => false,
+    })?;
+    if is_field {
+        return Ok(None);
+    }
+
+    let world = LuaWorld::new(ctx.get_world()?);
+    if world
+        .method_name(ref_, name)
+        .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?
+        .is_none()
+    {
+        return Ok(None);
+    }
+
+    let method = name.to_owned();
+    ctx.create_function(move |ctx, args: mlua::MultiValue| {
+        let mut args = args.into_vec().into_iter();
+        let receiver = ReflectedValue::from_lua(args.next().unwrap_or(Value::Nil), ctx)?;
+        let args = args
+            .map(|arg| lua_to_script_value(ctx, arg))
+            .collect::<mlua::Result<Vec<_>>>()?;
+
+        world
+            .call_method(&receiver.ref_, &method, args)
+            .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+    })
+    .map(Some)
+}
+
 /// For internal use only.
 ///
 /// Mainly necessary for separation of concerns on the [`ReflectReference`] type, but might have other uses potentially.
@@ -164,18 +313,117 @@ impl TealData for ReflectedValue {
             val.ref_.get(|s| Ok(format!("{:?}", &s)))?
         });
 
-        methods.add_meta_method_mut(MetaMethod::Index, |_, val, field: Value| {
-            let r = val.ref_.index(field)?;
-            Ok(r)
+        methods.add_meta_method_mut(MetaMethod::Index, |ctx, val, field: Value| {
+            match val.ref_.reflect_kind()? {
+                // missing keys evaluate to nil
+                ReflectKind::Map => val
+                    .ref_
+                    .map_get(lua_value_to_reflect(field)?)?
+                    .into_lua(ctx),
+                ReflectKind::Set => val
+                    .ref_
+                    .contains(lua_value_to_reflect(field)?)?
+                    .into_lua(ctx),
+                // out of range elements evaluate to nil, so that `ipairs` stops at the end
+                ReflectKind::List
+                | ReflectKind::Array
+                | ReflectKind::Tuple
+                | ReflectKind::TupleStruct
+                    if matches!(field, Value::Integer(i) if i < 1 || (i as usize) > val.ref_.len()?) =>
+                {
+                    Ok(Value::Nil)
+                }
+                _ => {
+                    if let Value::String(name) = &field {
+                        if let Some(method) = reflected_method(ctx, &val.ref_, name.to_str()?)? {
+                            return Ok(Value::Function(method));
+                        }
+                    }
+                    val.ref_.index(field)?.into_lua(ctx)
+                }
+            }
         });
 
         methods.add_meta_method_mut(
             MetaMethod::NewIndex,
             |ctx, val, (field, new_val): (Value, Value)| {
-                val.ref_.index(field)?.apply_lua(ctx, new_val)?;
+                match val.ref_.reflect_kind()? {
+                    // assigning nil removes the entry, new keys are inserted
+                    ReflectKind::Map => {
+                        let key = lua_value_to_reflect(field)?;
+                        if new_val.is_nil() {
+                            val.ref_.map_remove(key)?;
+                        } else if let Some(mut entry) = val.ref_.map_get(key.clone_value())? {
+                            entry.apply_lua(ctx, new_val)?;
+                        } else {
+                            val.ref_.map_insert(key, lua_value_to_reflect(new_val)?)?;
+                        }
+                    }
+                    // assigning a truthy value inserts the element, nil or false removes it
+                    ReflectKind::Set => {
+                        let element = lua_value_to_reflect(field)?;
+                        if matches!(new_val, Value::Nil | Value::Boolean(false)) {
+                            val.ref_.map_remove(element)?;
+                        } else {
+                            val.ref_.set_insert(element)?;
+                        }
+                    }
+                    _ => val.ref_.index(field)?.apply_lua(ctx, new_val)?,
+                }
                 Ok(())
             },
         );
+
+        methods.add_meta_method(MetaMethod::Len, |_, val, ()| Ok(val.ref_.len()?));
+
+        bevy_mod_scripting_lua::__cfg_feature_any_lua52_lua53_lua54_luajit52!(
+            methods.add_meta_method(
+                MetaMethod::Pairs,
+                |ctx, val, _: ()| {
+                    // sets yield their elements followed by `true`, like lua sets
+                    let is_set = val.ref_.reflect_kind()? == ReflectKind::Set;
+                    let mut entries = val.ref_.entries()?.into_iter();
+                    TypedFunction::from_rust_mut(
+                        move |ctx, ()| {
+                            let Some((key, value)) = entries.next() else {
+                                return Ok((Value::Nil, Value::Nil));
+                            };
+
+                            let value = if is_set {
+                                Value::Boolean(true)
+                            } else {
+                                value.into_lua(ctx)?
+                            };
+                            Ok((entry_key_to_lua(key, ctx)?, value))
+                        },
+                        ctx,
+                    )
+                },
+            );
+        );
+
+        bevy_mod_scripting_lua::__cfg_feature_any_lua52_luajit52!(
+            methods.add_meta_method(
+                MetaMethod::IPairs,
+                |ctx, val, _: ()| {
+                    let mut entries = val
+                        .ref_
+                        .entries()?
+                        .into_iter()
+                        .filter(|(key, _)| matches!(key, EntryKey::Index(_)));
+                    TypedFunction::from_rust_mut(
+                        move |ctx, ()| {
+                            let Some((key, value)) = entries.next() else {
+                                return Ok((Value::Nil, Value::Nil));
+                            };
+
+                            Ok((entry_key_to_lua(key, ctx)?, value.into_lua(ctx)?))
+                        },
+                        ctx,
+                    )
+                },
+            );
+        );
     }
 }
 /// A higher level trait for allowing types to be interpreted as custom lua proxy types (or just normal types, this interface is flexible).
@@ -128,20 +128,60 @@ impl TealMultiValue for VariadicComponents {
     }
 }
 
+/// A utility type that allows us to accept the name of a function followed by any number of arguments to pass to it.
+pub struct VariadicFunctionCall<'lua> {
+    pub name: String,
+    pub args: Vec<LuaValue<'lua>>,
+}
+
+impl<'lua> FromLuaMulti<'lua> for VariadicFunctionCall<'lua> {
+    fn from_lua_multi(value: LuaMultiValue<'lua>, lua: &'lua Lua) -> Result<Self, LuaError> {
+        let mut args = value.into_vec().into_iter();
+        let name = args.next().unwrap_or(LuaValue::Nil);
+
+        Ok(VariadicFunctionCall {
+            name: String::from_lua(name, lua)?,
+            args: args.collect(),
+        })
+    }
+}
+
+impl TealMultiValue for VariadicFunctionCall<'_> {
+    fn get_types_as_params() -> Vec<FunctionParam> {
+        vec![
+            FunctionParam {
+                param_name: Some(Name("name".into())),
+                ty: String::to_typename(),
+            },
+            FunctionParam {
+                // `...:T` will be a variadic type
+                param_name: Some(Name("...".into())),
+                ty: LuaValue::to_typename(),
+            },
+        ]
+    }
+}
+
 /// A utility enum that allows us to return an entity and any number of components from a function.
+/// Optional components are returned after the required ones, as `nil` if missing.
 #[derive(Clone)]
 pub enum VariadicQueryResult {
-    Some(LuaEntity, Vec<ReflectReference>),
+    Some(
+        LuaEntity,
+        Vec<ReflectReference>,
+        Vec<Option<ReflectReference>>,
+    ),
     None,
 }
 
 impl IntoLuaMulti<'_> for VariadicQueryResult {
     fn into_lua_multi(self, lua: &Lua) -> Result<LuaMultiValue<'_>, LuaError> {
         match self {
-            VariadicQueryResult::Some(entity, vec) => {
+            VariadicQueryResult::Some(entity, vec, optional) => {
                 let mut values = LuaMultiValue::from_vec(
                     vec.into_iter()
                         .map(|v| v.into_lua(lua))
+                        .chain(optional.into_iter().map(|v| v.into_lua(lua)))
                         .collect::<Result<Vec<_>, LuaError>>()?,
                 );
 
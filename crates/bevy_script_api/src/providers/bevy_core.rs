@@ -5,10 +5,13 @@
 use super::bevy_ecs::*;
 use super::bevy_reflect::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::core::prelude::Name",
@@ -37,8 +40,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct Name {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -47,7 +52,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyCoreAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyCoreAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -93,3 +100,24 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyCoreAPIProvider {
         app.register_foreign_lua_type::<bevy::core::prelude::Name>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyCoreRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyCoreRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiName>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::core::prelude::Name>();
+    }
+}
@@ -4,10 +4,13 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
 use super::bevy_reflect::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::entity::Entity",
@@ -90,7 +93,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Entity {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::ecs::world::OnAdd",
@@ -102,7 +106,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct OnAdd {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::ecs::world::OnInsert",
@@ -114,7 +119,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct OnInsert {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::ecs::world::OnRemove",
@@ -126,7 +132,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct OnRemove {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::ecs::world::OnReplace",
@@ -138,7 +145,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct OnReplace {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::component::ComponentId",
@@ -189,7 +197,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct ComponentId();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::component::Tick",
@@ -259,7 +268,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Tick {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::component::ComponentTicks",
@@ -337,7 +347,8 @@ struct ComponentTicks {
     #[lua(output(proxy))]
     changed: bevy::ecs::component::Tick,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::identifier::Identifier",
@@ -397,7 +408,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Identifier {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::entity::EntityHash",
@@ -409,7 +421,8 @@ struct Identifier {}
 "#]
 )]
 struct EntityHash {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::ecs::removal_detection::RemovedComponentEntity",
@@ -427,11 +440,14 @@ fn index(&self) -> String {
 "#]
 )]
 struct RemovedComponentEntity();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(derive(), remote = "bevy::ecs::system::SystemIdMarker", functions[])]
 struct SystemIdMarker {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -467,7 +483,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyEcsAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyEcsAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -558,3 +576,48 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyEcsAPIProvider {
         app.register_foreign_lua_type::<bevy::ecs::system::SystemIdMarker>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyEcsRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyEcsRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiOnAdd>();
+        engine.register_rhai_proxy::<RhaiOnInsert>();
+        engine.register_rhai_proxy::<RhaiOnRemove>();
+        engine.register_rhai_proxy::<RhaiOnReplace>();
+        engine.register_rhai_proxy::<RhaiComponentId>();
+        engine.register_rhai_proxy::<RhaiTick>();
+        engine.register_rhai_proxy::<RhaiComponentTicks>();
+        engine.register_rhai_proxy::<RhaiIdentifier>();
+        engine.register_rhai_proxy::<RhaiEntityHash>();
+        engine.register_rhai_proxy::<RhaiRemovedComponentEntity>();
+        engine.register_rhai_proxy::<RhaiSystemIdMarker>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::ecs::world::OnAdd>();
+        app.register_foreign_rhai_type::<bevy::ecs::world::OnInsert>();
+        app.register_foreign_rhai_type::<bevy::ecs::world::OnRemove>();
+        app.register_foreign_rhai_type::<bevy::ecs::world::OnReplace>();
+        app.register_foreign_rhai_type::<bevy::ecs::component::ComponentId>();
+        app.register_foreign_rhai_type::<bevy::ecs::component::Tick>();
+        app.register_foreign_rhai_type::<
+                bevy::ecs::component::ComponentTicks,
+            >();
+        app.register_foreign_rhai_type::<bevy::ecs::identifier::Identifier>();
+        app.register_foreign_rhai_type::<bevy::ecs::entity::EntityHash>();
+        app.register_foreign_rhai_type::<
+                bevy::ecs::removal_detection::RemovedComponentEntity,
+            >();
+        app.register_foreign_rhai_type::<bevy::ecs::system::SystemIdMarker>();
+    }
+}
@@ -6,10 +6,13 @@ use super::bevy_ecs::*;
 use super::bevy_reflect::*;
 use super::bevy_core::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::hierarchy::prelude::Children",
@@ -28,7 +31,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Children();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::hierarchy::prelude::Parent",
@@ -64,7 +68,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Parent();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::hierarchy::HierarchyEvent",
@@ -99,8 +104,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct HierarchyEvent {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -109,7 +116,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyHierarchyAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyHierarchyAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -159,3 +168,28 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyHierarchyAPIProvider {
         app.register_foreign_lua_type::<bevy::hierarchy::HierarchyEvent>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyHierarchyRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyHierarchyRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiChildren>();
+        engine.register_rhai_proxy::<RhaiParent>();
+        engine.register_rhai_proxy::<RhaiHierarchyEvent>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::hierarchy::prelude::Children>();
+        app.register_foreign_rhai_type::<bevy::hierarchy::prelude::Parent>();
+        app.register_foreign_rhai_type::<bevy::hierarchy::HierarchyEvent>();
+    }
+}
@@ -7,10 +7,13 @@ use super::bevy_reflect::*;
 use super::bevy_core::*;
 use super::bevy_math::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "bevy::input::gamepad::Gamepad",
@@ -89,7 +92,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Gamepad {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadAxis",
@@ -124,7 +128,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct GamepadAxis {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadButton",
@@ -159,7 +164,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct GamepadButton {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadSettings",
@@ -187,7 +193,8 @@ struct GamepadSettings {
     axis_settings: ReflectedValue,
     button_axis_settings: ReflectedValue,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::KeyCode",
@@ -222,7 +229,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct KeyCode {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::MouseButton",
@@ -257,7 +265,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct MouseButton {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::touch::TouchInput",
@@ -295,7 +304,8 @@ struct TouchInput {
     force: ReflectedValue,
     id: u64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::KeyboardFocusLost",
@@ -330,7 +340,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct KeyboardFocusLost {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::KeyboardInput",
@@ -375,7 +386,8 @@ struct KeyboardInput {
     #[lua(output(proxy))]
     window: bevy::ecs::entity::Entity,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::AccumulatedMouseMotion",
@@ -407,7 +419,8 @@ struct AccumulatedMouseMotion {
     #[lua(output(proxy))]
     delta: bevy::math::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::AccumulatedMouseScroll",
@@ -441,7 +454,8 @@ struct AccumulatedMouseScroll {
     #[lua(output(proxy))]
     delta: bevy::math::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::MouseButtonInput",
@@ -483,7 +497,8 @@ struct MouseButtonInput {
     #[lua(output(proxy))]
     window: bevy::ecs::entity::Entity,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::MouseMotion",
@@ -515,7 +530,8 @@ struct MouseMotion {
     #[lua(output(proxy))]
     delta: bevy::math::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::MouseWheel",
@@ -551,7 +567,8 @@ struct MouseWheel {
     #[lua(output(proxy))]
     window: bevy::ecs::entity::Entity,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadAxisChangedEvent",
@@ -599,7 +616,8 @@ struct GamepadAxisChangedEvent {
     axis: bevy::input::gamepad::GamepadAxis,
     value: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadButtonChangedEvent",
@@ -651,7 +669,8 @@ struct GamepadButtonChangedEvent {
     state: bevy::input::ButtonState,
     value: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadButtonStateChangedEvent",
@@ -707,7 +726,8 @@ struct GamepadButtonStateChangedEvent {
     #[lua(output(proxy))]
     state: bevy::input::ButtonState,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadConnection",
@@ -736,7 +756,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct GamepadConnection {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadConnectionEvent",
@@ -796,7 +817,8 @@ struct GamepadConnectionEvent {
     #[lua(output(proxy))]
     connection: bevy::input::gamepad::GamepadConnection,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadEvent",
@@ -825,7 +847,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct GamepadEvent {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadInput",
@@ -860,7 +883,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct GamepadInput {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadRumbleRequest",
@@ -879,7 +903,8 @@ struct GamepadInput {}
 "#]
 )]
 struct GamepadRumbleRequest {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::RawGamepadAxisChangedEvent",
@@ -927,7 +952,8 @@ struct RawGamepadAxisChangedEvent {
     axis: bevy::input::gamepad::GamepadAxis,
     value: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::RawGamepadButtonChangedEvent",
@@ -975,7 +1001,8 @@ struct RawGamepadButtonChangedEvent {
     button: bevy::input::gamepad::GamepadButton,
     value: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::RawGamepadEvent",
@@ -1004,7 +1031,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct RawGamepadEvent {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gestures::PinchGesture",
@@ -1033,7 +1061,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct PinchGesture(f32);
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gestures::RotationGesture",
@@ -1062,7 +1091,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct RotationGesture(f32);
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gestures::DoubleTapGesture",
@@ -1091,7 +1121,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct DoubleTapGesture {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gestures::PanGesture",
@@ -1120,7 +1151,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct PanGesture(#[lua(output(proxy))] bevy::math::Vec2);
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::ButtonState",
@@ -1162,7 +1194,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct ButtonState {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::ButtonSettings",
@@ -1239,7 +1272,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct ButtonSettings {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::AxisSettings",
@@ -1372,7 +1406,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AxisSettings {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::ButtonAxisSettings",
@@ -1407,7 +1442,8 @@ struct ButtonAxisSettings {
     low: f32,
     threshold: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::gamepad::GamepadRumbleIntensity",
@@ -1455,7 +1491,8 @@ struct GamepadRumbleIntensity {
     strong_motor: f32,
     weak_motor: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::Key",
@@ -1490,7 +1527,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Key {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::NativeKeyCode",
@@ -1525,7 +1563,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct NativeKeyCode {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::keyboard::NativeKey",
@@ -1560,7 +1599,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct NativeKey {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::mouse::MouseScrollUnit",
@@ -1595,7 +1635,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct MouseScrollUnit {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::touch::TouchPhase",
@@ -1630,7 +1671,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct TouchPhase {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::input::touch::ForceTouch",
@@ -1659,8 +1701,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct ForceTouch {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -1718,7 +1762,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyInputAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyInputAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -1885,3 +1931,144 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyInputAPIProvider {
         app.register_foreign_lua_type::<bevy::input::touch::ForceTouch>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyInputRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyInputRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiGamepad>();
+        engine.register_rhai_proxy::<RhaiGamepadAxis>();
+        engine.register_rhai_proxy::<RhaiGamepadButton>();
+        engine.register_rhai_proxy::<RhaiGamepadSettings>();
+        engine.register_rhai_proxy::<RhaiKeyCode>();
+        engine.register_rhai_proxy::<RhaiMouseButton>();
+        engine.register_rhai_proxy::<RhaiTouchInput>();
+        engine.register_rhai_proxy::<RhaiKeyboardFocusLost>();
+        engine.register_rhai_proxy::<RhaiKeyboardInput>();
+        engine.register_rhai_proxy::<RhaiAccumulatedMouseMotion>();
+        engine.register_rhai_proxy::<RhaiAccumulatedMouseScroll>();
+        engine.register_rhai_proxy::<RhaiMouseButtonInput>();
+        engine.register_rhai_proxy::<RhaiMouseMotion>();
+        engine.register_rhai_proxy::<RhaiMouseWheel>();
+        engine.register_rhai_proxy::<RhaiGamepadAxisChangedEvent>();
+        engine.register_rhai_proxy::<RhaiGamepadButtonChangedEvent>();
+        engine.register_rhai_proxy::<RhaiGamepadButtonStateChangedEvent>();
+        engine.register_rhai_proxy::<RhaiGamepadConnection>();
+        engine.register_rhai_proxy::<RhaiGamepadConnectionEvent>();
+        engine.register_rhai_proxy::<RhaiGamepadEvent>();
+        engine.register_rhai_proxy::<RhaiGamepadInput>();
+        engine.register_rhai_proxy::<RhaiGamepadRumbleRequest>();
+        engine.register_rhai_proxy::<RhaiRawGamepadAxisChangedEvent>();
+        engine.register_rhai_proxy::<RhaiRawGamepadButtonChangedEvent>();
+        engine.register_rhai_proxy::<RhaiRawGamepadEvent>();
+        engine.register_rhai_proxy::<RhaiPinchGesture>();
+        engine.register_rhai_proxy::<RhaiRotationGesture>();
+        engine.register_rhai_proxy::<RhaiDoubleTapGesture>();
+        engine.register_rhai_proxy::<RhaiPanGesture>();
+        engine.register_rhai_proxy::<RhaiButtonState>();
+        engine.register_rhai_proxy::<RhaiButtonSettings>();
+        engine.register_rhai_proxy::<RhaiAxisSettings>();
+        engine.register_rhai_proxy::<RhaiButtonAxisSettings>();
+        engine.register_rhai_proxy::<RhaiGamepadRumbleIntensity>();
+        engine.register_rhai_proxy::<RhaiKey>();
+        engine.register_rhai_proxy::<RhaiNativeKeyCode>();
+        engine.register_rhai_proxy::<RhaiNativeKey>();
+        engine.register_rhai_proxy::<RhaiMouseScrollUnit>();
+        engine.register_rhai_proxy::<RhaiTouchPhase>();
+        engine.register_rhai_proxy::<RhaiForceTouch>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::input::gamepad::Gamepad>();
+        app.register_foreign_rhai_type::<bevy::input::gamepad::GamepadAxis>();
+        app.register_foreign_rhai_type::<bevy::input::gamepad::GamepadButton>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadSettings,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::keyboard::KeyCode>();
+        app.register_foreign_rhai_type::<bevy::input::mouse::MouseButton>();
+        app.register_foreign_rhai_type::<bevy::input::touch::TouchInput>();
+        app.register_foreign_rhai_type::<
+                bevy::input::keyboard::KeyboardFocusLost,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::keyboard::KeyboardInput,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::mouse::AccumulatedMouseMotion,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::mouse::AccumulatedMouseScroll,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::mouse::MouseButtonInput,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::mouse::MouseMotion>();
+        app.register_foreign_rhai_type::<bevy::input::mouse::MouseWheel>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadAxisChangedEvent,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadButtonChangedEvent,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadButtonStateChangedEvent,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadConnection,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadConnectionEvent,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::gamepad::GamepadEvent>();
+        app.register_foreign_rhai_type::<bevy::input::gamepad::GamepadInput>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadRumbleRequest,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::RawGamepadAxisChangedEvent,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::RawGamepadButtonChangedEvent,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::RawGamepadEvent,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::gestures::PinchGesture>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gestures::RotationGesture,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gestures::DoubleTapGesture,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::gestures::PanGesture>();
+        app.register_foreign_rhai_type::<bevy::input::ButtonState>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::ButtonSettings,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::gamepad::AxisSettings>();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::ButtonAxisSettings,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::input::gamepad::GamepadRumbleIntensity,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::keyboard::Key>();
+        app.register_foreign_rhai_type::<
+                bevy::input::keyboard::NativeKeyCode,
+            >();
+        app.register_foreign_rhai_type::<bevy::input::keyboard::NativeKey>();
+        app.register_foreign_rhai_type::<bevy::input::mouse::MouseScrollUnit>();
+        app.register_foreign_rhai_type::<bevy::input::touch::TouchPhase>();
+        app.register_foreign_rhai_type::<bevy::input::touch::ForceTouch>();
+    }
+}
@@ -4,10 +4,13 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
 use super::bevy_reflect::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::AspectRatio",
@@ -71,7 +74,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AspectRatio();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::CompassOctant",
@@ -106,7 +110,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct CompassOctant {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::CompassQuadrant",
@@ -141,7 +146,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct CompassQuadrant {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Isometry2d",
@@ -292,7 +298,8 @@ struct Isometry2d {
     #[lua(output(proxy))]
     translation: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Isometry3d",
@@ -411,7 +418,8 @@ struct Isometry3d {
     #[lua(output(proxy))]
     translation: bevy::math::Vec3A,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Ray2d",
@@ -477,7 +485,8 @@ struct Ray2d {
     #[lua(output(proxy))]
     direction: bevy::math::prelude::Dir2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Ray3d",
@@ -543,7 +552,8 @@ struct Ray3d {
     #[lua(output(proxy))]
     direction: bevy::math::prelude::Dir3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Rot2",
@@ -873,7 +883,8 @@ struct Rot2 {
     cos: f32,
     sin: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::Dir2",
@@ -1037,7 +1048,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Dir2();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::Dir3",
@@ -1181,7 +1193,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Dir3();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::Dir3A",
@@ -1300,7 +1313,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Dir3A();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::IRect",
@@ -1619,7 +1633,8 @@ struct IRect {
     #[lua(output(proxy))]
     max: bevy::math::prelude::IVec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::Rect",
@@ -1949,7 +1964,8 @@ struct Rect {
     #[lua(output(proxy))]
     max: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::prelude::URect",
@@ -2268,7 +2284,8 @@ struct URect {
     #[lua(output(proxy))]
     max: bevy::math::prelude::UVec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(derive(), remote = "bevy::math::Affine3", functions[])]
 struct Affine3 {
     #[lua(output(proxy))]
@@ -2276,7 +2293,8 @@ struct Affine3 {
     #[lua(output(proxy))]
     translation: bevy::math::prelude::Vec3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::Aabb2d",
@@ -2331,7 +2349,8 @@ struct Aabb2d {
     #[lua(output(proxy))]
     max: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::BoundingCircle",
@@ -2392,7 +2411,8 @@ struct BoundingCircle {
     #[lua(output(proxy))]
     circle: bevy::math::primitives::Circle,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Circle",
@@ -2450,7 +2470,8 @@ fn index(&self) -> String {
 struct Circle {
     radius: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Annulus",
@@ -2519,7 +2540,8 @@ struct Annulus {
     #[lua(output(proxy))]
     outer_circle: bevy::math::primitives::Circle,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Arc2d",
@@ -2672,7 +2694,8 @@ struct Arc2d {
     radius: f32,
     half_angle: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Capsule2d",
@@ -2718,7 +2741,8 @@ struct Capsule2d {
     radius: f32,
     half_length: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::CircularSector",
@@ -2847,7 +2871,8 @@ struct CircularSector {
     #[lua(output(proxy))]
     arc: bevy::math::primitives::Arc2d,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::CircularSegment",
@@ -2974,7 +2999,8 @@ struct CircularSegment {
     #[lua(output(proxy))]
     arc: bevy::math::primitives::Arc2d,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Ellipse",
@@ -3056,7 +3082,8 @@ struct Ellipse {
     #[lua(output(proxy))]
     half_size: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Line2d",
@@ -3088,7 +3115,8 @@ struct Line2d {
     #[lua(output(proxy))]
     direction: bevy::math::prelude::Dir2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Plane2d",
@@ -3132,7 +3160,8 @@ struct Plane2d {
     #[lua(output(proxy))]
     normal: bevy::math::prelude::Dir2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Rectangle",
@@ -3221,7 +3250,8 @@ struct Rectangle {
     #[lua(output(proxy))]
     half_size: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::RegularPolygon",
@@ -3324,7 +3354,8 @@ struct RegularPolygon {
     circumcircle: bevy::math::primitives::Circle,
     sides: u32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Rhombus",
@@ -3416,7 +3447,8 @@ struct Rhombus {
     #[lua(output(proxy))]
     half_diagonals: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Segment2d",
@@ -3474,7 +3506,8 @@ struct Segment2d {
     direction: bevy::math::prelude::Dir2,
     half_length: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Triangle2d",
@@ -3557,7 +3590,8 @@ fn index(&self) -> String {
 struct Triangle2d {
     vertices: ReflectedValue,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::Aabb3d",
@@ -3587,7 +3621,8 @@ struct Aabb3d {
     #[lua(output(proxy))]
     max: bevy::math::Vec3A,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::BoundingSphere",
@@ -3624,7 +3659,8 @@ struct BoundingSphere {
     #[lua(output(proxy))]
     sphere: bevy::math::primitives::Sphere,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Sphere",
@@ -3682,7 +3718,8 @@ fn index(&self) -> String {
 struct Sphere {
     radius: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Cuboid",
@@ -3775,7 +3812,8 @@ struct Cuboid {
     #[lua(output(proxy))]
     half_size: bevy::math::prelude::Vec3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Cylinder",
@@ -3836,7 +3874,8 @@ struct Cylinder {
     radius: f32,
     half_height: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Capsule3d",
@@ -3883,7 +3922,8 @@ struct Capsule3d {
     radius: f32,
     half_length: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Cone",
@@ -3952,7 +3992,8 @@ struct Cone {
     radius: f32,
     height: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::ConicalFrustum",
@@ -3985,7 +4026,8 @@ struct ConicalFrustum {
     radius_bottom: f32,
     height: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::InfinitePlane3d",
@@ -4073,7 +4115,8 @@ struct InfinitePlane3d {
     #[lua(output(proxy))]
     normal: bevy::math::prelude::Dir3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Line3d",
@@ -4105,7 +4148,8 @@ struct Line3d {
     #[lua(output(proxy))]
     direction: bevy::math::prelude::Dir3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Segment3d",
@@ -4163,7 +4207,8 @@ struct Segment3d {
     direction: bevy::math::prelude::Dir3,
     half_length: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Torus",
@@ -4222,7 +4267,8 @@ struct Torus {
     minor_radius: f32,
     major_radius: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Triangle3d",
@@ -4320,7 +4366,8 @@ fn index(&self) -> String {
 struct Triangle3d {
     vertices: ReflectedValue,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::RayCast2d",
@@ -4395,7 +4442,8 @@ struct RayCast2d {
     ray: bevy::math::Ray2d,
     max: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::AabbCast2d",
@@ -4457,7 +4505,8 @@ struct AabbCast2d {
     #[lua(output(proxy))]
     aabb: bevy::math::bounding::Aabb2d,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::BoundingCircleCast",
@@ -4519,7 +4568,8 @@ struct BoundingCircleCast {
     #[lua(output(proxy))]
     circle: bevy::math::bounding::BoundingCircle,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::RayCast3d",
@@ -4583,7 +4633,8 @@ struct RayCast3d {
     direction: bevy::math::prelude::Dir3A,
     max: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::AabbCast3d",
@@ -4630,7 +4681,8 @@ struct AabbCast3d {
     #[lua(output(proxy))]
     aabb: bevy::math::bounding::Aabb3d,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::bounding::BoundingSphereCast",
@@ -4677,7 +4729,8 @@ struct BoundingSphereCast {
     #[lua(output(proxy))]
     sphere: bevy::math::bounding::BoundingSphere,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::curve::interval::Interval",
@@ -4775,7 +4828,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Interval {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::FloatOrd",
@@ -4840,7 +4894,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct FloatOrd(f32);
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Plane3d",
@@ -4888,7 +4943,8 @@ struct Plane3d {
     #[lua(output(proxy))]
     half_size: bevy::math::prelude::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::primitives::Tetrahedron",
@@ -4954,7 +5010,8 @@ fn index(&self) -> String {
 struct Tetrahedron {
     vertices: ReflectedValue,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::curve::easing::EaseFunction",
@@ -4983,8 +5040,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct EaseFunction {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -5215,7 +5274,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyMathAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyMathAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -5537,3 +5598,154 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyMathAPIProvider {
         app.register_foreign_lua_type::<bevy::math::curve::easing::EaseFunction>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyMathRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyMathRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiAspectRatio>();
+        engine.register_rhai_proxy::<RhaiCompassOctant>();
+        engine.register_rhai_proxy::<RhaiCompassQuadrant>();
+        engine.register_rhai_proxy::<RhaiIsometry2d>();
+        engine.register_rhai_proxy::<RhaiIsometry3d>();
+        engine.register_rhai_proxy::<RhaiRay2d>();
+        engine.register_rhai_proxy::<RhaiRay3d>();
+        engine.register_rhai_proxy::<RhaiRot2>();
+        engine.register_rhai_proxy::<RhaiDir2>();
+        engine.register_rhai_proxy::<RhaiDir3>();
+        engine.register_rhai_proxy::<RhaiDir3A>();
+        engine.register_rhai_proxy::<RhaiIRect>();
+        engine.register_rhai_proxy::<RhaiRect>();
+        engine.register_rhai_proxy::<RhaiURect>();
+        engine.register_rhai_proxy::<RhaiAffine3>();
+        engine.register_rhai_proxy::<RhaiAabb2d>();
+        engine.register_rhai_proxy::<RhaiBoundingCircle>();
+        engine.register_rhai_proxy::<RhaiCircle>();
+        engine.register_rhai_proxy::<RhaiAnnulus>();
+        engine.register_rhai_proxy::<RhaiArc2d>();
+        engine.register_rhai_proxy::<RhaiCapsule2d>();
+        engine.register_rhai_proxy::<RhaiCircularSector>();
+        engine.register_rhai_proxy::<RhaiCircularSegment>();
+        engine.register_rhai_proxy::<RhaiEllipse>();
+        engine.register_rhai_proxy::<RhaiLine2d>();
+        engine.register_rhai_proxy::<RhaiPlane2d>();
+        engine.register_rhai_proxy::<RhaiRectangle>();
+        engine.register_rhai_proxy::<RhaiRegularPolygon>();
+        engine.register_rhai_proxy::<RhaiRhombus>();
+        engine.register_rhai_proxy::<RhaiSegment2d>();
+        engine.register_rhai_proxy::<RhaiTriangle2d>();
+        engine.register_rhai_proxy::<RhaiAabb3d>();
+        engine.register_rhai_proxy::<RhaiBoundingSphere>();
+        engine.register_rhai_proxy::<RhaiSphere>();
+        engine.register_rhai_proxy::<RhaiCuboid>();
+        engine.register_rhai_proxy::<RhaiCylinder>();
+        engine.register_rhai_proxy::<RhaiCapsule3d>();
+        engine.register_rhai_proxy::<RhaiCone>();
+        engine.register_rhai_proxy::<RhaiConicalFrustum>();
+        engine.register_rhai_proxy::<RhaiInfinitePlane3d>();
+        engine.register_rhai_proxy::<RhaiLine3d>();
+        engine.register_rhai_proxy::<RhaiSegment3d>();
+        engine.register_rhai_proxy::<RhaiTorus>();
+        engine.register_rhai_proxy::<RhaiTriangle3d>();
+        engine.register_rhai_proxy::<RhaiRayCast2d>();
+        engine.register_rhai_proxy::<RhaiAabbCast2d>();
+        engine.register_rhai_proxy::<RhaiBoundingCircleCast>();
+        engine.register_rhai_proxy::<RhaiRayCast3d>();
+        engine.register_rhai_proxy::<RhaiAabbCast3d>();
+        engine.register_rhai_proxy::<RhaiBoundingSphereCast>();
+        engine.register_rhai_proxy::<RhaiInterval>();
+        engine.register_rhai_proxy::<RhaiFloatOrd>();
+        engine.register_rhai_proxy::<RhaiPlane3d>();
+        engine.register_rhai_proxy::<RhaiTetrahedron>();
+        engine.register_rhai_proxy::<RhaiEaseFunction>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::math::AspectRatio>();
+        app.register_foreign_rhai_type::<bevy::math::CompassOctant>();
+        app.register_foreign_rhai_type::<bevy::math::CompassQuadrant>();
+        app.register_foreign_rhai_type::<bevy::math::Isometry2d>();
+        app.register_foreign_rhai_type::<bevy::math::Isometry3d>();
+        app.register_foreign_rhai_type::<bevy::math::Ray2d>();
+        app.register_foreign_rhai_type::<bevy::math::Ray3d>();
+        app.register_foreign_rhai_type::<bevy::math::Rot2>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::Dir2>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::Dir3>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::Dir3A>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::IRect>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::Rect>();
+        app.register_foreign_rhai_type::<bevy::math::prelude::URect>();
+        app.register_foreign_rhai_type::<bevy::math::Affine3>();
+        app.register_foreign_rhai_type::<bevy::math::bounding::Aabb2d>();
+        app.register_foreign_rhai_type::<
+                bevy::math::bounding::BoundingCircle,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Circle>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Annulus>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Arc2d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Capsule2d>();
+        app.register_foreign_rhai_type::<
+                bevy::math::primitives::CircularSector,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::math::primitives::CircularSegment,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Ellipse>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Line2d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Plane2d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Rectangle>();
+        app.register_foreign_rhai_type::<
+                bevy::math::primitives::RegularPolygon,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Rhombus>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Segment2d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Triangle2d>();
+        app.register_foreign_rhai_type::<bevy::math::bounding::Aabb3d>();
+        app.register_foreign_rhai_type::<
+                bevy::math::bounding::BoundingSphere,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Sphere>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Cuboid>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Cylinder>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Capsule3d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Cone>();
+        app.register_foreign_rhai_type::<
+                bevy::math::primitives::ConicalFrustum,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::math::primitives::InfinitePlane3d,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Line3d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Segment3d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Torus>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Triangle3d>();
+        app.register_foreign_rhai_type::<bevy::math::bounding::RayCast2d>();
+        app.register_foreign_rhai_type::<bevy::math::bounding::AabbCast2d>();
+        app.register_foreign_rhai_type::<
+                bevy::math::bounding::BoundingCircleCast,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::bounding::RayCast3d>();
+        app.register_foreign_rhai_type::<bevy::math::bounding::AabbCast3d>();
+        app.register_foreign_rhai_type::<
+                bevy::math::bounding::BoundingSphereCast,
+            >();
+        app.register_foreign_rhai_type::<
+                bevy::math::curve::interval::Interval,
+            >();
+        app.register_foreign_rhai_type::<bevy::math::FloatOrd>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Plane3d>();
+        app.register_foreign_rhai_type::<bevy::math::primitives::Tetrahedron>();
+        app.register_foreign_rhai_type::<
+                bevy::math::curve::easing::EaseFunction,
+            >();
+    }
+}
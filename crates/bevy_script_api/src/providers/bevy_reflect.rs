@@ -3,10 +3,13 @@
 #![allow(unused, deprecated, dead_code)]
 #![cfg_attr(rustfmt, rustfmt_skip)]
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicBool",
@@ -31,7 +34,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicBool {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicI16",
@@ -55,7 +59,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicI16 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicI32",
@@ -79,7 +84,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicI32 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicI64",
@@ -103,7 +109,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicI64 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicI8",
@@ -127,7 +134,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicI8 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicIsize",
@@ -151,7 +159,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicIsize {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicU16",
@@ -175,7 +184,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicU16 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicU32",
@@ -199,7 +209,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicU32 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicU64",
@@ -223,7 +234,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicU64 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicU8",
@@ -247,7 +259,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicU8 {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(),
     remote = "std::sync::atomic::AtomicUsize",
@@ -271,7 +284,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct AtomicUsize {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::utils::Duration",
@@ -811,7 +825,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Duration {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::utils::Instant",
@@ -978,7 +993,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Instant();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "std::path::PathBuf",
@@ -1120,7 +1136,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct PathBuf {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "std::ops::RangeFull",
@@ -1159,7 +1176,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct RangeFull {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Quat",
@@ -1713,7 +1731,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Quat();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Vec3",
@@ -2627,7 +2646,8 @@ struct Vec3 {
     y: f32,
     z: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::IVec2",
@@ -3298,7 +3318,8 @@ struct IVec2 {
     x: i32,
     y: i32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::IVec3",
@@ -3975,7 +3996,8 @@ struct IVec3 {
     y: i32,
     z: i32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::IVec4",
@@ -4639,7 +4661,8 @@ struct IVec4 {
     z: i32,
     w: i32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::I64Vec2",
@@ -5306,7 +5329,8 @@ struct I64Vec2 {
     x: i64,
     y: i64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::I64Vec3",
@@ -5979,7 +6003,8 @@ struct I64Vec3 {
     y: i64,
     z: i64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::I64Vec4",
@@ -6639,7 +6664,8 @@ struct I64Vec4 {
     z: i64,
     w: i64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::UVec2",
@@ -7199,7 +7225,8 @@ struct UVec2 {
     x: u32,
     y: u32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::UVec3",
@@ -7789,7 +7816,8 @@ struct UVec3 {
     y: u32,
     z: u32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::UVec4",
@@ -8366,7 +8394,8 @@ struct UVec4 {
     z: u32,
     w: u32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::U64Vec2",
@@ -8922,7 +8951,8 @@ struct U64Vec2 {
     x: u64,
     y: u64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::U64Vec3",
@@ -9508,7 +9538,8 @@ struct U64Vec3 {
     y: u64,
     z: u64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::U64Vec4",
@@ -10081,7 +10112,8 @@ struct U64Vec4 {
     z: u64,
     w: u64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Vec2",
@@ -11015,7 +11047,8 @@ struct Vec2 {
     x: f32,
     y: f32,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Vec3A",
@@ -11941,7 +11974,8 @@ fn index(&mut self, lua: &Lua, idx: crate::lua::util::LuaIndex, val: f32) -> Res
 "#]
 )]
 struct Vec3A();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Vec4",
@@ -12817,7 +12851,8 @@ fn index(&mut self, lua: &Lua, idx: crate::lua::util::LuaIndex, val: f32) -> Res
 "#]
 )]
 struct Vec4();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::BVec2",
@@ -12919,7 +12954,8 @@ struct BVec2 {
     x: bool,
     y: bool,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::BVec3",
@@ -13022,7 +13058,8 @@ struct BVec3 {
     y: bool,
     z: bool,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::BVec4",
@@ -13126,7 +13163,8 @@ struct BVec4 {
     z: bool,
     w: bool,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DVec2",
@@ -14068,7 +14106,8 @@ struct DVec2 {
     x: f64,
     y: f64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DVec3",
@@ -14997,7 +15036,8 @@ struct DVec3 {
     y: f64,
     z: f64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DVec4",
@@ -15885,7 +15925,8 @@ struct DVec4 {
     z: f64,
     w: f64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Mat2",
@@ -16249,7 +16290,8 @@ fn index(&self, ctx : &Lua, idx: crate::lua::util::LuaIndex) -> Result<LuaVec2,_
 "#]
 )]
 struct Mat2();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Mat3",
@@ -16754,7 +16796,8 @@ struct Mat3 {
     #[lua(output(proxy))]
     z_axis: bevy::math::Vec3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Mat3A",
@@ -17259,7 +17302,8 @@ struct Mat3A {
     #[lua(output(proxy))]
     z_axis: bevy::math::Vec3A,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Mat4",
@@ -18026,7 +18070,8 @@ struct Mat4 {
     #[lua(output(proxy))]
     w_axis: bevy::math::Vec4,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DMat2",
@@ -18373,7 +18418,8 @@ struct DMat2 {
     #[lua(output(proxy))]
     y_axis: bevy::math::DVec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DMat3",
@@ -18863,7 +18909,8 @@ struct DMat3 {
     #[lua(output(proxy))]
     z_axis: bevy::math::DVec3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DMat4",
@@ -19598,7 +19645,8 @@ struct DMat4 {
     #[lua(output(proxy))]
     w_axis: bevy::math::DVec4,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Affine2",
@@ -19842,7 +19890,8 @@ struct Affine2 {
     #[lua(output(proxy))]
     translation: bevy::math::Vec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::Affine3A",
@@ -20191,7 +20240,8 @@ struct Affine3A {
     #[lua(output(proxy))]
     translation: bevy::math::Vec3A,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DAffine2",
@@ -20416,7 +20466,8 @@ struct DAffine2 {
     #[lua(output(proxy))]
     translation: bevy::math::DVec2,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DAffine3",
@@ -20749,7 +20800,8 @@ struct DAffine3 {
     #[lua(output(proxy))]
     translation: bevy::math::DVec3,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::DQuat",
@@ -21282,7 +21334,8 @@ struct DQuat {
     z: f64,
     w: f64,
 }
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::EulerRot",
@@ -21321,7 +21374,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct EulerRot {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::BVec3A",
@@ -21414,7 +21468,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct BVec3A();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::math::BVec4A",
@@ -21507,7 +21562,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct BVec4A();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "smol_str::SmolStr",
@@ -21564,7 +21620,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct SmolStr();
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "uuid::Uuid",
@@ -21966,8 +22023,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct Uuid();
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -22236,7 +22295,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyReflectAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyReflectAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -22576,3 +22637,132 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyReflectAPIProvider {
         app.register_foreign_lua_type::<uuid::Uuid>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyReflectRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyReflectRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiAtomicBool>();
+        engine.register_rhai_proxy::<RhaiAtomicI16>();
+        engine.register_rhai_proxy::<RhaiAtomicI32>();
+        engine.register_rhai_proxy::<RhaiAtomicI64>();
+        engine.register_rhai_proxy::<RhaiAtomicI8>();
+        engine.register_rhai_proxy::<RhaiAtomicIsize>();
+        engine.register_rhai_proxy::<RhaiAtomicU16>();
+        engine.register_rhai_proxy::<RhaiAtomicU32>();
+        engine.register_rhai_proxy::<RhaiAtomicU64>();
+        engine.register_rhai_proxy::<RhaiAtomicU8>();
+        engine.register_rhai_proxy::<RhaiAtomicUsize>();
+        engine.register_rhai_proxy::<RhaiDuration>();
+        engine.register_rhai_proxy::<RhaiInstant>();
+        engine.register_rhai_proxy::<RhaiPathBuf>();
+        engine.register_rhai_proxy::<RhaiRangeFull>();
+        engine.register_rhai_proxy::<RhaiQuat>();
+        engine.register_rhai_proxy::<RhaiVec3>();
+        engine.register_rhai_proxy::<RhaiIVec2>();
+        engine.register_rhai_proxy::<RhaiIVec3>();
+        engine.register_rhai_proxy::<RhaiIVec4>();
+        engine.register_rhai_proxy::<RhaiI64Vec2>();
+        engine.register_rhai_proxy::<RhaiI64Vec3>();
+        engine.register_rhai_proxy::<RhaiI64Vec4>();
+        engine.register_rhai_proxy::<RhaiUVec2>();
+        engine.register_rhai_proxy::<RhaiUVec3>();
+        engine.register_rhai_proxy::<RhaiUVec4>();
+        engine.register_rhai_proxy::<RhaiU64Vec2>();
+        engine.register_rhai_proxy::<RhaiU64Vec3>();
+        engine.register_rhai_proxy::<RhaiU64Vec4>();
+        engine.register_rhai_proxy::<RhaiVec2>();
+        engine.register_rhai_proxy::<RhaiVec3A>();
+        engine.register_rhai_proxy::<RhaiVec4>();
+        engine.register_rhai_proxy::<RhaiBVec2>();
+        engine.register_rhai_proxy::<RhaiBVec3>();
+        engine.register_rhai_proxy::<RhaiBVec4>();
+        engine.register_rhai_proxy::<RhaiDVec2>();
+        engine.register_rhai_proxy::<RhaiDVec3>();
+        engine.register_rhai_proxy::<RhaiDVec4>();
+        engine.register_rhai_proxy::<RhaiMat2>();
+        engine.register_rhai_proxy::<RhaiMat3>();
+        engine.register_rhai_proxy::<RhaiMat3A>();
+        engine.register_rhai_proxy::<RhaiMat4>();
+        engine.register_rhai_proxy::<RhaiDMat2>();
+        engine.register_rhai_proxy::<RhaiDMat3>();
+        engine.register_rhai_proxy::<RhaiDMat4>();
+        engine.register_rhai_proxy::<RhaiAffine2>();
+        engine.register_rhai_proxy::<RhaiAffine3A>();
+        engine.register_rhai_proxy::<RhaiDAffine2>();
+        engine.register_rhai_proxy::<RhaiDAffine3>();
+        engine.register_rhai_proxy::<RhaiDQuat>();
+        engine.register_rhai_proxy::<RhaiEulerRot>();
+        engine.register_rhai_proxy::<RhaiBVec3A>();
+        engine.register_rhai_proxy::<RhaiBVec4A>();
+        engine.register_rhai_proxy::<RhaiSmolStr>();
+        engine.register_rhai_proxy::<RhaiUuid>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicBool>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicI16>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicI32>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicI64>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicI8>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicIsize>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicU16>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicU32>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicU64>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicU8>();
+        app.register_foreign_rhai_type::<std::sync::atomic::AtomicUsize>();
+        app.register_foreign_rhai_type::<bevy::utils::Duration>();
+        app.register_foreign_rhai_type::<bevy::utils::Instant>();
+        app.register_foreign_rhai_type::<std::path::PathBuf>();
+        app.register_foreign_rhai_type::<std::ops::RangeFull>();
+        app.register_foreign_rhai_type::<bevy::math::Quat>();
+        app.register_foreign_rhai_type::<bevy::math::Vec3>();
+        app.register_foreign_rhai_type::<bevy::math::IVec2>();
+        app.register_foreign_rhai_type::<bevy::math::IVec3>();
+        app.register_foreign_rhai_type::<bevy::math::IVec4>();
+        app.register_foreign_rhai_type::<bevy::math::I64Vec2>();
+        app.register_foreign_rhai_type::<bevy::math::I64Vec3>();
+        app.register_foreign_rhai_type::<bevy::math::I64Vec4>();
+        app.register_foreign_rhai_type::<bevy::math::UVec2>();
+        app.register_foreign_rhai_type::<bevy::math::UVec3>();
+        app.register_foreign_rhai_type::<bevy::math::UVec4>();
+        app.register_foreign_rhai_type::<bevy::math::U64Vec2>();
+        app.register_foreign_rhai_type::<bevy::math::U64Vec3>();
+        app.register_foreign_rhai_type::<bevy::math::U64Vec4>();
+        app.register_foreign_rhai_type::<bevy::math::Vec2>();
+        app.register_foreign_rhai_type::<bevy::math::Vec3A>();
+        app.register_foreign_rhai_type::<bevy::math::Vec4>();
+        app.register_foreign_rhai_type::<bevy::math::BVec2>();
+        app.register_foreign_rhai_type::<bevy::math::BVec3>();
+        app.register_foreign_rhai_type::<bevy::math::BVec4>();
+        app.register_foreign_rhai_type::<bevy::math::DVec2>();
+        app.register_foreign_rhai_type::<bevy::math::DVec3>();
+        app.register_foreign_rhai_type::<bevy::math::DVec4>();
+        app.register_foreign_rhai_type::<bevy::math::Mat2>();
+        app.register_foreign_rhai_type::<bevy::math::Mat3>();
+        app.register_foreign_rhai_type::<bevy::math::Mat3A>();
+        app.register_foreign_rhai_type::<bevy::math::Mat4>();
+        app.register_foreign_rhai_type::<bevy::math::DMat2>();
+        app.register_foreign_rhai_type::<bevy::math::DMat3>();
+        app.register_foreign_rhai_type::<bevy::math::DMat4>();
+        app.register_foreign_rhai_type::<bevy::math::Affine2>();
+        app.register_foreign_rhai_type::<bevy::math::Affine3A>();
+        app.register_foreign_rhai_type::<bevy::math::DAffine2>();
+        app.register_foreign_rhai_type::<bevy::math::DAffine3>();
+        app.register_foreign_rhai_type::<bevy::math::DQuat>();
+        app.register_foreign_rhai_type::<bevy::math::EulerRot>();
+        app.register_foreign_rhai_type::<bevy::math::BVec3A>();
+        app.register_foreign_rhai_type::<bevy::math::BVec4A>();
+        app.register_foreign_rhai_type::<smol_str::SmolStr>();
+        app.register_foreign_rhai_type::<uuid::Uuid>();
+    }
+}
@@ -5,10 +5,13 @@
 use super::bevy_ecs::*;
 use super::bevy_reflect::*;
 extern crate self as bevy_script_api;
-use bevy_script_api::{
-    lua::RegisterForeignLuaType, ReflectedValue, common::bevy::GetWorld,
-};
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+use bevy_script_api::{ReflectedValue, common::bevy::GetWorld};
+#[cfg(feature = "lua")]
+use bevy_script_api::lua::RegisterForeignLuaType;
+#[cfg(feature = "rhai")]
+use bevy_script_api::rhai::{RegisterForeignRhaiType, RegisterRhaiProxy};
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::prelude::Fixed",
@@ -26,7 +29,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Fixed {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::prelude::Real",
@@ -44,7 +48,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Real {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::prelude::Timer",
@@ -414,7 +419,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Timer {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::prelude::TimerMode",
@@ -449,7 +455,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct TimerMode {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::prelude::Virtual",
@@ -467,7 +474,8 @@ fn index(&self) -> String {
 "#]
 )]
 struct Virtual {}
-#[derive(bevy_mod_scripting_lua_derive::LuaProxy)]
+#[cfg_attr(feature = "lua", derive(bevy_mod_scripting_lua_derive::LuaProxy))]
+#[cfg_attr(feature = "rhai", derive(bevy_mod_scripting_rhai_derive::RhaiProxy))]
 #[proxy(
     derive(clone),
     remote = "bevy::time::Stopwatch",
@@ -650,8 +658,10 @@ fn index(&self) -> String {
 "#]
 )]
 struct Stopwatch {}
+#[cfg(feature = "lua")]
 #[derive(Default)]
 pub(crate) struct Globals;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
     fn add_instances<
         'lua,
@@ -670,7 +680,9 @@ impl bevy_mod_scripting_lua::tealr::mlu::ExportInstances for Globals {
         Ok(())
     }
 }
+#[cfg(feature = "lua")]
 pub struct BevyTimeAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for BevyTimeAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -734,3 +746,34 @@ impl bevy_mod_scripting_core::hosts::APIProvider for BevyTimeAPIProvider {
         app.register_foreign_lua_type::<bevy::time::Stopwatch>();
     }
 }
+#[cfg(feature = "rhai")]
+pub struct BevyTimeRhaiAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for BevyTimeRhaiAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        engine.register_rhai_proxy::<RhaiFixed>();
+        engine.register_rhai_proxy::<RhaiReal>();
+        engine.register_rhai_proxy::<RhaiTimer>();
+        engine.register_rhai_proxy::<RhaiTimerMode>();
+        engine.register_rhai_proxy::<RhaiVirtual>();
+        engine.register_rhai_proxy::<RhaiStopwatch>();
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        None
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        app.register_foreign_rhai_type::<bevy::time::prelude::Fixed>();
+        app.register_foreign_rhai_type::<bevy::time::prelude::Real>();
+        app.register_foreign_rhai_type::<bevy::time::prelude::Timer>();
+        app.register_foreign_rhai_type::<bevy::time::prelude::TimerMode>();
+        app.register_foreign_rhai_type::<bevy::time::prelude::Virtual>();
+        app.register_foreign_rhai_type::<bevy::time::Stopwatch>();
+    }
+}
@@ -11,8 +11,11 @@ pub mod bevy_time;
 pub mod bevy_hierarchy;
 pub mod bevy_reflect;
 extern crate self as bevy_script_api;
+#[cfg(feature = "lua")]
 use bevy_mod_scripting_core::docs::DocFragment;
+#[cfg(feature = "lua")]
 pub struct LuaBevyAPIProvider;
+#[cfg(feature = "lua")]
 impl bevy_mod_scripting_core::hosts::APIProvider for LuaBevyAPIProvider {
     type APITarget = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
     type ScriptContext = std::sync::Mutex<bevy_mod_scripting_lua::tealr::mlu::mlua::Lua>;
@@ -78,3 +81,44 @@ impl bevy_mod_scripting_core::hosts::APIProvider for LuaBevyAPIProvider {
         bevy_reflect::BevyReflectAPIProvider.register_with_app(app);
     }
 }
+/// Registers the proxies of every generated Bevy type with Rhai
+#[cfg(feature = "rhai")]
+pub struct RhaiBevyTypesAPIProvider;
+#[cfg(feature = "rhai")]
+impl bevy_mod_scripting_core::hosts::APIProvider for RhaiBevyTypesAPIProvider {
+    type APITarget = bevy_mod_scripting_rhai::rhai::Engine;
+    type ScriptContext = bevy_mod_scripting_rhai::RhaiContext;
+    type DocTarget = bevy_mod_scripting_rhai::docs::RhaiDocFragment;
+    fn attach_api(
+        &mut self,
+        engine: &mut Self::APITarget,
+    ) -> Result<(), bevy_mod_scripting_core::error::ScriptError> {
+        bevy_ecs::BevyEcsRhaiAPIProvider.attach_api(engine)?;
+        bevy_transform::BevyTransformRhaiAPIProvider.attach_api(engine)?;
+        bevy_math::BevyMathRhaiAPIProvider.attach_api(engine)?;
+        bevy_input::BevyInputRhaiAPIProvider.attach_api(engine)?;
+        bevy_core::BevyCoreRhaiAPIProvider.attach_api(engine)?;
+        bevy_time::BevyTimeRhaiAPIProvider.attach_api(engine)?;
+        bevy_hierarchy::BevyHierarchyRhaiAPIProvider.attach_api(engine)?;
+        bevy_reflect::BevyReflectRhaiAPIProvider.attach_api(engine)?;
+        Ok(())
+    }
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        Some(
+            bevy_mod_scripting_rhai::docs::RhaiDocFragment::new(
+                "BevyAPI",
+                |engine| RhaiBevyTypesAPIProvider.attach_api(engine),
+            ),
+        )
+    }
+    fn register_with_app(&self, app: &mut bevy::app::App) {
+        bevy_ecs::BevyEcsRhaiAPIProvider.register_with_app(app);
+        bevy_transform::BevyTransformRhaiAPIProvider.register_with_app(app);
+        bevy_math::BevyMathRhaiAPIProvider.register_with_app(app);
+        bevy_input::BevyInputRhaiAPIProvider.register_with_app(app);
+        bevy_core::BevyCoreRhaiAPIProvider.register_with_app(app);
+        bevy_time::BevyTimeRhaiAPIProvider.register_with_app(app);
+        bevy_hierarchy::BevyHierarchyRhaiAPIProvider.register_with_app(app);
+        bevy_reflect::BevyReflectRhaiAPIProvider.register_with_app(app);
+    }
+}
@@ -1,19 +1,35 @@
-use bevy::prelude::Entity;
-use bevy_mod_scripting_core::{prelude::*, world::WorldPointer};
+use bevy::prelude::{Entity, World};
+use bevy_mod_scripting_core::{
+    prelude::*,
+    world::{ScriptCommandQueue, WorldPointer, WorldPointerGuard},
+};
 
 #[allow(deprecated)]
 use bevy_mod_scripting_rhai::{
+    calling_script_id,
     prelude::*,
-    rhai::{self, CustomType, INT},
+    rhai::{self, CallFnOptions, CustomType, FnPtr, INT},
 };
 use rhai::plugin::*;
 
 use crate::{
-    common::bevy::{ScriptQueryBuilder, ScriptTypeRegistration, ScriptWorld},
-    ReflectedValue,
+    common::bevy::{
+        commands::ScriptCommands,
+        events::SharedReflect,
+        hooks::{ComponentChange, ScriptChangeHook},
+        observers::ScriptObserver,
+        query::{ScriptPreparedQuery, ScriptQueryIter},
+        script_types::ScriptTypeKind,
+        systems::{
+            report_script_system_error, ScriptCallback, ScriptFunctionCall, ScriptFunctionRef,
+            ScriptSystem, ScriptSystemArg, ScriptSystemHost,
+        },
+        ScriptQueryBuilder, ScriptQueryOr, ScriptQueryResult, ScriptTypeRegistration, ScriptWorld,
+    },
+    ReflectReference, ReflectedValue,
 };
 
-use super::{RegisterForeignRhaiType, ToDynamic};
+use super::{dynamic_to_script_value, ApplyRhai, RegisterForeignRhaiType, ToDynamic};
 
 #[allow(deprecated)]
 impl CustomType for ScriptTypeRegistration {
@@ -23,7 +39,7 @@ impl CustomType for ScriptTypeRegistration {
             .with_fn("short_name", |self_: &mut Self| {
                 ImmutableString::from(self_.short_name())
             })
-            .with_fn("type_name", |self_: &mut Self| self_.type_name())
+            .with_fn("type_name", |self_: &mut Self| self_.type_name().to_owned())
             .with_fn("to_string", |self_: &mut Self| self_.to_string())
             .with_fn("to_debug", |self_: &mut Self| format!("{:?}", self_));
     }
@@ -55,40 +71,186 @@ impl CustomType for ScriptQueryBuilder {
 
                     Dynamic::from(self_.clone())
                 },
+            )
+            .with_fn(
+                "optional_components",
+                |self_: &mut Self, optional: Vec<Dynamic>| {
+                    self_.optional(
+                        optional
+                            .into_iter()
+                            .map(Dynamic::cast::<ScriptTypeRegistration>)
+                            .collect(),
+                    );
+
+                    Dynamic::from(self_.clone())
+                },
+            )
+            .with_fn(
+                "changed_components",
+                |self_: &mut Self, changed: Vec<Dynamic>| {
+                    self_.changed(
+                        changed
+                            .into_iter()
+                            .map(Dynamic::cast::<ScriptTypeRegistration>)
+                            .collect(),
+                    );
+
+                    Dynamic::from(self_.clone())
+                },
+            )
+            .with_fn(
+                "added_components",
+                |self_: &mut Self, added: Vec<Dynamic>| {
+                    self_.added(
+                        added
+                            .into_iter()
+                            .map(Dynamic::cast::<ScriptTypeRegistration>)
+                            .collect(),
+                    );
+
+                    Dynamic::from(self_.clone())
+                },
+            )
+            // `or` is not a valid function name, groups are given as maps with the same keys as the builder methods, i.e. `with_components`
+            .with_fn("any_of", |self_: &mut Self, group: rhai::Map| {
+                let mut or = ScriptQueryOr::default();
+                for (key, components) in group {
+                    let components = components
+                        .into_typed_array::<ScriptTypeRegistration>()
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                format!("Expected an array of types for `{key}`, got {e}").into(),
+                                Position::NONE,
+                            ))
+                        })?;
+
+                    match key.as_str() {
+                        "with_components" => or.with.extend(components),
+                        "without_components" => or.without.extend(components),
+                        "changed_components" => or.changed.extend(components),
+                        "added_components" => or.added.extend(components),
+                        _ => {
+                            return Err(Box::new(EvalAltResult::ErrorRuntime(
+                                format!("Unknown filter `{key}`, expected one of `with_components`, `without_components`, `changed_components` or `added_components`").into(),
+                                Position::NONE,
+                            )))
+                        }
+                    }
+                }
+                self_.or(or);
+
+                Ok(Dynamic::from(self_.clone()))
+            })
+            .with_fn("prepare", |self_: &mut Self| {
+                self_.prepare().map_err(|e| {
+                    Box::new(EvalAltResult::ErrorRuntime(
+                        e.to_string().into(),
+                        Position::NONE,
+                    ))
+                })
+            });
+    }
+}
+
+impl CustomType for ScriptPreparedQuery {
+    fn build(mut builder: rhai::TypeBuilder<Self>) {
+        builder
+            .with_name("PreparedQuery")
+            // the query runs in the world passed by the script, the world it was prepared in is only valid during that run
+            .with_fn("iter", |self_: &mut Self, world: ScriptWorld| {
+                self_
+                    .iter(&world)
+                    .map(|iter| ScriptQueryMaps {
+                        names: self_.query().component_names(),
+                        iter,
+                    })
+                    .map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+            })
+            .with_fn("single", |self_: &mut Self, world: ScriptWorld| {
+                let names = self_.query().component_names();
+                self_
+                    .single(&world)
+                    .map(|result| query_map(&names, result))
+                    .map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+            })
+            // returns `()` if the entity does not match the query
+            .with_fn(
+                "get",
+                |self_: &mut Self, world: ScriptWorld, entity: Entity| {
+                    let names = self_.query().component_names();
+                    self_
+                        .get(&world, entity)
+                        .map(|result| match result {
+                            Some(result) => Dynamic::from(query_map(&names, result)),
+                            None => Dynamic::UNIT,
+                        })
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                },
             );
     }
 }
 
+/// Converts a query result into a map from component names to components, missing optional components are set to `()`
+fn query_map(names: &[String], result: ScriptQueryResult) -> rhai::Map {
+    let mut map = rhai::Map::new();
+    map.insert("Entity".into(), Dynamic::from(result.0));
+
+    let components = result.1.into_iter().map(Some).chain(result.2);
+    for (name, component) in names.iter().zip(components) {
+        let value = match component {
+            Some(component) => component
+                .to_dynamic()
+                .unwrap_or_else(|_| panic!("Converting component {} to dynamic failed!", name)),
+            None => Dynamic::UNIT,
+        };
+
+        map.insert(name.into(), value);
+    }
+
+    map
+}
+
+/// Iterates over the results of a query as maps from component names to components
+#[derive(Clone)]
+pub struct ScriptQueryMaps {
+    names: Vec<String>,
+    iter: ScriptQueryIter,
+}
+
+impl Iterator for ScriptQueryMaps {
+    type Item = rhai::Map;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|result| query_map(&self.names, result))
+    }
+}
+
 impl IntoIterator for ScriptQueryBuilder {
     type Item = rhai::Map;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
-
-    fn into_iter(mut self) -> Self::IntoIter {
-        self.build()
-            .expect("Query failed!")
-            .into_iter()
-            .map(|result| {
-                let mut map = rhai::Map::new();
-                map.insert("Entity".into(), Dynamic::from(result.0));
-
-                for component in result.1.into_iter() {
-                    let name = component
-                        .get(|value| value.get_represented_type_info()?.type_path_table().ident())
-                        .unwrap()
-                        .unwrap();
-
-                    map.insert(
-                        name.into(),
-                        component.to_dynamic().unwrap_or_else(|_| {
-                            panic!("Converting component {} to dynamic failed!", &name)
-                        }),
-                    );
-                }
+    type IntoIter = ScriptQueryMaps;
 
-                map
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
+    fn into_iter(self) -> Self::IntoIter {
+        ScriptQueryMaps {
+            names: self.query().component_names(),
+            iter: self.iter().expect("Query failed!"),
+        }
     }
 }
 
@@ -117,6 +279,57 @@ impl CustomType for ScriptWorld {
                         .and_then(|ok| ok.to_dynamic())
                 },
             )
+            .with_fn(
+                "insert_component",
+                |self_: ScriptWorld,
+                 entity: Entity,
+                 comp_type: ScriptTypeRegistration,
+                 value: Dynamic| {
+                    let value = dynamic_to_script_value(value, self_.clone().into())?;
+                    self_
+                        .insert_component(entity, comp_type, value)
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                        .and_then(|ok| ok.to_dynamic())
+                },
+            )
+            .with_fn(
+                "register_component",
+                |self_: ScriptWorld, name: &str, fields: rhai::Map| {
+                    register_script_type(&self_, name, ScriptTypeKind::Component, fields)
+                },
+            )
+            .with_fn("register_component", |self_: ScriptWorld, name: &str| {
+                register_script_type(&self_, name, ScriptTypeKind::Component, Default::default())
+            })
+            .with_fn(
+                "register_resource",
+                |self_: ScriptWorld, name: &str, fields: rhai::Map| {
+                    register_script_type(&self_, name, ScriptTypeKind::Resource, fields)
+                },
+            )
+            .with_fn("register_resource", |self_: ScriptWorld, name: &str| {
+                register_script_type(&self_, name, ScriptTypeKind::Resource, Default::default())
+            })
+            .with_fn(
+                "construct",
+                |self_: ScriptWorld, value_type: ScriptTypeRegistration, value: Dynamic| {
+                    let value = dynamic_to_script_value(value, self_.clone().into())?;
+                    self_
+                        .construct(&value_type, value)
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                        .and_then(|ok| ok.to_dynamic())
+                },
+            )
             .with_fn(
                 "get_component",
                 |self_: ScriptWorld, entity: Entity, comp_type: ScriptTypeRegistration| {
@@ -183,6 +396,35 @@ impl CustomType for ScriptWorld {
                     })
                 },
             )
+            .with_fn(
+                "insert_resource",
+                |self_: ScriptWorld, res_type: ScriptTypeRegistration, value: Dynamic| {
+                    let value = dynamic_to_script_value(value, self_.clone().into())?;
+                    self_
+                        .insert_resource(res_type, value)
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                        .and_then(|ok| ok.to_dynamic())
+                },
+            )
+            .with_fn(
+                "init_resource",
+                |self_: ScriptWorld, res_type: ScriptTypeRegistration| {
+                    self_
+                        .init_resource(res_type)
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                        .and_then(|ok| ok.to_dynamic())
+                },
+            )
             .with_fn(
                 "remove_resource",
                 |self_: &mut ScriptWorld, res_type: ScriptTypeRegistration| {
@@ -272,32 +514,629 @@ impl CustomType for ScriptWorld {
 
                 w.despawn(entity)
             })
+            .with_fn(
+                "add_system",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 label: &str,
+                 callback: FnPtr,
+                 query: ScriptQueryBuilder| {
+                    let sid = calling_script_id(&ctx).ok_or_else(|| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            "Script systems can only be added from within a script".into(),
+                            Position::NONE,
+                        ))
+                    })?;
+
+                    self_
+                        .add_system::<RhaiContext>(ScriptSystem {
+                            sid,
+                            label: label.to_owned(),
+                            callback: ScriptFunctionRef::new(
+                                callback.fn_name().to_owned(),
+                                callback,
+                            ),
+                            query: query.query().clone(),
+                            cache: query.cache().clone(),
+                        })
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                },
+            )
+            .with_fn(
+                "remove_system",
+                |ctx: NativeCallContext, self_: &mut ScriptWorld, callback: FnPtr| {
+                    calling_script_id(&ctx)
+                        .map(|sid| self_.remove_system::<RhaiContext>(sid, callback.fn_name()))
+                        .unwrap_or_default()
+                },
+            )
+            .with_fn(
+                "on_added",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 comp_type: ScriptTypeRegistration,
+                 callback: FnPtr| {
+                    add_change_hook(&ctx, self_, ComponentChange::Added, comp_type, callback)
+                },
+            )
+            .with_fn(
+                "on_changed",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 comp_type: ScriptTypeRegistration,
+                 callback: FnPtr| {
+                    add_change_hook(&ctx, self_, ComponentChange::Changed, comp_type, callback)
+                },
+            )
+            .with_fn(
+                "on_removed",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 comp_type: ScriptTypeRegistration,
+                 callback: FnPtr| {
+                    add_change_hook(&ctx, self_, ComponentChange::Removed, comp_type, callback)
+                },
+            )
+            .with_fn(
+                "remove_change_hooks",
+                |ctx: NativeCallContext, self_: &mut ScriptWorld, callback: FnPtr| {
+                    calling_script_id(&ctx)
+                        .map(|sid| {
+                            self_.remove_change_hooks::<RhaiContext>(sid, callback.fn_name())
+                        })
+                        .unwrap_or_default()
+                },
+            )
+            .with_fn(
+                "observe",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 event_type: ScriptTypeRegistration,
+                 callback: FnPtr| {
+                    add_observer(&ctx, self_, event_type, None, callback)
+                },
+            )
+            .with_fn(
+                "observe_entity",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 entity: Entity,
+                 event_type: ScriptTypeRegistration,
+                 callback: FnPtr| {
+                    add_observer(&ctx, self_, event_type, Some(entity), callback)
+                },
+            )
+            .with_fn(
+                "remove_observers",
+                |ctx: NativeCallContext, self_: &mut ScriptWorld, callback: FnPtr| {
+                    calling_script_id(&ctx)
+                        .map(|sid| self_.remove_observers::<RhaiContext>(sid, callback.fn_name()))
+                        .unwrap_or_default()
+                },
+            )
+            .with_fn(
+                "trigger",
+                |self_: &mut ScriptWorld, event_type: ScriptTypeRegistration| {
+                    trigger_event(self_, event_type, None, Vec::default())
+                },
+            )
+            .with_fn(
+                "trigger",
+                |self_: &mut ScriptWorld, event_type: ScriptTypeRegistration, event: Dynamic| {
+                    trigger_event(self_, event_type, Some(event), Vec::default())
+                },
+            )
+            .with_fn(
+                "trigger",
+                |self_: &mut ScriptWorld,
+                 event_type: ScriptTypeRegistration,
+                 event: Dynamic,
+                 targets: Vec<Dynamic>| {
+                    let targets = targets
+                        .into_iter()
+                        .map(|target| {
+                            let type_name = target.type_name();
+                            target.try_cast::<Entity>().ok_or_else(|| {
+                                Box::new(EvalAltResult::ErrorMismatchDataType(
+                                    "Entity".to_owned(),
+                                    type_name.to_owned(),
+                                    Position::NONE,
+                                ))
+                            })
+                        })
+                        .collect::<Result<_, _>>()?;
+                    trigger_event(self_, event_type, Some(event), targets)
+                },
+            )
+            .with_fn(
+                "send_event",
+                |self_: &mut ScriptWorld, event_type: ScriptTypeRegistration| {
+                    send_event(self_, event_type, None)
+                },
+            )
+            .with_fn(
+                "send_event",
+                |self_: &mut ScriptWorld, event_type: ScriptTypeRegistration, event: Dynamic| {
+                    send_event(self_, event_type, Some(event))
+                },
+            )
+            .with_fn(
+                "read_events",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 event_type: ScriptTypeRegistration| {
+                    let sid = calling_script_id(&ctx).ok_or_else(|| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            "Events can only be read from within a script".into(),
+                            Position::NONE,
+                        ))
+                    })?;
+
+                    self_
+                        .read_events::<RhaiContext>(sid, &event_type)
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })?
+                        .into_iter()
+                        .map(ToDynamic::to_dynamic)
+                        .collect::<Result<Vec<_>, _>>()
+                },
+            )
+            .with_fn("commands", |self_: &mut ScriptWorld| {
+                self_.commands().map_err(|e| {
+                    Box::new(EvalAltResult::ErrorRuntime(
+                        e.to_string().into(),
+                        Position::NONE,
+                    ))
+                })
+            })
+            // functions registered in the `AppFunctionRegistry`, i.e. `world.call_function("my_game::spawn_wave", [3])`
+            .with_fn("call_function", |self_: &mut ScriptWorld, name: &str| {
+                call_function(self_, name, rhai::Array::default())
+            })
+            .with_fn(
+                "call_function",
+                |self_: &mut ScriptWorld, name: &str, args: rhai::Array| {
+                    call_function(self_, name, args)
+                },
+            )
             .with_fn("to_string", |self_: &mut ScriptWorld| self_.to_string())
             .with_fn("to_debug", |self_: &mut ScriptWorld| format!("{:?}", self_))
             .with_fn(
                 "query",
-                |self_: &mut ScriptWorld, component: ScriptTypeRegistration| {
-                    ScriptQueryBuilder::new(self_.clone())
-                        .components(vec![component])
-                        .clone()
+                |ctx: NativeCallContext, self_: &mut ScriptWorld| {
+                    script_query(&ctx, self_, Vec::default())
                 },
             )
             .with_fn(
                 "query",
-                |self_: &mut ScriptWorld, components: Vec<Dynamic>| {
-                    ScriptQueryBuilder::new(self_.clone())
-                        .components(
-                            components
-                                .into_iter()
-                                .map(Dynamic::cast::<ScriptTypeRegistration>)
-                                .collect::<Vec<_>>(),
-                        )
-                        .clone()
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 component: ScriptTypeRegistration| {
+                    script_query(&ctx, self_, vec![component])
+                },
+            )
+            .with_fn(
+                "query",
+                |ctx: NativeCallContext, self_: &mut ScriptWorld, components: Vec<Dynamic>| {
+                    script_query(
+                        &ctx,
+                        self_,
+                        components
+                            .into_iter()
+                            .map(Dynamic::cast::<ScriptTypeRegistration>)
+                            .collect::<Vec<_>>(),
+                    )
+                },
+            )
+            .with_fn(
+                "prepare_query",
+                |ctx: NativeCallContext,
+                 self_: &mut ScriptWorld,
+                 component: ScriptTypeRegistration| {
+                    script_query(&ctx, self_, vec![component])
+                        .prepare()
+                        .map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                },
+            )
+            .with_fn(
+                "prepare_query",
+                |ctx: NativeCallContext, self_: &mut ScriptWorld, components: Vec<Dynamic>| {
+                    script_query(
+                        &ctx,
+                        self_,
+                        components
+                            .into_iter()
+                            .map(Dynamic::cast::<ScriptTypeRegistration>)
+                            .collect::<Vec<_>>(),
+                    )
+                    .prepare()
+                    .map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
                 },
             );
     }
 }
 
+/// Calls the function registered under the given name, functions returning `()` return `()` to the script
+fn call_function(
+    world: &ScriptWorld,
+    name: &str,
+    args: rhai::Array,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let args = args
+        .into_iter()
+        .map(|arg| dynamic_to_script_value(arg, world.clone().into()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    world
+        .call_function(name, args)
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })?
+        .map_or(Ok(Dynamic::UNIT), ToDynamic::to_dynamic)
+}
+
+/// Creates a query builder for the given components, `Changed` and `Added` filters are relative to the calling script's last run
+fn script_query(
+    ctx: &NativeCallContext,
+    world: &ScriptWorld,
+    components: Vec<ScriptTypeRegistration>,
+) -> ScriptQueryBuilder {
+    let mut query = ScriptQueryBuilder::new(world.clone());
+    query.components(components);
+    if let Some(sid) = calling_script_id(ctx) {
+        query.for_script(sid);
+    }
+    query
+}
+
+/// Defines a script type from a map from field names to type names or type registrations
+fn register_script_type(
+    world: &ScriptWorld,
+    name: &str,
+    kind: ScriptTypeKind,
+    fields: rhai::Map,
+) -> Result<ScriptTypeRegistration, Box<EvalAltResult>> {
+    let fields = fields
+        .into_iter()
+        .map(|(field, field_type)| {
+            let field_type = match field_type.clone().try_cast::<ScriptTypeRegistration>() {
+                Some(field_type) => Some(field_type),
+                None => field_type
+                    .into_immutable_string()
+                    .ok()
+                    .and_then(|type_name| world.get_type_by_name(&type_name)),
+            }
+            .ok_or_else(|| {
+                Box::new(EvalAltResult::ErrorRuntime(
+                    format!("Field `{field}` has an unknown type").into(),
+                    Position::NONE,
+                ))
+            })?;
+            Ok((field.to_string(), field_type))
+        })
+        .collect::<Result<Vec<_>, Box<EvalAltResult>>>()?;
+
+    world
+        .register_script_type(name.to_owned(), kind, fields)
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })
+}
+
+impl CustomType for ScriptCommands {
+    fn build(mut builder: rhai::TypeBuilder<Self>) {
+        builder
+            .with_name("Commands")
+            .with_fn("spawn", |self_: &mut ScriptCommands| self_.spawn())
+            .with_fn("despawn", |self_: &mut ScriptCommands, entity: Entity| {
+                self_.despawn(entity)
+            })
+            .with_fn(
+                "despawn_recursive",
+                |self_: &mut ScriptCommands, entity: Entity| self_.despawn_recursive(entity),
+            )
+            .with_fn(
+                "add_default_component",
+                |self_: &mut ScriptCommands, entity: Entity, comp_type: ScriptTypeRegistration| {
+                    self_.add_default_component(entity, comp_type).map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+                },
+            )
+            .with_fn(
+                "remove_component",
+                |self_: &mut ScriptCommands, entity: Entity, comp_type: ScriptTypeRegistration| {
+                    self_.remove_component(entity, comp_type).map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+                },
+            )
+            .with_fn(
+                "remove_resource",
+                |self_: &mut ScriptCommands, res_type: ScriptTypeRegistration| {
+                    self_.remove_resource(res_type).map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+                },
+            )
+            .with_fn(
+                "push_child",
+                |self_: &mut ScriptCommands, parent: Entity, child: Entity| {
+                    self_.push_child(parent, child)
+                },
+            )
+            .with_fn(
+                "remove_children",
+                |self_: &mut ScriptCommands, parent: Entity, children: Vec<Dynamic>| {
+                    self_.remove_children(
+                        parent,
+                        children
+                            .into_iter()
+                            .map(Dynamic::cast::<Entity>)
+                            .collect::<Vec<_>>(),
+                    )
+                },
+            )
+            .with_fn("to_string", |self_: &mut ScriptCommands| self_.to_string())
+            .with_fn("to_debug", |self_: &mut ScriptCommands| {
+                format!("{:?}", self_)
+            });
+    }
+}
+
+fn add_change_hook(
+    ctx: &NativeCallContext,
+    world: &ScriptWorld,
+    change: ComponentChange,
+    component: ScriptTypeRegistration,
+    callback: FnPtr,
+) -> Result<(), Box<EvalAltResult>> {
+    let sid = calling_script_id(ctx).ok_or_else(|| {
+        Box::new(EvalAltResult::ErrorRuntime(
+            "Change hooks can only be added from within a script".into(),
+            Position::NONE,
+        ))
+    })?;
+
+    world
+        .add_change_hook::<RhaiContext>(ScriptChangeHook {
+            sid,
+            change,
+            component,
+            callback: ScriptFunctionRef::new(callback.fn_name().to_owned(), callback),
+        })
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })
+}
+
+fn add_observer(
+    ctx: &NativeCallContext,
+    world: &ScriptWorld,
+    event: ScriptTypeRegistration,
+    entity: Option<Entity>,
+    callback: FnPtr,
+) -> Result<(), Box<EvalAltResult>> {
+    let sid = calling_script_id(ctx).ok_or_else(|| {
+        Box::new(EvalAltResult::ErrorRuntime(
+            "Observers can only be added from within a script".into(),
+            Position::NONE,
+        ))
+    })?;
+
+    world
+        .add_observer::<RhaiContext>(ScriptObserver {
+            sid,
+            event,
+            entity,
+            callback: ScriptFunctionRef::new(callback.fn_name().to_owned(), callback),
+        })
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })
+}
+
+/// Creates a default event of the given type, with the given value assigned to it
+fn new_event(
+    world: &ScriptWorld,
+    event_type: &ScriptTypeRegistration,
+    value: Option<Dynamic>,
+) -> Result<SharedReflect, Box<EvalAltResult>> {
+    let event = world.default_event(event_type).map_err(|e| {
+        Box::new(EvalAltResult::ErrorRuntime(
+            e.to_string().into(),
+            Position::NONE,
+        ))
+    })?;
+
+    if let Some(value) = value {
+        ReflectReference::new_shared_ref(event.clone(), world.clone().into()).apply_rhai(value)?;
+    }
+
+    Ok(event)
+}
+
+fn trigger_event(
+    world: &ScriptWorld,
+    event_type: ScriptTypeRegistration,
+    value: Option<Dynamic>,
+    targets: Vec<Entity>,
+) -> Result<(), Box<EvalAltResult>> {
+    let event = new_event(world, &event_type, value)?;
+    let event = event.read();
+    world
+        .trigger_event(&event_type, event.as_partial_reflect(), &targets)
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })
+}
+
+fn send_event(
+    world: &ScriptWorld,
+    event_type: ScriptTypeRegistration,
+    value: Option<Dynamic>,
+) -> Result<(), Box<EvalAltResult>> {
+    let event = new_event(world, &event_type, value)?;
+    let event = event.read();
+    world
+        .send_event(&event_type, event.as_partial_reflect())
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })
+}
+
+impl<A: FuncArgs + Send + Clone + Sync + 'static> ScriptSystemHost for RhaiScriptHost<A> {
+    fn call_script_functions(world: &mut World, calls: Vec<ScriptFunctionCall>) {
+        let mut contexts: ScriptContexts<RhaiContext> = world.remove_resource().unwrap();
+        let host: Self = world.remove_resource().unwrap();
+        let mut providers: APIProviders<Self> = world.remove_resource().unwrap();
+
+        {
+            // safety:
+            // - we have &mut World access
+            // - we do not use the original reference again anywhere in this block
+            let world = unsafe { WorldPointerGuard::new(world) };
+
+            for call in calls {
+                let Some((entity, Some(ctx), name)) = contexts.context_entities.get_mut(&call.sid)
+                else {
+                    continue;
+                };
+
+                let script_data = ScriptData {
+                    sid: call.sid,
+                    entity: *entity,
+                    name,
+                };
+
+                providers
+                    .setup_runtime_all(world.clone(), &script_data, ctx)
+                    .expect("Failed to setup script runtime");
+
+                let args = match &call.arg {
+                    ScriptSystemArg::Query(query, cache) => Ok(vec![Dynamic::from(
+                        ScriptQueryBuilder::from_query(
+                            ScriptWorld::new(world.clone()),
+                            query.clone(),
+                        )
+                        .with_cache(cache.clone())
+                        .for_script(call.sid)
+                        .clone(),
+                    )]),
+                    ScriptSystemArg::Entity(entity) => Ok(vec![Dynamic::from(*entity)]),
+                    ScriptSystemArg::Event { event, target } => {
+                        ReflectReference::new_shared_ref(event.clone(), world.clone())
+                            .to_dynamic()
+                            .map(|event| vec![event, target.map(Dynamic::from).unwrap_or_default()])
+                    }
+                };
+
+                // function pointers are called by name, with their curried arguments first.
+                // Closures capturing `world` hold on to the world of the call creating them, which is no longer valid
+                let function = match &call.callback {
+                    ScriptCallback::Named(name) => Ok((name.as_str(), Vec::default())),
+                    ScriptCallback::Value(function) => function
+                        .get::<FnPtr>()
+                        .map(|fn_ptr| {
+                            let curry = fn_ptr
+                                .iter_curry()
+                                .map(|value| match value.is::<ScriptWorld>() {
+                                    true => Dynamic::from(ScriptWorld::new(world.clone())),
+                                    false => value.clone(),
+                                })
+                                .collect();
+                            (fn_ptr.fn_name(), curry)
+                        })
+                        .ok_or_else(|| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                format!("Not a Rhai function pointer: {function:?}").into(),
+                                Position::NONE,
+                            ))
+                        }),
+                };
+
+                let result = function.and_then(|(function, curry)| {
+                    let args = args?;
+                    // return values are ignored, same as in Lua
+                    host.engine
+                        .call_fn_with_options::<Dynamic>(
+                            CallFnOptions::new().with_tag(call.sid),
+                            &mut ctx.scope,
+                            &ctx.ast,
+                            function,
+                            curry.into_iter().chain(args).collect::<Vec<_>>(),
+                        )
+                        .map(|_| ())
+                });
+
+                if let Err(e) = result {
+                    report_script_system_error(
+                        &world,
+                        ScriptError::RuntimeError {
+                            script: script_data.name.to_owned(),
+                            msg: e.to_string(),
+                        },
+                    );
+                }
+
+                // same as in `handle_events`, global statements are only ever executed once
+                ctx.ast.clear_statements();
+            }
+        }
+
+        world.insert_resource(contexts);
+        world.insert_resource(host);
+        world.insert_resource(providers);
+        ScriptCommandQueue::apply(world);
+    }
+}
+
 pub struct RhaiBevyAPIProvider;
 
 impl APIProvider for RhaiBevyAPIProvider {
@@ -307,11 +1146,16 @@ impl APIProvider for RhaiBevyAPIProvider {
 
     fn attach_api(&mut self, engine: &mut Self::APITarget) -> Result<(), ScriptError> {
         engine.build_type::<ReflectedValue>();
+        engine.register_iterator_result::<ReflectedValue, Dynamic>();
         engine.build_type::<ScriptTypeRegistration>();
         engine.build_type::<ScriptWorld>();
+        engine.build_type::<ScriptCommands>();
         engine.build_type::<ScriptQueryBuilder>();
         engine.register_iterator::<Vec<rhai::Map>>();
         engine.register_iterator::<ScriptQueryBuilder>();
+        engine.build_type::<ScriptPreparedQuery>();
+        engine.register_type_with_name::<ScriptQueryMaps>("QueryResults");
+        engine.register_iterator::<ScriptQueryMaps>();
         Ok(())
     }
 
@@ -335,7 +1179,9 @@ impl APIProvider for RhaiBevyAPIProvider {
     }
 
     fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
-        None
+        Some(RhaiDocFragment::new("BevyAPI", |engine| {
+            RhaiBevyAPIProvider.attach_api(engine)
+        }))
     }
 
     fn register_with_app(&self, app: &mut bevy::prelude::App) {
@@ -1,13 +1,21 @@
 use ::std::borrow::Cow;
 
 use ::bevy::{
-    prelude::{App, AppTypeRegistry},
-    reflect::{FromType, GetTypeRegistration, Reflect},
+    prelude::{App, AppTypeRegistry, Entity, ReflectDefault},
+    reflect::{
+        FromType, GetTypeRegistration, PartialReflect, Reflect, ReflectKind, TypeRegistration,
+    },
 };
+use bevy_mod_scripting_core::world::WorldPointer;
 #[allow(deprecated)]
-use bevy_mod_scripting_rhai::rhai::{CustomType, Dynamic, EvalAltResult, INT};
+use bevy_mod_scripting_rhai::rhai::{
+    self, CustomType, Dynamic, EvalAltResult, Map, Position, FLOAT, INT,
+};
 
-use crate::{ReflectReference, ReflectedValue, ValueIndex};
+use crate::{
+    common::bevy::ScriptWorld, error::ReflectionError, EntryKey, ReflectReference, ReflectedValue,
+    ScriptValue, ValueIndex,
+};
 
 pub mod bevy;
 pub mod std;
@@ -45,6 +53,32 @@ impl RegisterForeignRhaiType for App {
     }
 }
 
+/// Implemented by proxies generated with `#[derive(RhaiProxy)]`, provides the functions of the proxied type which do not take a receiver.
+pub trait RhaiProxyStatics {
+    /// The name of the static module containing the functions, which is the name of the proxied type
+    const NAME: &'static str;
+
+    /// Returns a module containing the functions, i.e. `Vec3::splat(1.0)`
+    fn statics() -> rhai::Module;
+}
+
+/// A trait allowing the registration of generated proxies with an [`Engine`](rhai::Engine)
+pub trait RegisterRhaiProxy {
+    /// Builds the proxy type and registers its static functions as a module named after the proxied type
+    fn register_rhai_proxy<T: CustomType + RhaiProxyStatics>(&mut self) -> &mut Self;
+}
+
+impl RegisterRhaiProxy for rhai::Engine {
+    fn register_rhai_proxy<T: CustomType + RhaiProxyStatics>(&mut self) -> &mut Self {
+        self.build_type::<T>();
+        let statics = T::statics();
+        if !statics.is_empty() {
+            self.register_static_module(T::NAME, statics.into());
+        }
+        self
+    }
+}
+
 pub trait RhaiProxyable {
     fn ref_to_rhai(self_: ReflectReference) -> Result<Dynamic, Box<EvalAltResult>>;
     fn apply_rhai(self_: &mut ReflectReference, new_val: Dynamic)
@@ -169,6 +203,120 @@ impl ValueIndex<Dynamic> for ReflectReference {
     }
 }
 
+/// Iterates over the values of a reflected container, see [`ReflectReference::entries`].
+/// Register it via `engine.register_iterator_result::<ReflectedValue, Dynamic>()`.
+impl IntoIterator for ReflectedValue {
+    type Item = Result<Dynamic, Box<EvalAltResult>>;
+    type IntoIter = ::std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.ref_.entries() {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|(_, value)| value.to_dynamic())
+                .collect::<Vec<_>>()
+                .into_iter(),
+            Err(e) => vec![Err(e.into())].into_iter(),
+        }
+    }
+}
+
+/// Converts a rhai value into a [`ScriptValue`], which can be converted to any registered type.
+///
+/// Arrays and object maps are converted recursively, values of other types are converted
+/// via [`ApplyRhai`] once the type they need to be converted to is known.
+pub(crate) fn dynamic_to_script_value(
+    value: Dynamic,
+    world: WorldPointer,
+) -> Result<ScriptValue<'static>, Box<EvalAltResult>> {
+    Ok(if value.is_unit() {
+        ScriptValue::Unit
+    } else if value.is::<INT>() {
+        #[allow(clippy::unnecessary_cast)]
+        ScriptValue::Integer(value.as_int().unwrap() as i64)
+    } else if value.is::<FLOAT>() {
+        #[allow(clippy::unnecessary_cast)]
+        ScriptValue::Float(value.as_float().unwrap() as f64)
+    } else if value.is::<bool>() {
+        ScriptValue::Bool(value.as_bool().unwrap())
+    } else if value.is_string() {
+        ScriptValue::String(value.into_string().unwrap())
+    } else if value.is_array() {
+        ScriptValue::List(
+            value
+                .into_array()
+                .unwrap()
+                .into_iter()
+                .map(|v| dynamic_to_script_value(v, world.clone()))
+                .collect::<Result<_, _>>()?,
+        )
+    } else if value.is_map() {
+        ScriptValue::Map(
+            value
+                .cast::<Map>()
+                .into_iter()
+                .map(|(k, v)| {
+                    Ok((
+                        ScriptValue::String(k.to_string()),
+                        dynamic_to_script_value(v, world.clone())?,
+                    ))
+                })
+                .collect::<Result<_, Box<EvalAltResult>>>()?,
+        )
+    } else if value.is::<Entity>() || value.is::<ReflectedValue>() {
+        ScriptValue::Reflect(dynamic_to_reflect(value)?)
+    } else {
+        ScriptValue::Deferred(Box::new(move |registration: &TypeRegistration| {
+            let default = registration.data::<ReflectDefault>().ok_or_else(|| {
+                ReflectionError::Other(format!(
+                    "Cannot convert {} to `{}` since it has no `ReflectDefault` type data",
+                    value.type_name(),
+                    registration.type_info().type_path()
+                ))
+            })?;
+
+            let mut ref_ = ReflectReference::new_owned_ref(default.default(), world);
+            ref_.apply_rhai(value)
+                .map_err(|e| ReflectionError::Other(e.to_string()))?;
+            ref_.get(|v| v.clone_value())
+        }))
+    })
+}
+
+/// Converts a rhai value into a reflected value, used for map keys and map or set entries.
+///
+/// Supports integers, floats, booleans, strings, entities and reflected values.
+pub(crate) fn dynamic_to_reflect(
+    value: Dynamic,
+) -> Result<Box<dyn PartialReflect>, Box<EvalAltResult>> {
+    if value.is::<INT>() {
+        #[allow(clippy::unnecessary_cast)]
+        let v = value.as_int().unwrap() as i64;
+        Ok(Box::new(v))
+    } else if value.is::<FLOAT>() {
+        #[allow(clippy::unnecessary_cast)]
+        let v = value.as_float().unwrap() as f64;
+        Ok(Box::new(v))
+    } else if value.is::<bool>() {
+        Ok(Box::new(value.as_bool().unwrap()))
+    } else if value.is_string() {
+        Ok(Box::new(value.into_string().unwrap()))
+    } else if value.is::<Entity>() {
+        Ok(Box::new(value.cast::<Entity>()))
+    } else if value.is::<ReflectedValue>() {
+        Ok(value
+            .cast::<ReflectedValue>()
+            .ref_
+            .get(|s| s.clone_value())?)
+    } else {
+        Err(Box::new(EvalAltResult::ErrorMismatchDataType(
+            "integer, float, bool, string, Entity or reflected value".to_owned(),
+            value.type_name().to_owned(),
+            Position::NONE,
+        )))
+    }
+}
+
 use bevy_mod_scripting_rhai::rhai::plugin::*;
 
 #[allow(deprecated)]
@@ -176,11 +324,81 @@ impl CustomType for ReflectedValue {
     fn build(mut builder: bevy_mod_scripting_rhai::rhai::TypeBuilder<Self>) {
         builder
             .with_indexer_get_result(|obj: &mut ReflectedValue, index: Dynamic| {
-                obj.ref_.index(index)?.to_dynamic()
+                match obj.ref_.reflect_kind()? {
+                    // missing keys evaluate to `()`
+                    ReflectKind::Map => obj
+                        .ref_
+                        .map_get(dynamic_to_reflect(index)?)?
+                        .map_or(Ok(Dynamic::UNIT), ToDynamic::to_dynamic),
+                    ReflectKind::Set => Ok(obj.ref_.contains(dynamic_to_reflect(index)?)?.into()),
+                    _ => obj.ref_.index(index)?.to_dynamic(),
+                }
             })
             .with_indexer_set_result(|obj: &mut ReflectedValue, index: Dynamic, value: Dynamic| {
-                obj.ref_.index(index)?.apply_rhai(value)
+                match obj.ref_.reflect_kind()? {
+                    // assigning `()` removes the entry, new keys are inserted
+                    ReflectKind::Map => {
+                        let key = dynamic_to_reflect(index)?;
+                        if value.is_unit() {
+                            obj.ref_.map_remove(key)?;
+                        } else if let Some(mut entry) = obj.ref_.map_get(key.clone_value())? {
+                            entry.apply_rhai(value)?;
+                        } else {
+                            obj.ref_.map_insert(key, dynamic_to_reflect(value)?)?;
+                        }
+                        Ok(())
+                    }
+                    // assigning `true` inserts the element, `false` removes it
+                    ReflectKind::Set => {
+                        let element = dynamic_to_reflect(index)?;
+                        if value.as_bool().map_err(|t| {
+                            EvalAltResult::ErrorMismatchDataType(
+                                "bool".to_owned(),
+                                t.to_owned(),
+                                Position::NONE,
+                            )
+                        })? {
+                            obj.ref_.set_insert(element)?;
+                        } else {
+                            obj.ref_.map_remove(element)?;
+                        }
+                        Ok(())
+                    }
+                    _ => obj.ref_.index(index)?.apply_rhai(value),
+                }
             })
+            .with_fn("contains", |obj: &mut ReflectedValue, key: Dynamic| {
+                obj.ref_
+                    .contains(dynamic_to_reflect(key)?)
+                    .map_err::<Box<EvalAltResult>, _>(|e| e.into())
+            })
+            .with_fn("keys", |obj: &mut ReflectedValue| {
+                obj.ref_
+                    .entries()?
+                    .into_iter()
+                    .map(|(key, _)| match key {
+                        EntryKey::Index(i) => Ok(Dynamic::from(i as INT)),
+                        EntryKey::Field(name) => Ok(Dynamic::from(name.into_owned())),
+                        EntryKey::Key(key) => key.to_dynamic(),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .with_fn("len", |obj: &mut ReflectedValue| {
+                obj.ref_
+                    .len()
+                    .map(|len| len as INT)
+                    .map_err::<Box<EvalAltResult>, _>(|e| e.into())
+            })
+            // methods registered in the `AppFunctionRegistry`, i.e. `health.call_method("heal", [5])`
+            .with_fn("call_method", |obj: &mut ReflectedValue, method: &str| {
+                call_reflected_method(obj, method, rhai::Array::default())
+            })
+            .with_fn(
+                "call_method",
+                |obj: &mut ReflectedValue, method: &str, args: rhai::Array| {
+                    call_reflected_method(obj, method, args)
+                },
+            )
             .with_fn("to_debug", |self_: &mut ReflectedValue| {
                 format!("{:?}", self_)
             })
@@ -192,3 +410,26 @@ impl CustomType for ReflectedValue {
             });
     }
 }
+
+/// Calls a method registered in the `AppFunctionRegistry` on the referenced value, see [`ScriptWorld::call_method`]
+fn call_reflected_method(
+    obj: &ReflectedValue,
+    method: &str,
+    args: rhai::Array,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let world = ScriptWorld::new(obj.ref_.world_ptr.clone());
+    let args = args
+        .into_iter()
+        .map(|arg| dynamic_to_script_value(arg, world.clone().into()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    world
+        .call_method(&obj.ref_, method, args)
+        .map_err(|e| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                e.to_string().into(),
+                Position::NONE,
+            ))
+        })?
+        .map_or(Ok(Dynamic::UNIT), ToDynamic::to_dynamic)
+}
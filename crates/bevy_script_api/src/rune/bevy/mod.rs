@@ -0,0 +1,469 @@
+use ::bevy::prelude::Entity;
+use bevy_mod_scripting_core::prelude::*;
+use bevy_mod_scripting_rune::prelude::{
+    rune::{
+        self,
+        alloc::fmt::TryWrite,
+        runtime::{Formatter, Iterator as RuneIterator, Object, Protocol, VmResult},
+        vm_try, vm_write, Context, ContextError, ToValue, Value,
+    },
+    DocumentedModule, RuneDocFragment, RuneScriptContext, RuneScriptData,
+};
+
+use crate::{
+    common::bevy::{
+        ScriptQueryBuilder, ScriptQueryOr, ScriptQueryResult, ScriptTypeRegistration, ScriptWorld,
+    },
+    ReflectReference,
+};
+
+use super::{
+    install_reflect_types, rune_to_script_value, rune_vec_to_any, RuneEntity, ToRuneValue,
+    ToVmResult,
+};
+
+/// The data of the script calling into the bevy API
+fn current_script() -> VmResult<RuneScriptData> {
+    match RuneScriptData::current() {
+        Some(script) => VmResult::Ok(script),
+        None => VmResult::panic("The bevy API can only be used by a script handling an event"),
+    }
+}
+
+fn optional_to_rune(value: Option<ReflectReference>) -> VmResult<Option<Value>> {
+    VmResult::Ok(match value {
+        Some(value) => Some(vm_try!(value.to_rune_value())),
+        None => None,
+    })
+}
+
+fn entities(values: &[Value]) -> VmResult<Vec<Entity>> {
+    VmResult::Ok(
+        vm_try!(rune_vec_to_any::<RuneEntity>(values))
+            .into_iter()
+            .map(|entity| entity.0)
+            .collect(),
+    )
+}
+
+/// Converts a query result into an object from component names to components, missing optional components are set to `()`
+fn query_object(names: &[String], result: ScriptQueryResult) -> VmResult<Value> {
+    let mut object = Object::new();
+    vm_try!(object.insert_value(
+        vm_try!(rune::alloc::String::try_from("Entity")),
+        RuneEntity(result.0)
+    ));
+
+    let components = result.1.into_iter().map(Some).chain(result.2);
+    for (name, component) in names.iter().zip(components) {
+        let value = match component {
+            Some(component) => vm_try!(component.to_rune_value()),
+            None => Value::EmptyTuple,
+        };
+        vm_try!(object.insert(vm_try!(rune::alloc::String::try_from(name.as_str())), value));
+    }
+
+    object.to_value()
+}
+
+/// Iterates over the results of a query as objects from component names to components
+fn query_iter(query: &ScriptQueryBuilder) -> VmResult<RuneIterator> {
+    let names = query.query().component_names();
+    let iter = vm_try!(query.iter().to_vm_result());
+    VmResult::Ok(RuneIterator::from(
+        "QueryBuilder",
+        iter.map(move |result| query_object(&names, result)),
+    ))
+}
+
+/// Installs a method of the query builder adding the given components to one of its lists, returning the builder
+macro_rules! query_filter {
+    ($module:ident, $name:literal, $method:ident, $docs:literal) => {
+        $module.method(
+            $name,
+            &["components"],
+            $docs,
+            |this: &mut ScriptQueryBuilder, components: &[Value]| {
+                this.$method(vm_try!(rune_vec_to_any(components)));
+                VmResult::Ok(this.clone())
+            },
+        )?;
+    };
+}
+
+fn install_query_builder(module: &mut DocumentedModule) -> Result<(), ContextError> {
+    module.ty::<ScriptQueryBuilder>("A query which can be refined before being iterated. Iterating yields objects from component names and `Entity` to the components and entity of each result.")?;
+    // named like the methods of the Rhai query builder, so queries read the same in both languages
+    query_filter!(
+        module,
+        "with_components",
+        with,
+        "Only matches entities with the components."
+    );
+    query_filter!(
+        module,
+        "without_components",
+        without,
+        "Only matches entities without the components."
+    );
+    query_filter!(
+        module,
+        "optional_components",
+        optional,
+        "Adds components to the results which are `()` for entities without them."
+    );
+    query_filter!(
+        module,
+        "changed_components",
+        changed,
+        "Only matches entities whose components changed since the script last ran."
+    );
+    query_filter!(
+        module,
+        "added_components",
+        added,
+        "Only matches entities whose components were added since the script last ran."
+    );
+    // groups are given as objects with the same keys as the builder methods, i.e. `with_components`
+    module.method(
+        "any_of",
+        &["group"],
+        "Matches entities satisfying any filter of the group, keyed like the filter methods e.g. `#{ with_components: [a], added_components: [b] }`.",
+        |this: &mut ScriptQueryBuilder, group: &Object| {
+            let mut or = ScriptQueryOr::default();
+            for (key, components) in group.iter() {
+                let components = vm_try!(vm_try!(components.clone().into_vec()).into_ref());
+                let components = vm_try!(rune_vec_to_any(&components));
+                match key.as_str() {
+                    "with_components" => or.with.extend(components),
+                    "without_components" => or.without.extend(components),
+                    "changed_components" => or.changed.extend(components),
+                    "added_components" => or.added.extend(components),
+                    _ => return VmResult::panic(format!("Unknown filter `{key}`, expected one of `with_components`, `without_components`, `changed_components` or `added_components`")),
+                };
+            }
+            this.or(or);
+            VmResult::Ok(this.clone())
+        },
+    )?;
+    module.method(
+        "iter",
+        &[],
+        "Iterates over the results of the query.",
+        query_iter,
+    )?;
+    module
+        .module_mut()
+        .associated_function(Protocol::INTO_ITER, query_iter)?;
+    Ok(())
+}
+
+fn install_world(module: &mut DocumentedModule) -> Result<(), ContextError> {
+    module
+        .ty::<ScriptWorld>("The bevy world, only valid while the script is handling an event.")?;
+    module.method(
+        "get_type_by_name",
+        &["type_name"],
+        "Retrieves the registration of a type given its short name or full path.",
+        |this: &ScriptWorld, name: &str| this.get_type_by_name(name),
+    )?;
+    module.method(
+        "add_default_component",
+        &["entity", "type"],
+        "Adds the default value of the component to the entity, returning a reference to it.",
+        |this: &ScriptWorld, entity: &RuneEntity, comp_type: &ScriptTypeRegistration| {
+            vm_try!(this
+                .add_default_component(entity.0, comp_type.clone())
+                .to_vm_result())
+            .to_rune_value()
+        },
+    )?;
+    module.method(
+        "insert_component",
+        &["entity", "type", "value"],
+        "Inserts the given value as a component of the given type, returning a reference to it.",
+        |this: &ScriptWorld,
+         entity: &RuneEntity,
+         comp_type: &ScriptTypeRegistration,
+         value: Value| {
+            let value = vm_try!(rune_to_script_value(value));
+            vm_try!(this
+                .insert_component(entity.0, comp_type.clone(), value)
+                .to_vm_result())
+            .to_rune_value()
+        },
+    )?;
+    module.method(
+        "construct",
+        &["type", "value"],
+        "Constructs a value of the given type from a script value.",
+        |this: &ScriptWorld, value_type: &ScriptTypeRegistration, value: Value| {
+            let value = vm_try!(rune_to_script_value(value));
+            vm_try!(this.construct(value_type, value).to_vm_result()).to_rune_value()
+        },
+    )?;
+    module.method(
+        "get_component",
+        &["entity", "type"],
+        "Retrieves a component of the entity, primitive components are copied.",
+        |this: &ScriptWorld, entity: &RuneEntity, comp_type: &ScriptTypeRegistration| {
+            optional_to_rune(vm_try!(this
+                .get_component(entity.0, comp_type.clone())
+                .to_vm_result()))
+        },
+    )?;
+    module.method(
+        "has_component",
+        &["entity", "type"],
+        "Returns true if the entity has the component.",
+        |this: &ScriptWorld, entity: &RuneEntity, comp_type: &ScriptTypeRegistration| {
+            this.has_component(entity.0, comp_type.clone())
+                .to_vm_result()
+        },
+    )?;
+    module.method(
+        "remove_component",
+        &["entity", "type"],
+        "Removes the component from the entity.",
+        |this: &ScriptWorld, entity: &RuneEntity, comp_type: &ScriptTypeRegistration| {
+            this.clone()
+                .remove_component(entity.0, comp_type.clone())
+                .to_vm_result()
+        },
+    )?;
+    module.method(
+        "get_resource",
+        &["type"],
+        "Retrieves a resource, primitive resources are copied.",
+        |this: &ScriptWorld, res_type: &ScriptTypeRegistration| {
+            optional_to_rune(vm_try!(this.get_resource(res_type.clone()).to_vm_result()))
+        },
+    )?;
+    module.method(
+        "has_resource",
+        &["type"],
+        "Returns true if the resource exists.",
+        |this: &ScriptWorld, res_type: &ScriptTypeRegistration| {
+            this.has_resource(res_type.clone()).to_vm_result()
+        },
+    )?;
+    module.method(
+        "insert_resource",
+        &["type", "value"],
+        "Inserts the given value as a resource of the given type, returning a reference to it.",
+        |this: &ScriptWorld, res_type: &ScriptTypeRegistration, value: Value| {
+            let value = vm_try!(rune_to_script_value(value));
+            vm_try!(this.insert_resource(res_type.clone(), value).to_vm_result()).to_rune_value()
+        },
+    )?;
+    module.method(
+        "init_resource",
+        &["type"],
+        "Inserts the default value of the resource unless it already exists, returning a reference to it.",
+        |this: &ScriptWorld, res_type: &ScriptTypeRegistration| {
+            vm_try!(this.init_resource(res_type.clone()).to_vm_result()).to_rune_value()
+        },
+    )?;
+    module.method(
+        "remove_resource",
+        &["type"],
+        "Removes the resource.",
+        |this: &ScriptWorld, res_type: &ScriptTypeRegistration| {
+            this.clone()
+                .remove_resource(res_type.clone())
+                .to_vm_result()
+        },
+    )?;
+    module.method(
+        "get_parent",
+        &["entity"],
+        "Retrieves the parent of the entity.",
+        |this: &ScriptWorld, entity: &RuneEntity| this.get_parent(entity.0).map(RuneEntity),
+    )?;
+    module.method(
+        "get_children",
+        &["parent"],
+        "Retrieves the children of the entity.",
+        |this: &ScriptWorld, parent: &RuneEntity| {
+            this.get_children(parent.0)
+                .into_iter()
+                .map(RuneEntity)
+                .collect::<Vec<_>>()
+        },
+    )?;
+    module.method(
+        "push_child",
+        &["parent", "child"],
+        "Appends the child to the children of the parent.",
+        |this: &ScriptWorld, parent: &RuneEntity, child: &RuneEntity| {
+            this.push_child(parent.0, child.0)
+        },
+    )?;
+    module.method(
+        "remove_children",
+        &["parent", "children"],
+        "Removes the given children from the parent.",
+        |this: &ScriptWorld, parent: &RuneEntity, children: &[Value]| {
+            this.remove_children(parent.0, &vm_try!(entities(children)));
+            VmResult::Ok(())
+        },
+    )?;
+    module.method(
+        "insert_children",
+        &["parent", "index", "children"],
+        "Inserts the children into the children of the parent at the given index.",
+        |this: &ScriptWorld, parent: &RuneEntity, index: usize, children: &[Value]| {
+            this.insert_children(parent.0, index, &vm_try!(entities(children)));
+            VmResult::Ok(())
+        },
+    )?;
+    module.method(
+        "despawn_children_recursive",
+        &["entity"],
+        "Despawns the descendants of the entity.",
+        |this: &ScriptWorld, entity: &RuneEntity| this.despawn_children_recursive(entity.0),
+    )?;
+    module.method(
+        "despawn_recursive",
+        &["entity"],
+        "Despawns the entity and its descendants.",
+        |this: &ScriptWorld, entity: &RuneEntity| this.despawn_recursive(entity.0),
+    )?;
+    module.method(
+        "spawn",
+        &[],
+        "Spawns an empty entity.",
+        |this: &ScriptWorld| RuneEntity(this.write().spawn(()).id()),
+    )?;
+    module.method(
+        "despawn",
+        &["entity"],
+        "Despawns the entity, returning false if it did not exist.",
+        |this: &ScriptWorld, entity: &RuneEntity| this.write().despawn(entity.0),
+    )?;
+    // functions registered in the `AppFunctionRegistry`, i.e. `world.call_function("my_game::spawn_wave", [3])`
+    module.method(
+        "call_function",
+        &["name", "args"],
+        "Calls a function registered in the `AppFunctionRegistry`.",
+        |this: &ScriptWorld, name: &str, args: Value| {
+            let args = match vm_try!(rune_to_script_value(args)) {
+                crate::ScriptValue::List(args) => args,
+                crate::ScriptValue::Unit => Vec::default(),
+                arg => vec![arg],
+            };
+            match vm_try!(this.call_function(name, args).to_vm_result()) {
+                Some(returned) => returned.to_rune_value(),
+                None => VmResult::Ok(Value::EmptyTuple),
+            }
+        },
+    )?;
+    // `Changed` and `Added` filters are relative to the calling script's last run
+    module.method(
+        "query",
+        &["components"],
+        "Starts a query of entities with the given components.",
+        |this: &ScriptWorld, components: &[Value]| {
+            let mut query = ScriptQueryBuilder::new(this.clone());
+            query.components(vm_try!(rune_vec_to_any(components)));
+            query.for_script(vm_try!(current_script()).sid);
+            VmResult::Ok(query)
+        },
+    )?;
+    module.module_mut().associated_function(
+        Protocol::STRING_DISPLAY,
+        |this: &ScriptWorld, f: &mut Formatter| {
+            vm_write!(f, "{this}");
+            VmResult::Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+fn install_type_registration(module: &mut DocumentedModule) -> Result<(), ContextError> {
+    module.ty::<ScriptTypeRegistration>("The registration of a reflected type.")?;
+    module.method(
+        "short_name",
+        &[],
+        "The short name of the type.",
+        |this: &ScriptTypeRegistration| this.short_name().to_owned(),
+    )?;
+    module.method(
+        "type_name",
+        &[],
+        "The full path of the type.",
+        |this: &ScriptTypeRegistration| this.type_name().to_owned(),
+    )?;
+    module.module_mut().associated_function(
+        Protocol::STRING_DISPLAY,
+        |this: &ScriptTypeRegistration, f: &mut Formatter| {
+            vm_write!(f, "{this}");
+            VmResult::Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+/// Creates the `bevy` module, containing the world API and functions returning the world and the calling script's data:
+///
+/// ```rune
+/// use bevy::{entity, world};
+///
+/// pub fn on_update() {
+///     let world = world();
+///     let health = world.get_component(entity(), world.get_type_by_name("Health")?)?;
+///     health["value"] = health["value"] - 1;
+/// }
+/// ```
+pub fn bevy_module() -> Result<DocumentedModule, ContextError> {
+    let mut module = DocumentedModule::with_crate(
+        "bevy",
+        "Access to the bevy world and the data of the calling script.",
+    )?;
+
+    module.function(
+        "world",
+        &[],
+        "Returns the world the calling script runs in.",
+        || VmResult::Ok(ScriptWorld::new(vm_try!(current_script()).world)),
+    )?;
+    module.function(
+        "entity",
+        &[],
+        "Returns the entity the calling script is attached to.",
+        || VmResult::Ok(RuneEntity(vm_try!(current_script()).entity)),
+    )?;
+    module.function(
+        "script_id",
+        &[],
+        "Returns the id of the calling script.",
+        || VmResult::Ok(vm_try!(current_script()).sid),
+    )?;
+
+    install_world(&mut module)?;
+    install_query_builder(&mut module)?;
+    install_type_registration(&mut module)?;
+    install_reflect_types(&mut module)?;
+
+    Ok(module)
+}
+
+/// Provides the `bevy` module to Rune scripts, see [`bevy_module`]
+pub struct RuneBevyAPIProvider;
+
+impl APIProvider for RuneBevyAPIProvider {
+    type APITarget = Context;
+    type ScriptContext = RuneScriptContext;
+    type DocTarget = RuneDocFragment;
+
+    fn attach_api(&mut self, ctx: &mut Self::APITarget) -> Result<(), ScriptError> {
+        ctx.install(bevy_module().map_err(ScriptError::new_other)?.into_module())
+            .map_err(ScriptError::new_other)
+    }
+
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        // failures to build the module are reported by `attach_api`
+        let module = bevy_module().ok()?;
+        Some(RuneDocFragment::new("BevyAPI").with_module(&module))
+    }
+}
@@ -0,0 +1,396 @@
+use ::std::{borrow::Cow, fmt::Display};
+
+use ::bevy::{
+    prelude::{AppTypeRegistry, Entity},
+    reflect::{PartialReflect, ReflectKind},
+};
+use bevy_mod_scripting_rune::prelude::{
+    rune::{
+        alloc::fmt::TryWrite,
+        runtime::{Formatter, Iterator as RuneIterator, Protocol, VmResult},
+        vm_try, vm_write, Any, ContextError, ToValue, Value,
+    },
+    DocumentedModule,
+};
+
+use crate::{common::bevy::ScriptWorld, EntryKey, ReflectReference, ScriptValue, ValueIndex};
+
+pub mod bevy;
+
+/// An entity, bevy's `Entity` cannot be used as a rune type directly
+#[derive(Any, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[rune(module = ::bevy_mod_scripting_rune::prelude::rune, item = ::bevy, name = Entity)]
+pub struct RuneEntity(pub Entity);
+
+/// Converts errors of the bevy API into rune errors, which abort the running script
+pub(crate) trait ToVmResult<T> {
+    fn to_vm_result(self) -> VmResult<T>;
+}
+
+impl<T, E: Display> ToVmResult<T> for Result<T, E> {
+    fn to_vm_result(self) -> VmResult<T> {
+        match self {
+            Ok(v) => VmResult::Ok(v),
+            Err(e) => VmResult::panic(e.to_string()),
+        }
+    }
+}
+
+pub trait ToRuneValue {
+    fn to_rune_value(self) -> VmResult<Value>;
+}
+
+impl ToRuneValue for ReflectReference {
+    /// Numbers, booleans, strings and entities are copied into rune values,
+    /// values of any other type are returned as references.
+    fn to_rune_value(self) -> VmResult<Value> {
+        match vm_try!(self
+            .get(|s| reflect_to_rune(s.as_partial_reflect()))
+            .to_vm_result())
+        {
+            Some(value) => VmResult::Ok(vm_try!(value)),
+            None => self.to_value(),
+        }
+    }
+}
+
+/// Converts the given value to a rune value if it is a primitive rune has its own representation for
+fn reflect_to_rune(value: &dyn PartialReflect) -> Option<VmResult<Value>> {
+    macro_rules! convert {
+        (integers: $($int:ty),*; floats: $($float:ty),*) => {
+            $(
+                if let Some(v) = value.try_downcast_ref::<$int>() {
+                    return Some(i64::try_from(*v).map(Value::Integer).to_vm_result());
+                }
+            )*
+            $(
+                if let Some(v) = value.try_downcast_ref::<$float>() {
+                    return Some(VmResult::Ok(Value::Float(*v as f64)));
+                }
+            )*
+        };
+    }
+
+    convert!(
+        integers: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+        floats: f32, f64
+    );
+
+    if let Some(v) = value.try_downcast_ref::<bool>() {
+        Some(VmResult::Ok(Value::Bool(*v)))
+    } else if let Some(v) = value.try_downcast_ref::<String>() {
+        Some(v.as_str().to_value())
+    } else {
+        value
+            .try_downcast_ref::<Entity>()
+            .map(|v| RuneEntity(*v).to_value())
+    }
+}
+
+/// Converts a rune value into a [`ScriptValue`], which can be converted to any registered type.
+///
+/// Vectors, tuples and objects are converted recursively, `None` converts to [`ScriptValue::Unit`].
+pub(crate) fn rune_to_script_value(value: Value) -> VmResult<ScriptValue<'static>> {
+    VmResult::Ok(match value {
+        Value::EmptyTuple => ScriptValue::Unit,
+        Value::Bool(b) => ScriptValue::Bool(b),
+        Value::Byte(b) => ScriptValue::Integer(b.into()),
+        Value::Integer(i) => ScriptValue::Integer(i),
+        Value::Float(f) => ScriptValue::Float(f),
+        Value::Char(c) => ScriptValue::String(c.to_string()),
+        Value::String(s) => ScriptValue::String(vm_try!(s.borrow_ref()).to_string()),
+        Value::Vec(vec) => ScriptValue::List(vm_try!(rune_list_to_script_values(&vm_try!(
+            vec.borrow_ref()
+        )))),
+        Value::Tuple(tuple) => ScriptValue::List(vm_try!(rune_list_to_script_values(&vm_try!(
+            tuple.borrow_ref()
+        )))),
+        Value::Object(object) => {
+            let mut entries = Vec::new();
+            for (k, v) in vm_try!(object.borrow_ref()).iter() {
+                entries.push((
+                    ScriptValue::String(k.to_string()),
+                    vm_try!(rune_to_script_value(v.clone())),
+                ));
+            }
+            ScriptValue::Map(entries)
+        }
+        Value::Option(option) => match vm_try!(option.borrow_ref()).clone() {
+            Some(v) => vm_try!(rune_to_script_value(v)),
+            None => ScriptValue::Unit,
+        },
+        Value::Any(_) => ScriptValue::Reflect(vm_try!(rune_to_reflect(value))),
+        value => {
+            return VmResult::panic(format!(
+                "Cannot convert `{}` to a rust value",
+                vm_try!(value.type_info())
+            ))
+        }
+    })
+}
+
+fn rune_list_to_script_values(values: &[Value]) -> VmResult<Vec<ScriptValue<'static>>> {
+    let mut list = Vec::with_capacity(values.len());
+    for v in values {
+        list.push(vm_try!(rune_to_script_value(v.clone())));
+    }
+    VmResult::Ok(list)
+}
+
+/// Converts a rune value into a reflected value, used for map keys and map or set entries.
+///
+/// Supports integers, floats, booleans, strings, entities and reflected values.
+pub(crate) fn rune_to_reflect(value: Value) -> VmResult<Box<dyn PartialReflect>> {
+    VmResult::Ok(match value {
+        Value::Bool(b) => Box::new(b),
+        Value::Byte(b) => Box::new(i64::from(b)),
+        Value::Integer(i) => Box::new(i),
+        Value::Float(f) => Box::new(f),
+        Value::String(s) => Box::new(vm_try!(s.borrow_ref()).to_string()),
+        Value::Any(any) => {
+            let any = vm_try!(any.borrow_ref());
+            if let Some(entity) = any.downcast_borrow_ref::<RuneEntity>() {
+                Box::new(entity.0)
+            } else if let Some(ref_) = any.downcast_borrow_ref::<ReflectReference>() {
+                vm_try!(ref_.get(|s| s.clone_value()).to_vm_result())
+            } else {
+                return VmResult::panic(format!(
+                    "Cannot convert `{}` to a rust value",
+                    any.type_name()
+                ));
+            }
+        }
+        value => {
+            return VmResult::panic(format!(
+                "Expected integer, float, bool, string, Entity or reflected value, got `{}`",
+                vm_try!(value.type_info())
+            ))
+        }
+    })
+}
+
+/// Clones the values of the given rune vector, which need to be of the given type
+pub(crate) fn rune_vec_to_any<T: Any + Clone>(vec: &[Value]) -> VmResult<Vec<T>> {
+    let mut values = Vec::with_capacity(vec.len());
+    for v in vec {
+        let any = vm_try!(v.clone().into_any());
+        values.push(vm_try!(any.downcast_borrow_ref::<T>()).clone());
+    }
+    VmResult::Ok(values)
+}
+
+pub trait ApplyRune {
+    fn apply_rune(&mut self, value: Value) -> VmResult<()>;
+}
+
+impl ApplyRune for ReflectReference {
+    /// Converts the value to the type of the referenced value, see [`ScriptValue::into_reflect`], and applies it
+    fn apply_rune(&mut self, value: Value) -> VmResult<()> {
+        let value = vm_try!(rune_to_script_value(value));
+
+        let type_id = vm_try!(self.get(|s| s.type_id()).to_vm_result());
+        let registry = self.world_ptr.read().resource::<AppTypeRegistry>().clone();
+        let value = {
+            let registry = registry.read();
+            let Some(registration) = registry.get(type_id) else {
+                return VmResult::panic(format!(
+                    "Cannot assign to `{}`, its type is not registered",
+                    self.path
+                ));
+            };
+            vm_try!(value.into_reflect(registration, &registry).to_vm_result())
+        };
+
+        vm_try!(vm_try!(self
+            .get_mut(|s| s.try_apply(value.as_partial_reflect()))
+            .to_vm_result())
+        .to_vm_result());
+        VmResult::Ok(())
+    }
+}
+
+impl ValueIndex<Value> for ReflectReference {
+    type Output = VmResult<Self>;
+
+    fn index(&self, index: Value) -> Self::Output {
+        match index {
+            Value::Integer(i) => {
+                VmResult::Ok(self.index(vm_try!(usize::try_from(i).to_vm_result())))
+            }
+            Value::String(s) => {
+                VmResult::Ok(self.index(Cow::Owned(vm_try!(s.borrow_ref()).to_string())))
+            }
+            index => VmResult::panic(format!(
+                "Expected integer or string index, got `{}`",
+                vm_try!(index.type_info())
+            )),
+        }
+    }
+}
+
+/// Missing map keys evaluate to `()`, indexing a set returns whether it contains the element
+fn index_get(this: &ReflectReference, index: Value) -> VmResult<Value> {
+    match vm_try!(this.reflect_kind().to_vm_result()) {
+        ReflectKind::Map => {
+            match vm_try!(this.map_get(vm_try!(rune_to_reflect(index))).to_vm_result()) {
+                Some(entry) => entry.to_rune_value(),
+                None => VmResult::Ok(Value::EmptyTuple),
+            }
+        }
+        ReflectKind::Set => VmResult::Ok(Value::Bool(vm_try!(this
+            .contains(vm_try!(rune_to_reflect(index)))
+            .to_vm_result()))),
+        _ => vm_try!(this.index(index)).to_rune_value(),
+    }
+}
+
+/// Assigning `()` removes a map entry and new keys are inserted,
+/// assigning `true` inserts a set element and `false` removes it
+fn index_set(this: &mut ReflectReference, index: Value, value: Value) -> VmResult<()> {
+    match vm_try!(this.reflect_kind().to_vm_result()) {
+        ReflectKind::Map => {
+            let key = vm_try!(rune_to_reflect(index));
+            if matches!(value, Value::EmptyTuple) {
+                vm_try!(this.map_remove(key).to_vm_result());
+            } else if let Some(mut entry) = vm_try!(this.map_get(key.clone_value()).to_vm_result())
+            {
+                vm_try!(entry.apply_rune(value));
+            } else {
+                vm_try!(this
+                    .map_insert(key, vm_try!(rune_to_reflect(value)))
+                    .to_vm_result());
+            }
+        }
+        ReflectKind::Set => {
+            let element = vm_try!(rune_to_reflect(index));
+            if vm_try!(value.as_bool()) {
+                vm_try!(this.set_insert(element).to_vm_result());
+            } else {
+                vm_try!(this.map_remove(element).to_vm_result());
+            }
+        }
+        _ => vm_try!(vm_try!(this.index(index)).apply_rune(value)),
+    }
+    VmResult::Ok(())
+}
+
+/// Iterates over the values of a reflected container, see [`ReflectReference::entries`]
+fn reflected_values(this: &ReflectReference) -> VmResult<RuneIterator> {
+    let entries = vm_try!(this.entries().to_vm_result());
+    VmResult::Ok(RuneIterator::from(
+        "ReflectReference",
+        entries.into_iter().map(|(_, value)| value.to_rune_value()),
+    ))
+}
+
+fn reflected_keys(this: &ReflectReference) -> VmResult<Vec<Value>> {
+    let mut keys = Vec::new();
+    for (key, _) in vm_try!(this.entries().to_vm_result()) {
+        keys.push(match key {
+            EntryKey::Index(i) => Value::Integer(vm_try!(i64::try_from(i).to_vm_result())),
+            EntryKey::Field(name) => vm_try!(name.as_ref().to_value()),
+            EntryKey::Key(key) => vm_try!(key.to_rune_value()),
+        });
+    }
+    VmResult::Ok(keys)
+}
+
+/// Calls a method registered in the `AppFunctionRegistry` on the referenced value, see [`ScriptWorld::call_method`]
+fn call_reflected_method(this: &ReflectReference, method: &str, args: Value) -> VmResult<Value> {
+    let world = ScriptWorld::new(this.world_ptr.clone());
+    let args = match vm_try!(rune_to_script_value(args)) {
+        ScriptValue::List(args) => args,
+        ScriptValue::Unit => Vec::default(),
+        arg => vec![arg],
+    };
+
+    match vm_try!(world.call_method(this, method, args).to_vm_result()) {
+        Some(returned) => returned.to_rune_value(),
+        None => VmResult::Ok(Value::EmptyTuple),
+    }
+}
+
+/// Installs the [`ReflectReference`] and [`RuneEntity`] types into the given module
+pub(crate) fn install_reflect_types(module: &mut DocumentedModule) -> Result<(), ContextError> {
+    module.ty::<ReflectReference>("A reference to a reflected value, its fields, elements and entries are accessed by indexing e.g. `transform[\"translation\"][\"x\"]`.")?;
+    module.method(
+        "keys",
+        &[],
+        "The keys of a map or the field names of a struct.",
+        reflected_keys,
+    )?;
+    module.method(
+        "contains",
+        &["key"],
+        "Returns true if the map or set contains the key.",
+        |this: &ReflectReference, key: Value| {
+            this.contains(vm_try!(rune_to_reflect(key))).to_vm_result()
+        },
+    )?;
+    module.method(
+        "len",
+        &[],
+        "The number of elements or entries.",
+        |this: &ReflectReference| this.len().to_vm_result(),
+    )?;
+    // methods registered in the `AppFunctionRegistry`, i.e. `health.call_method("heal", [5])`
+    module.method(
+        "call_method",
+        &["method", "args"],
+        "Calls a method of the referenced type registered in the `AppFunctionRegistry`.",
+        call_reflected_method,
+    )?;
+    let protocols = module.module_mut();
+    protocols.associated_function(Protocol::INDEX_GET, index_get)?;
+    protocols.associated_function(Protocol::INDEX_SET, index_set)?;
+    protocols.associated_function(Protocol::INTO_ITER, reflected_values)?;
+    protocols.associated_function(
+        Protocol::STRING_DEBUG,
+        |this: &ReflectReference, f: &mut Formatter| {
+            let value = vm_try!(this.get(|s| format!("{s:?}")).to_vm_result());
+            vm_write!(f, "{value}");
+            VmResult::Ok(())
+        },
+    )?;
+
+    module.ty::<RuneEntity>("An entity id.")?;
+    module.method(
+        "index",
+        &[],
+        "The index of the entity.",
+        |this: &RuneEntity| this.0.index(),
+    )?;
+    module.method(
+        "generation",
+        &[],
+        "The generation of the entity.",
+        |this: &RuneEntity| this.0.generation(),
+    )?;
+    module.method(
+        "to_bits",
+        &[],
+        "The entity id as a single integer.",
+        |this: &RuneEntity| this.0.to_bits(),
+    )?;
+    let protocols = module.module_mut();
+    protocols.associated_function(
+        Protocol::PARTIAL_EQ,
+        |this: &RuneEntity, other: &RuneEntity| this == other,
+    )?;
+    protocols.associated_function(
+        Protocol::STRING_DISPLAY,
+        |this: &RuneEntity, f: &mut Formatter| {
+            vm_write!(f, "{}", this.0);
+            VmResult::Ok(())
+        },
+    )?;
+    protocols.associated_function(
+        Protocol::STRING_DEBUG,
+        |this: &RuneEntity, f: &mut Formatter| {
+            vm_write!(f, "{:?}", this.0);
+            VmResult::Ok(())
+        },
+    )?;
+
+    Ok(())
+}
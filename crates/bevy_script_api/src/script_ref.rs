@@ -1,12 +1,24 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    reflect::{
+        PartialReflect, ReflectFromReflect, ReflectKind, ReflectMut, ReflectRef, TypeInfo,
+        VariantType,
+    },
+};
 use parking_lot::RwLock;
+use std::any::TypeId;
 use std::fmt::Debug;
-use std::{borrow::Cow, sync::Weak};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Weak},
+};
 
 use bevy_mod_scripting_core::world::WorldPointer;
 
 use crate::{
+    common::bevy::{events::SharedReflect, script_types::ScriptTypeDescriptor},
     error::ReflectionError,
+    script_value::cast_number,
     sub_reflect::{ReflectBase, ReflectionPath, ReflectionPathElement},
 };
 
@@ -18,6 +30,11 @@ use crate::{
 /// Automatically converts to the most convenient lua representation.
 /// See [`ReflectReference::to_lua`]
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "rune", derive(bevy_mod_scripting_rune::prelude::rune::Any))]
+#[cfg_attr(
+    feature = "rune",
+    rune(module = ::bevy_mod_scripting_rune::prelude::rune, item = ::bevy, name = ReflectReference)
+)]
 pub struct ReflectReference {
     /// The reflection path from the root
     pub(crate) path: ReflectionPath,
@@ -50,6 +67,32 @@ impl ReflectReference {
         }
     }
 
+    /// Creates a reference to a component of a type defined by a script
+    pub fn new_script_component_ref(
+        script_type: Arc<ScriptTypeDescriptor>,
+        entity: Entity,
+        world_ptr: WorldPointer,
+    ) -> Self {
+        Self {
+            path: ReflectionPath::new(ReflectBase::ScriptComponent {
+                script_type,
+                entity,
+            }),
+            world_ptr,
+        }
+    }
+
+    /// Creates a reference to a resource of a type defined by a script
+    pub fn new_script_resource_ref(
+        script_type: Arc<ScriptTypeDescriptor>,
+        world_ptr: WorldPointer,
+    ) -> Self {
+        Self {
+            path: ReflectionPath::new(ReflectBase::ScriptResource { script_type }),
+            world_ptr,
+        }
+    }
+
     /// Creates a reference to a script owned value
     pub fn new_script_ref<T: Reflect>(ptr: Weak<RwLock<T>>, world_ptr: WorldPointer) -> Self {
         Self {
@@ -58,6 +101,19 @@ impl ReflectReference {
         }
     }
 
+    /// Creates a reference owning the given value, the value lives as long as any reference to it
+    pub fn new_owned_ref(value: Box<dyn Reflect>, world_ptr: WorldPointer) -> Self {
+        Self::new_shared_ref(Arc::new(RwLock::new(value)), world_ptr)
+    }
+
+    /// Creates a reference sharing ownership of the given value, the value lives as long as any reference to it
+    pub fn new_shared_ref(value: SharedReflect, world_ptr: WorldPointer) -> Self {
+        Self {
+            path: ReflectionPath::new(ReflectBase::Owned { val: value }),
+            world_ptr,
+        }
+    }
+
     /// Creates a new script reference which points to a sub component of the original data,
     /// This also updates the pointer
     pub(crate) fn sub_ref(&self, elem: ReflectionPathElement) -> ReflectReference {
@@ -146,6 +202,251 @@ impl ReflectReference {
     {
         self.get_mut_typed(|s| *s = other)
     }
+
+    /// Returns the kind of the referenced value, i.e. whether it's a struct, a map etc.
+    pub fn reflect_kind(&self) -> Result<ReflectKind, ReflectionError> {
+        self.get(|s| s.reflect_kind())
+    }
+
+    /// Retrieves a reference to the value stored under the given key if this is a Map, or to the given element if this is a Set.
+    /// Returns `None` if no such entry exists.
+    ///
+    /// The key is converted to the key type of the container first, see [`Self::convert_value`].
+    pub fn map_get(&self, key: Box<dyn PartialReflect>) -> Result<Option<Self>, ReflectionError> {
+        let key = self.convert_value(key, self.key_type()?)?;
+
+        let contains = self.get(|s| match s.reflect_ref() {
+            ReflectRef::Map(m) => m.get(key.as_ref()).is_some(),
+            ReflectRef::Set(s) => s.contains(key.as_ref()),
+            _ => false,
+        })?;
+
+        Ok(contains.then(|| self.sub_ref(ReflectionPathElement::MapKeyAccess(Arc::from(key)))))
+    }
+
+    /// Returns true if the referenced Map contains the given key, or if the referenced Set contains the given element
+    pub fn contains(&self, key: Box<dyn PartialReflect>) -> Result<bool, ReflectionError> {
+        self.map_get(key).map(|entry| entry.is_some())
+    }
+
+    /// Returns the number of entries of the referenced container, see [`Self::entries`]
+    pub fn len(&self) -> Result<usize, ReflectionError> {
+        self.get(|s| match s.reflect_ref() {
+            ReflectRef::Struct(s) => s.field_len(),
+            ReflectRef::TupleStruct(s) => s.field_len(),
+            ReflectRef::Tuple(s) => s.field_len(),
+            ReflectRef::List(s) => s.len(),
+            ReflectRef::Array(s) => s.len(),
+            ReflectRef::Map(s) => s.len(),
+            ReflectRef::Set(s) => s.len(),
+            ReflectRef::Enum(s) => s.field_len(),
+            _ => 0,
+        })
+    }
+
+    /// Returns true if the referenced container has no entries
+    pub fn is_empty(&self) -> Result<bool, ReflectionError> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Retrieves the entries of the referenced container in iteration order, i.e. their keys along with references to their values.
+    ///
+    /// Any reflected container can be walked this way:
+    /// - Lists, Arrays, Tuples and TupleStructs yield their elements by index
+    /// - Structs yield their fields by name
+    /// - Enums yield the fields of the active variant, by name for struct variants and by index for tuple variants
+    /// - Maps yield their values by key, Sets yield their elements keyed by the elements themselves
+    ///
+    /// Other values have no entries.
+    pub fn entries(&self) -> Result<Vec<(EntryKey, Self)>, ReflectionError> {
+        let keys = self.get(|s| match s.reflect_ref() {
+            ReflectRef::Struct(s) => (0..s.field_len())
+                .filter_map(|i| s.name_at(i))
+                .map(|name| EntryKey::Field(name.to_owned().into()))
+                .collect(),
+            ReflectRef::Enum(e) if e.variant_type() == VariantType::Struct => (0..e.field_len())
+                .filter_map(|i| e.name_at(i))
+                .map(|name| EntryKey::Field(name.to_owned().into()))
+                .collect(),
+            ReflectRef::TupleStruct(s) => (0..s.field_len()).map(EntryKey::Index).collect(),
+            ReflectRef::Tuple(s) => (0..s.field_len()).map(EntryKey::Index).collect(),
+            ReflectRef::List(s) => (0..s.len()).map(EntryKey::Index).collect(),
+            ReflectRef::Array(s) => (0..s.len()).map(EntryKey::Index).collect(),
+            ReflectRef::Enum(e) => (0..e.field_len()).map(EntryKey::Index).collect(),
+            _ => Vec::default(),
+        })?;
+
+        if !keys.is_empty() {
+            return Ok(keys
+                .into_iter()
+                .map(|key| {
+                    let value = match &key {
+                        EntryKey::Index(i) => self.sub_ref(ReflectionPathElement::IndexAccess(*i)),
+                        EntryKey::Field(name) => {
+                            self.sub_ref(ReflectionPathElement::FieldAccess(name.clone()))
+                        }
+                        EntryKey::Key(key) => key.clone(),
+                    };
+                    (key, value)
+                })
+                .collect());
+        }
+
+        match self.reflect_kind()? {
+            ReflectKind::Map => self
+                .map_keys()?
+                .into_iter()
+                .filter_map(|key| {
+                    key.get(|k| k.clone_value())
+                        .and_then(|k| self.map_get(k))
+                        .transpose()
+                        .map(|value| value.map(|value| (EntryKey::Key(key), value)))
+                })
+                .collect(),
+            ReflectKind::Set => Ok(self
+                .map_keys()?
+                .into_iter()
+                .map(|element| (EntryKey::Key(element.clone()), element))
+                .collect()),
+            _ => Ok(Vec::default()),
+        }
+    }
+
+    /// Retrieves references to the keys of the referenced Map, or to the elements of the referenced Set, in iteration order.
+    ///
+    /// The references are positional, modifying the container invalidates them.
+    pub fn map_keys(&self) -> Result<Vec<Self>, ReflectionError> {
+        let len = self.get(|s| match s.reflect_ref() {
+            ReflectRef::Map(m) => Ok(m.len()),
+            ReflectRef::Set(s) => Ok(s.len()),
+            _ => Err(self.not_a_map()),
+        })??;
+
+        Ok((0..len)
+            .map(|i| self.sub_ref(ReflectionPathElement::EntryKeyAccess(i)))
+            .collect())
+    }
+
+    /// Inserts the given entry into the referenced Map, replacing any existing value.
+    /// The key and value are converted to the types stored in the map first, see [`Self::convert_value`].
+    pub fn map_insert(
+        &mut self,
+        key: Box<dyn PartialReflect>,
+        value: Box<dyn PartialReflect>,
+    ) -> Result<(), ReflectionError> {
+        let value_type = self.get(|s| match s.reflect_ref() {
+            ReflectRef::Map(m) => Ok(m.get_represented_map_info().map(|i| i.value_ty().id())),
+            _ => Err(self.not_a_map()),
+        })??;
+        let key = self.convert_value(key, self.key_type()?)?;
+        let value = self.convert_value(value, value_type)?;
+
+        self.get_mut(|s| {
+            if let ReflectMut::Map(m) = s.reflect_mut() {
+                m.insert_boxed(key, value);
+            }
+        })
+    }
+
+    /// Inserts the given element into the referenced Set, returns false if it was already present.
+    /// The element is converted to the type stored in the set first, see [`Self::convert_value`].
+    pub fn set_insert(&mut self, value: Box<dyn PartialReflect>) -> Result<bool, ReflectionError> {
+        if self.reflect_kind()? != ReflectKind::Set {
+            return Err(self.not_a_map());
+        }
+        let value = self.convert_value(value, self.key_type()?)?;
+
+        self.get_mut(|s| match s.reflect_mut() {
+            ReflectMut::Set(s) => s.insert_boxed(value),
+            _ => false,
+        })
+    }
+
+    /// Removes the entry with the given key from the referenced Map, or the given element from the referenced Set.
+    /// Returns true if it existed.
+    pub fn map_remove(&mut self, key: Box<dyn PartialReflect>) -> Result<bool, ReflectionError> {
+        let key = self.convert_value(key, self.key_type()?)?;
+
+        self.get_mut(|s| match s.reflect_mut() {
+            ReflectMut::Map(m) => m.remove(key.as_ref()).is_some(),
+            ReflectMut::Set(s) => s.remove(key.as_ref()),
+            _ => false,
+        })
+    }
+
+    /// Converts a value coming from a script to the type with the given id, used for map keys and values.
+    ///
+    /// Integers (`i64`) and floats (`f64`) are cast to the target number type,
+    /// values of other types are converted via the [`ReflectFromReflect`] type data of the target type.
+    /// If the target type is not known the value is returned as is.
+    pub fn convert_value(
+        &self,
+        value: Box<dyn PartialReflect>,
+        type_id: Option<TypeId>,
+    ) -> Result<Box<dyn PartialReflect>, ReflectionError> {
+        let Some(type_id) = type_id else {
+            return Ok(value);
+        };
+
+        if value
+            .try_as_reflect()
+            .is_some_and(|v| v.type_id() == type_id)
+        {
+            return Ok(value);
+        }
+
+        if let Some(cast) = cast_number(value.as_ref(), type_id) {
+            return cast;
+        }
+
+        let world = self.world_ptr.read();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let registration = registry.get(type_id);
+
+        registration
+            .and_then(|r| r.data::<ReflectFromReflect>())
+            .and_then(|from_reflect| from_reflect.from_reflect(value.as_ref()))
+            .map(|v| v.into_partial_reflect())
+            .ok_or_else(|| {
+                ReflectionError::Other(format!(
+                    "Cannot convert `{}` to `{}`",
+                    value.reflect_type_path(),
+                    registration
+                        .map(|r| r.type_info().type_path())
+                        .unwrap_or("unregistered type")
+                ))
+            })
+    }
+
+    /// Returns the key type of the referenced Map, or the element type of the referenced Set, if known
+    fn key_type(&self) -> Result<Option<TypeId>, ReflectionError> {
+        self.get(|s| match s.reflect_ref() {
+            ReflectRef::Map(m) => Ok(m.get_represented_map_info().map(|i| i.key_ty().id())),
+            ReflectRef::Set(s) => Ok(match s.get_represented_type_info() {
+                Some(TypeInfo::Set(i)) => Some(i.value_ty().id()),
+                _ => None,
+            }),
+            _ => Err(self.not_a_map()),
+        })?
+    }
+
+    fn not_a_map(&self) -> ReflectionError {
+        ReflectionError::InvalidReflectionPath {
+            path: self.path.to_string(),
+            msg: "Not a map or set".to_owned(),
+        }
+    }
+}
+
+/// The key of an entry of a reflected container, see [`ReflectReference::entries`]
+#[derive(Clone, Debug)]
+pub enum EntryKey {
+    /// The position of a List, Array, Tuple or TupleStruct element, or of a tuple variant field
+    Index(usize),
+    /// The name of a Struct field, or of a struct variant field
+    Field(Cow<'static, str>),
+    /// A reference to the key of a Map entry, or to a Set element
+    Key(ReflectReference),
 }
 
 /// A version of index for returning values instead of references
@@ -0,0 +1,674 @@
+use std::any::TypeId;
+
+use bevy::prelude::ReflectDefault;
+use bevy::reflect::{
+    DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, EnumInfo, NamedField, PartialReflect, Reflect,
+    ReflectFromReflect, Type, TypeInfo, TypePathTable, TypeRegistration, TypeRegistry,
+    UnnamedField, VariantInfo,
+};
+
+use crate::error::ReflectionError;
+
+/// A function converting a script value once the type it needs to be converted to is known
+pub type DeferredConversion<'a> =
+    Box<dyn FnOnce(&TypeRegistration) -> Result<Box<dyn PartialReflect>, ReflectionError> + 'a>;
+
+/// A language independent representation of a value coming from a script, e.g. a lua table or a rhai object map.
+///
+/// Can be converted to a value of any registered type via [`ScriptValue::into_reflect`].
+pub enum ScriptValue<'a> {
+    /// `nil` or `()`, converts to `None` if the target type is an `Option`
+    Unit,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    /// A sequence, converts to lists, arrays, sets, tuples, tuple structs and tuple variants
+    List(Vec<ScriptValue<'a>>),
+    /// Key value pairs, convert to structs, maps, sets (keys with truthy values are the elements)
+    /// and enums (`{ Variant = fields }`, unit variants can also be given as strings)
+    Map(Vec<(ScriptValue<'a>, ScriptValue<'a>)>),
+    /// An already reflected value, used as is
+    Reflect(Box<dyn PartialReflect>),
+    /// A value which can only be converted once its type is known, e.g. a proxy of a script language
+    Deferred(DeferredConversion<'a>),
+}
+
+impl std::fmt::Debug for ScriptValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unit => f.write_str("Unit"),
+            Self::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            Self::Integer(i) => f.debug_tuple("Integer").field(i).finish(),
+            Self::Float(n) => f.debug_tuple("Float").field(n).finish(),
+            Self::String(s) => f.debug_tuple("String").field(s).finish(),
+            Self::List(items) => f.debug_tuple("List").field(items).finish(),
+            Self::Map(entries) => f.debug_tuple("Map").field(entries).finish(),
+            Self::Reflect(v) => f.debug_tuple("Reflect").field(v).finish(),
+            Self::Deferred(_) => f.write_str("Deferred"),
+        }
+    }
+}
+
+impl<'a> ScriptValue<'a> {
+    /// Converts this value to a value of the given type.
+    ///
+    /// The value is first converted to a dynamic value mirroring the type, which is then turned into a concrete value
+    /// via the [`ReflectFromReflect`] type data of the type, or by applying it to the default value given by [`ReflectDefault`].
+    /// Fields missing from the script value can only be filled in via the latter.
+    pub fn into_reflect(
+        self,
+        registration: &TypeRegistration,
+        registry: &TypeRegistry,
+    ) -> Result<Box<dyn Reflect>, ReflectionError> {
+        let dynamic = self.build(registration.type_info(), registry)?;
+        from_reflect_with_fallback(dynamic.as_ref(), registration)
+    }
+
+    /// A short description of the kind of this value, used in errors
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Self::Unit => "nil",
+            Self::Bool(_) => "a boolean",
+            Self::Integer(_) => "an integer",
+            Self::Float(_) => "a float",
+            Self::String(_) => "a string",
+            Self::List(_) => "a list",
+            Self::Map(_) => "a map",
+            Self::Reflect(_) | Self::Deferred(_) => "a rust value",
+        }
+    }
+
+    /// Returns true unless this is `Unit` or `false`
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Self::Unit | Self::Bool(false))
+    }
+
+    /// Converts this value to a dynamic value representing the given type
+    fn build(
+        self,
+        type_info: &'static TypeInfo,
+        registry: &TypeRegistry,
+    ) -> Result<Box<dyn PartialReflect>, ReflectionError> {
+        let type_path = type_info.type_path();
+        let kind = self.kind();
+
+        match (self, type_info) {
+            // values for options which are not options themselves are wrapped in `Some` below
+            (Self::Reflect(value), _)
+                if !is_option(type_info.type_path_table())
+                    || value
+                        .get_represented_type_info()
+                        .is_some_and(|i| i.type_id() == type_info.type_id()) =>
+            {
+                Ok(value)
+            }
+            (Self::Deferred(convert), _) if !is_option(type_info.type_path_table()) => {
+                convert(registry.get(type_info.type_id()).ok_or_else(|| {
+                    ReflectionError::Other(format!("`{type_path}` is not registered"))
+                })?)
+            }
+            (value, TypeInfo::Enum(info)) => {
+                let mut dynamic = build_enum(value, info, registry)?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::Bool(b), _) => build_primitive(b, kind, type_info),
+            (Self::Integer(i), _) => cast_number(&i, type_info.type_id())
+                .unwrap_or_else(|| build_primitive(i, kind, type_info)),
+            (Self::Float(n), _) => cast_number(&n, type_info.type_id())
+                .unwrap_or_else(|| build_primitive(n, kind, type_info)),
+            (Self::String(s), _) => build_primitive(s, kind, type_info),
+            // empty lua tables and rhai maps are ambiguous
+            (Self::List(items), TypeInfo::Struct(_) | TypeInfo::Map(_)) if items.is_empty() => {
+                Self::Map(Vec::default()).build(type_info, registry)
+            }
+            (
+                Self::Map(entries),
+                TypeInfo::TupleStruct(_)
+                | TypeInfo::Tuple(_)
+                | TypeInfo::List(_)
+                | TypeInfo::Array(_),
+            ) if entries.is_empty() => Self::List(Vec::default()).build(type_info, registry),
+            (Self::Map(entries), TypeInfo::Struct(info)) => {
+                let mut dynamic = build_struct(entries, type_path, |f| info.field(f), registry)?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::List(items), TypeInfo::TupleStruct(info)) => {
+                let mut dynamic: DynamicTupleStruct =
+                    build_tuple(items, type_path, |i| info.field_at(i), registry)?
+                        .into_iter()
+                        .collect();
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::List(items), TypeInfo::Tuple(info)) => {
+                let mut dynamic: DynamicTuple =
+                    build_tuple(items, type_path, |i| info.field_at(i), registry)?
+                        .into_iter()
+                        .collect();
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::List(items), TypeInfo::List(info)) => {
+                let item_info = resolve(info.item_info(), &info.item_ty(), registry)?;
+                let mut dynamic = items
+                    .into_iter()
+                    .map(|item| item.build(item_info, registry))
+                    .collect::<Result<DynamicList, _>>()?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::List(items), TypeInfo::Array(info)) => {
+                if items.len() != info.capacity() {
+                    return Err(ReflectionError::Other(format!(
+                        "Expected {} elements for `{type_path}`, found {}",
+                        info.capacity(),
+                        items.len()
+                    )));
+                }
+                let item_info = resolve(info.item_info(), &info.item_ty(), registry)?;
+                let mut dynamic = items
+                    .into_iter()
+                    .map(|item| item.build(item_info, registry))
+                    .collect::<Result<DynamicArray, _>>()?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::List(items), TypeInfo::Set(info)) => {
+                let value_info = resolve(None, &info.value_ty(), registry)?;
+                let mut dynamic = items
+                    .into_iter()
+                    .map(|item| item.build(value_info, registry))
+                    .collect::<Result<DynamicSet, _>>()?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::Map(entries), TypeInfo::Set(info)) => {
+                let value_info = resolve(None, &info.value_ty(), registry)?;
+                let mut dynamic = entries
+                    .into_iter()
+                    .filter(|(_, present)| present.is_truthy())
+                    .map(|(item, _)| item.build(value_info, registry))
+                    .collect::<Result<DynamicSet, _>>()?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (Self::Map(entries), TypeInfo::Map(info)) => {
+                let key_info = resolve(info.key_info(), &info.key_ty(), registry)?;
+                let value_info = resolve(info.value_info(), &info.value_ty(), registry)?;
+                let mut dynamic = entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            key.build(key_info, registry)?,
+                            value.build(value_info, registry)?,
+                        ))
+                    })
+                    .collect::<Result<DynamicMap, ReflectionError>>()?;
+                dynamic.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic))
+            }
+            (value, _) => Err(ReflectionError::Other(format!(
+                "Cannot convert {} to `{type_path}`",
+                value.kind()
+            ))),
+        }
+    }
+}
+
+/// Converts a value to an enum.
+///
+/// Options accept `nil` for `None` and any other value for `Some`,
+/// other enums accept the name of a unit variant or a single entry map from the variant name to its fields.
+fn build_enum(
+    value: ScriptValue,
+    info: &'static EnumInfo,
+    registry: &TypeRegistry,
+) -> Result<DynamicEnum, ReflectionError> {
+    let type_path = info.type_path();
+    let is_option = is_option(info.type_path_table());
+
+    let (variant_name, fields) = match value {
+        ScriptValue::Unit if is_option => ("None".to_owned(), ScriptValue::Unit),
+        value if is_option => ("Some".to_owned(), ScriptValue::List(vec![value])),
+        ScriptValue::String(name) => (name, ScriptValue::Unit),
+        ScriptValue::Map(entries) if entries.len() == 1 => {
+            let Some((ScriptValue::String(name), fields)) = entries.into_iter().next() else {
+                return Err(ReflectionError::Other(format!(
+                    "Variant names of `{type_path}` must be strings"
+                )));
+            };
+            (name, fields)
+        }
+        value => {
+            return Err(ReflectionError::Other(format!(
+                "Cannot convert {} to `{type_path}`, expected a variant name or a map from a variant name to its fields",
+                value.kind()
+            )))
+        }
+    };
+
+    let variant = info.variant(&variant_name).ok_or_else(|| {
+        ReflectionError::Other(format!("`{type_path}` has no variant `{variant_name}`"))
+    })?;
+
+    let dynamic_variant = match (variant, fields) {
+        (VariantInfo::Unit(_), _) => DynamicVariant::Unit,
+        (VariantInfo::Struct(variant), ScriptValue::Map(entries)) => DynamicVariant::Struct(
+            build_struct(entries, type_path, |f| variant.field(f), registry)?,
+        ),
+        (VariantInfo::Struct(_), ScriptValue::List(items)) if items.is_empty() => {
+            DynamicVariant::Struct(DynamicStruct::default())
+        }
+        (VariantInfo::Tuple(variant), fields) => {
+            let items = match fields {
+                ScriptValue::List(items) => items,
+                ScriptValue::Map(entries) if entries.is_empty() => Vec::default(),
+                // single field variants accept their field as is
+                field if variant.field_len() == 1 => vec![field],
+                field => {
+                    return Err(ReflectionError::Other(format!(
+                        "Cannot convert {} to variant `{variant_name}` of `{type_path}`, expected a list of fields",
+                        field.kind()
+                    )))
+                }
+            };
+            DynamicVariant::Tuple(
+                build_tuple(items, type_path, |i| variant.field_at(i), registry)?
+                    .into_iter()
+                    .collect(),
+            )
+        }
+        (VariantInfo::Struct(_), fields) => {
+            return Err(ReflectionError::Other(format!(
+                "Cannot convert {} to variant `{variant_name}` of `{type_path}`, expected a map of fields",
+                fields.kind()
+            )))
+        }
+    };
+
+    Ok(DynamicEnum::new(variant_name, dynamic_variant))
+}
+
+/// Returns the value if it is of the given type.
+///
+/// Mismatches need to be caught here, the `FromReflect` implementation of types with `#[reflect(Default)]` ignores fields of the wrong type.
+fn build_primitive<T: PartialReflect>(
+    value: T,
+    kind: &str,
+    type_info: &TypeInfo,
+) -> Result<Box<dyn PartialReflect>, ReflectionError> {
+    if type_info.type_id() == TypeId::of::<T>() {
+        Ok(Box::new(value))
+    } else {
+        Err(ReflectionError::Other(format!(
+            "Cannot convert {kind} to `{}`",
+            type_info.type_path()
+        )))
+    }
+}
+
+/// Returns true if the given type is an `Option`
+fn is_option(type_path: &TypePathTable) -> bool {
+    type_path.module_path() == Some("core::option") && type_path.ident() == Some("Option")
+}
+
+/// Converts the entries of a map to the fields of a struct or struct variant, fields are given by name
+fn build_struct<'f>(
+    entries: Vec<(ScriptValue, ScriptValue)>,
+    type_path: &str,
+    field: impl Fn(&str) -> Option<&'f NamedField>,
+    registry: &TypeRegistry,
+) -> Result<DynamicStruct, ReflectionError> {
+    let mut dynamic = DynamicStruct::default();
+
+    for (name, value) in entries {
+        let ScriptValue::String(name) = name else {
+            return Err(ReflectionError::Other(format!(
+                "Field names of `{type_path}` must be strings, found {}",
+                name.kind()
+            )));
+        };
+
+        let field = field(&name).ok_or_else(|| {
+            ReflectionError::Other(format!("`{type_path}` has no field `{name}`"))
+        })?;
+
+        let field_info = resolve(field.type_info(), field.ty(), registry)?;
+        dynamic.insert_boxed(name, value.build(field_info, registry)?);
+    }
+
+    Ok(dynamic)
+}
+
+/// Converts the items of a list to the fields of a tuple, tuple struct or tuple variant, fields are given by position
+fn build_tuple<'f>(
+    items: Vec<ScriptValue>,
+    type_path: &str,
+    field_at: impl Fn(usize) -> Option<&'f UnnamedField>,
+    registry: &TypeRegistry,
+) -> Result<Vec<Box<dyn PartialReflect>>, ReflectionError> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let field = field_at(idx).ok_or_else(|| {
+                ReflectionError::Other(format!("`{type_path}` has no field at index {idx}"))
+            })?;
+            item.build(resolve(field.type_info(), field.ty(), registry)?, registry)
+        })
+        .collect()
+}
+
+/// Returns the given type info, or looks it up in the registry if it's not statically known
+fn resolve(
+    info: Option<&'static TypeInfo>,
+    ty: &Type,
+    registry: &TypeRegistry,
+) -> Result<&'static TypeInfo, ReflectionError> {
+    info.or_else(|| registry.get_type_info(ty.id()))
+        .ok_or_else(|| ReflectionError::Other(format!("`{}` is not registered", ty.path())))
+}
+
+/// Constructs a value of the given type from the given (usually dynamic) value.
+///
+/// Uses the [`ReflectFromReflect`] type data of the type if possible,
+/// otherwise applies the value to the default value given by [`ReflectDefault`].
+pub fn from_reflect_with_fallback(
+    value: &dyn PartialReflect,
+    registration: &TypeRegistration,
+) -> Result<Box<dyn Reflect>, ReflectionError> {
+    if let Some(value) = registration
+        .data::<ReflectFromReflect>()
+        .and_then(|from_reflect| from_reflect.from_reflect(value))
+    {
+        return Ok(value);
+    }
+
+    let type_path = registration.type_info().type_path();
+    let mut default = registration
+        .data::<ReflectDefault>()
+        .ok_or_else(|| {
+            ReflectionError::Other(format!(
+                "Cannot construct `{type_path}` from `{}`, and it has no `ReflectDefault` type data to fill in missing fields",
+                value.reflect_type_path()
+            ))
+        })?
+        .default();
+
+    default
+        .try_apply(value)
+        .map_err(|e| ReflectionError::Other(format!("Cannot construct `{type_path}`. {e}")))?;
+    Ok(default)
+}
+
+/// Casts script numbers, i.e. `i64` or `f64` values, to the number type with the given id.
+///
+/// Returns `None` if the value is not a script number or the target is not a number type.
+pub(crate) fn cast_number(
+    value: &dyn PartialReflect,
+    type_id: TypeId,
+) -> Option<Result<Box<dyn PartialReflect>, ReflectionError>> {
+    macro_rules! cast_number {
+        (integers: $($int:ty),*; floats: $($float:ty),*) => {
+            $(
+                if type_id == TypeId::of::<$int>() {
+                    if let Some(v) = value.try_downcast_ref::<i64>() {
+                        return Some(<$int>::try_from(*v)
+                            .map(|v| Box::new(v) as Box<dyn PartialReflect>)
+                            .map_err(|_| {
+                                ReflectionError::Other(format!(
+                                    "`{v}` does not fit into `{}`",
+                                    stringify!($int)
+                                ))
+                            }));
+                    }
+                }
+            )*
+            $(
+                if type_id == TypeId::of::<$float>() {
+                    if let Some(v) = value.try_downcast_ref::<i64>() {
+                        return Some(Ok(Box::new(*v as $float)));
+                    }
+                }
+            )*
+        };
+    }
+
+    // values of the target type itself are not cast
+    cast_number!(
+        integers: i8, i16, i32, i128, isize, u8, u16, u32, u64, u128, usize;
+        floats: f32, f64
+    );
+
+    if type_id == TypeId::of::<f32>() {
+        if let Some(v) = value.try_downcast_ref::<f64>() {
+            return Some(Ok(Box::new(*v as f32)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use bevy::reflect::TypePath;
+
+    use super::*;
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Default)]
+    struct Inner {
+        x: f32,
+        name: String,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Default)]
+    struct Outer {
+        inner: Inner,
+        count: u8,
+        shape: Shape,
+        tags: Vec<String>,
+        maybe: Option<u32>,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Default)]
+    enum Shape {
+        #[default]
+        Empty,
+        Circle(f32),
+        Rect {
+            w: f32,
+            h: f32,
+        },
+    }
+
+    #[derive(Reflect, Debug)]
+    struct NoDefault {
+        a: u32,
+        b: u32,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Outer>();
+        registry.register::<NoDefault>();
+        registry
+    }
+
+    fn convert<T: Reflect + TypePath>(value: ScriptValue) -> Result<T, ReflectionError> {
+        let registry = registry();
+        let registration = registry.get(TypeId::of::<T>()).unwrap();
+        value
+            .into_reflect(registration, &registry)
+            .map(|value| *value.downcast::<T>().unwrap())
+    }
+
+    fn string(s: &str) -> ScriptValue<'static> {
+        ScriptValue::String(s.to_owned())
+    }
+
+    fn map<const N: usize>(entries: [(&str, ScriptValue<'static>); N]) -> ScriptValue<'static> {
+        ScriptValue::Map(entries.into_iter().map(|(k, v)| (string(k), v)).collect())
+    }
+
+    fn assert_error<T: Reflect + TypePath + Debug>(value: ScriptValue, expected: &str) {
+        match convert::<T>(value) {
+            Err(e) => assert!(e.to_string().contains(expected), "{e}"),
+            Ok(v) => panic!("expected an error containing `{expected}`, got {v:?}"),
+        }
+    }
+
+    #[test]
+    fn test_nested_struct() {
+        let value = map([
+            (
+                "inner",
+                map([("x", ScriptValue::Integer(1)), ("name", string("a"))]),
+            ),
+            ("count", ScriptValue::Integer(3)),
+            (
+                "shape",
+                map([(
+                    "Rect",
+                    map([
+                        ("w", ScriptValue::Float(1.5)),
+                        ("h", ScriptValue::Integer(2)),
+                    ]),
+                )]),
+            ),
+            ("tags", ScriptValue::List(vec![string("b"), string("c")])),
+            ("maybe", ScriptValue::Integer(4)),
+        ]);
+
+        assert_eq!(
+            convert::<Outer>(value).unwrap(),
+            Outer {
+                inner: Inner {
+                    x: 1.0,
+                    name: "a".to_owned()
+                },
+                count: 3,
+                shape: Shape::Rect { w: 1.5, h: 2.0 },
+                tags: vec!["b".to_owned(), "c".to_owned()],
+                maybe: Some(4),
+            }
+        );
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        assert_eq!(convert::<Shape>(string("Empty")).unwrap(), Shape::Empty);
+        assert_eq!(
+            convert::<Shape>(map([("Empty", ScriptValue::Unit)])).unwrap(),
+            Shape::Empty
+        );
+        // single field variants accept their field with or without a list around it
+        assert_eq!(
+            convert::<Shape>(map([("Circle", ScriptValue::Float(2.0))])).unwrap(),
+            Shape::Circle(2.0)
+        );
+        assert_eq!(
+            convert::<Shape>(map([(
+                "Circle",
+                ScriptValue::List(vec![ScriptValue::Integer(2)])
+            )]))
+            .unwrap(),
+            Shape::Circle(2.0)
+        );
+        assert_eq!(
+            convert::<Shape>(map([(
+                "Rect",
+                map([
+                    ("w", ScriptValue::Float(1.0)),
+                    ("h", ScriptValue::Float(2.0))
+                ])
+            )]))
+            .unwrap(),
+            Shape::Rect { w: 1.0, h: 2.0 }
+        );
+
+        assert_error::<Shape>(string("Square"), "has no variant `Square`");
+        assert_error::<Shape>(
+            map([("Rect", ScriptValue::List(vec![ScriptValue::Float(1.0)]))]),
+            "expected a map of fields",
+        );
+        assert_error::<Shape>(ScriptValue::Integer(1), "expected a variant name");
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        // filled in from the default value
+        assert_eq!(
+            convert::<Outer>(map([
+                ("count", ScriptValue::Integer(1)),
+                ("inner", map([("name", string("a"))])),
+            ]))
+            .unwrap(),
+            Outer {
+                inner: Inner {
+                    x: 0.0,
+                    name: "a".to_owned()
+                },
+                count: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(convert::<Outer>(map([])).unwrap(), Outer::default());
+        assert_eq!(
+            convert::<Outer>(map([("maybe", ScriptValue::Unit)]))
+                .unwrap()
+                .maybe,
+            None
+        );
+
+        // without a default, all fields need to be given
+        let no_default = convert::<NoDefault>(map([
+            ("a", ScriptValue::Integer(1)),
+            ("b", ScriptValue::Integer(2)),
+        ]))
+        .unwrap();
+        assert_eq!((no_default.a, no_default.b), (1, 2));
+        assert_error::<NoDefault>(map([("a", ScriptValue::Integer(1))]), "ReflectDefault");
+    }
+
+    #[test]
+    fn test_type_mismatches() {
+        assert_error::<Outer>(
+            map([("count", string("three"))]),
+            "Cannot convert a string to `u8`",
+        );
+        assert_error::<Outer>(
+            map([("count", ScriptValue::Float(1.5))]),
+            "Cannot convert a float to `u8`",
+        );
+        assert_error::<Outer>(map([("count", ScriptValue::Integer(300))]), "does not fit");
+        assert_error::<Outer>(
+            map([("size", ScriptValue::Integer(1))]),
+            "has no field `size`",
+        );
+        assert_error::<Outer>(
+            ScriptValue::List(vec![ScriptValue::Integer(1)]),
+            "Cannot convert a list",
+        );
+        assert_error::<Outer>(
+            map([("tags", ScriptValue::List(vec![ScriptValue::Bool(true)]))]),
+            "Cannot convert a boolean to `alloc::string::String`",
+        );
+        assert_error::<Outer>(
+            map([("inner", map([("x", ScriptValue::Bool(true))]))]),
+            "Cannot convert a boolean to `f32`",
+        );
+        assert_error::<Outer>(
+            ScriptValue::Map(vec![(ScriptValue::Integer(1), ScriptValue::Integer(1))]),
+            "Field names",
+        );
+    }
+}
@@ -10,7 +10,10 @@ use bevy::{
     reflect::{Reflect, ReflectMut, ReflectRef},
 };
 
-use crate::error::ReflectionError;
+use crate::{
+    common::bevy::{events::SharedReflect, script_types::ScriptTypeDescriptor},
+    error::ReflectionError,
+};
 use bevy_mod_scripting_core::world::WorldPointer;
 
 /// The base of a reflect path, i.e. the top-level object or source.
@@ -29,6 +32,19 @@ pub(crate) enum ReflectBase {
 
     /// A script owned reflect type (for example a vector constructed in lua)
     ScriptOwned { val: Weak<RwLock<dyn Reflect>> },
+
+    /// A reflect type owned by the references to it (for example a value constructed in lua via `Type.new`)
+    Owned { val: SharedReflect },
+
+    /// A component of a type defined by a script
+    ScriptComponent {
+        script_type: Arc<ScriptTypeDescriptor>,
+        entity: Entity,
+    },
+    /// A resource of a type defined by a script
+    ScriptResource {
+        script_type: Arc<ScriptTypeDescriptor>,
+    },
 }
 
 /// Safety: we can safely send this value across thread boundaries
@@ -44,7 +60,20 @@ impl fmt::Debug for ReflectBase {
                 f.debug_struct("Component").field("entity", entity).finish()
             }
             Self::ScriptOwned { .. } => write!(f, "ScriptOwned"),
+            Self::Owned { .. } => write!(f, "Owned"),
             Self::Resource { .. } => f.debug_struct("Resource").finish(),
+            Self::ScriptComponent {
+                script_type,
+                entity,
+            } => f
+                .debug_struct("ScriptComponent")
+                .field("type", &script_type.name())
+                .field("entity", entity)
+                .finish(),
+            Self::ScriptResource { script_type } => f
+                .debug_struct("ScriptResource")
+                .field("type", &script_type.name())
+                .finish(),
         }
     }
 }
@@ -59,6 +88,12 @@ impl fmt::Display for ReflectBase {
             }
             ReflectBase::Resource { .. } => f.write_str("(Resource)"),
             ReflectBase::ScriptOwned { .. } => f.write_str("(ScriptOwned)"),
+            ReflectBase::Owned { .. } => f.write_str("(Owned)"),
+            ReflectBase::ScriptComponent {
+                script_type,
+                entity,
+            } => write!(f, "({} on {})", script_type.name(), entity.index()),
+            ReflectBase::ScriptResource { script_type } => write!(f, "({})", script_type.name()),
         }
     }
 }
@@ -78,7 +113,13 @@ pub enum ReflectionPathElement {
     /// Access to a struct field
     FieldAccess(Cow<'static, str>),
     /// Access to a TupleStruct, Tuple, List or Array element
-    IndexAccess(usize), // TODO: Map access
+    IndexAccess(usize),
+    /// Access to the Map value stored under the given key, or to the given Set element.
+    /// The key needs to be of the exact key type of the container, Set elements cannot be accessed mutably.
+    MapKeyAccess(Arc<dyn PartialReflect>),
+    /// Access to the key of the Map entry or to the Set element at the given position in iteration order.
+    /// Keys cannot be accessed mutably.
+    EntryKeyAccess(usize),
 }
 
 impl Debug for ReflectionPathElement {
@@ -90,6 +131,8 @@ impl Debug for ReflectionPathElement {
                 .finish(),
             Self::FieldAccess(arg0) => f.debug_tuple("FieldAccess").field(arg0).finish(),
             Self::IndexAccess(arg0) => f.debug_tuple("IndexAccess").field(arg0).finish(),
+            Self::MapKeyAccess(arg0) => f.debug_tuple("MapKeyAccess").field(arg0).finish(),
+            Self::EntryKeyAccess(arg0) => f.debug_tuple("EntryKeyAccess").field(arg0).finish(),
         }
     }
 }
@@ -111,6 +154,12 @@ impl Display for ReflectionPathElement {
                 f.write_str(&i.to_string())?;
                 f.write_str("]")
             }
+            ReflectionPathElement::MapKeyAccess(key) => write!(f, "[{key:?}]"),
+            ReflectionPathElement::EntryKeyAccess(i) => {
+                f.write_str(".keys()[")?;
+                f.write_str(&i.to_string())?;
+                f.write_str("]")
+            }
         }
     }
 }
@@ -130,6 +179,13 @@ impl ReflectionPathElement {
                         path: self.to_string(),
                         msg: "No such field".to_owned(),
                     }),
+                ReflectRef::Enum(e) => e
+                    .field(field)
+                    .and_then(PartialReflect::try_as_reflect)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: format!("No such field in variant `{}`", e.variant_name()),
+                    }),
                 _ => Err(ReflectionError::InvalidReflectionPath {
                     path: self.to_string(),
                     msg: "No such field".to_owned(),
@@ -164,11 +220,59 @@ impl ReflectionPathElement {
                         path: self.to_string(),
                         msg: "No such element".to_owned(),
                     }),
+                ReflectRef::Enum(e) => e
+                    .field_at(*index)
+                    .and_then(PartialReflect::try_as_reflect)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: format!("No such element in variant `{}`", e.variant_name()),
+                    }),
                 _ => Err(ReflectionError::InvalidReflectionPath {
                     path: self.to_string(),
                     msg: "No such element".to_owned(),
                 }),
             },
+            ReflectionPathElement::MapKeyAccess(key) => match base.reflect_ref() {
+                ReflectRef::Map(m) => m
+                    .get(key.as_ref())
+                    .and_then(PartialReflect::try_as_reflect)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: "No such key".to_owned(),
+                    }),
+                ReflectRef::Set(s) => s
+                    .get(key.as_ref())
+                    .and_then(PartialReflect::try_as_reflect)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: "No such element".to_owned(),
+                    }),
+                _ => Err(ReflectionError::InvalidReflectionPath {
+                    path: self.to_string(),
+                    msg: "Not a map or set".to_owned(),
+                }),
+            },
+            ReflectionPathElement::EntryKeyAccess(index) => match base.reflect_ref() {
+                ReflectRef::Map(m) => m
+                    .get_at(*index)
+                    .and_then(|(key, _)| key.try_as_reflect())
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: "No such entry".to_owned(),
+                    }),
+                ReflectRef::Set(s) => s
+                    .iter()
+                    .nth(*index)
+                    .and_then(PartialReflect::try_as_reflect)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: "No such element".to_owned(),
+                    }),
+                _ => Err(ReflectionError::InvalidReflectionPath {
+                    path: self.to_string(),
+                    msg: "Not a map or set".to_owned(),
+                }),
+            },
         }
     }
 
@@ -186,6 +290,15 @@ impl ReflectionPathElement {
                         path: self.to_string(),
                         msg: "No such field".to_owned(),
                     }),
+                ReflectMut::Enum(e) => {
+                    let variant = e.variant_name().to_owned();
+                    e.field_mut(field)
+                        .and_then(PartialReflect::try_as_reflect_mut)
+                        .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                            path: self.to_string(),
+                            msg: format!("No such field in variant `{variant}`"),
+                        })
+                }
                 _ => Err(ReflectionError::InvalidReflectionPath {
                     path: self.to_string(),
                     msg: "No such field".to_owned(),
@@ -220,11 +333,43 @@ impl ReflectionPathElement {
                         path: self.to_string(),
                         msg: "No such element".to_owned(),
                     }),
+                ReflectMut::Enum(e) => {
+                    let variant = e.variant_name().to_owned();
+                    e.field_at_mut(*index)
+                        .and_then(PartialReflect::try_as_reflect_mut)
+                        .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                            path: self.to_string(),
+                            msg: format!("No such element in variant `{variant}`"),
+                        })
+                }
                 _ => Err(ReflectionError::InvalidReflectionPath {
                     path: self.to_string(),
                     msg: "No such element".to_owned(),
                 }),
             },
+            ReflectionPathElement::MapKeyAccess(key) => match base.reflect_mut() {
+                ReflectMut::Map(m) => m
+                    .get_mut(key.as_ref())
+                    .and_then(PartialReflect::try_as_reflect_mut)
+                    .ok_or_else(|| ReflectionError::InvalidReflectionPath {
+                        path: self.to_string(),
+                        msg: "No such key".to_owned(),
+                    }),
+                ReflectMut::Set(_) => Err(ReflectionError::InvalidReflectionPath {
+                    path: self.to_string(),
+                    msg: "Set elements cannot be mutated".to_owned(),
+                }),
+                _ => Err(ReflectionError::InvalidReflectionPath {
+                    path: self.to_string(),
+                    msg: "Not a map or set".to_owned(),
+                }),
+            },
+            ReflectionPathElement::EntryKeyAccess(_) => {
+                Err(ReflectionError::InvalidReflectionPath {
+                    path: self.to_string(),
+                    msg: "Keys cannot be mutated".to_owned(),
+                })
+            }
         }
     }
 }
@@ -343,6 +488,42 @@ impl ReflectionPath {
                 let g = g.try_read().expect("Rust safety violation: attempted to borrow value {self:?} while it was already mutably borrowed");
                 Ok(f(self.walk_path(&*g)?))
             }
+            ReflectBase::Owned { val } => {
+                let g = val.try_read().expect("Rust safety violation: attempted to borrow value {self:?} while it was already mutably borrowed");
+                Ok(f(self.walk_path(&**g)?))
+            }
+            ReflectBase::ScriptComponent {
+                script_type,
+                entity,
+            } => {
+                let g = world_ptr.read();
+
+                let entity_ref =
+                    g.get_entity(*entity)
+                        .map_err(|e| ReflectionError::InvalidBaseReference {
+                            base: self.base.to_string(),
+                            reason: format!("This entity could not be retrieved. {e}"),
+                        })?;
+
+                let ref_ = self.walk_path(script_type.reflect(entity_ref).ok_or_else(|| {
+                    ReflectionError::InvalidBaseReference {
+                        base: self.base.to_string(),
+                        reason: "Given component does not exist on this entity".to_owned(),
+                    }
+                })?)?;
+                Ok(f(ref_))
+            }
+            ReflectBase::ScriptResource { script_type } => {
+                let g = world_ptr.read();
+
+                let ref_ = self.walk_path(script_type.resource(&g).ok_or_else(|| {
+                    ReflectionError::InvalidBaseReference {
+                        base: self.base.to_string(),
+                        reason: "Given resource does not exist in this world".to_owned(),
+                    }
+                })?)?;
+                Ok(f(ref_))
+            }
         }
     }
 
@@ -385,6 +566,203 @@ impl ReflectionPath {
                 let mut g = g.try_write().expect("Rust safety violation: attempted to borrow value {self:?} while it was already mutably borrowed");
                 Ok(f(self.walk_path_mut(&mut *g)?))
             }
+            ReflectBase::Owned { val } => {
+                let mut g = val.try_write().expect("Rust safety violation: attempted to borrow value {self:?} while it was already mutably borrowed");
+                Ok(f(self.walk_path_mut(&mut **g)?))
+            }
+            ReflectBase::ScriptComponent {
+                script_type,
+                entity,
+            } => {
+                let mut g = world_ptr.write();
+
+                let mut e = g.entity_mut(*entity);
+                let ref_ = self.walk_path_mut(
+                    script_type
+                        .reflect_mut(&mut e)
+                        .ok_or_else(|| ReflectionError::InvalidBaseReference {
+                            base: self.base.to_string(),
+                            reason: "Given component does not exist on this entity".to_owned(),
+                        })?
+                        .into_inner(),
+                )?;
+                Ok(f(ref_))
+            }
+            ReflectBase::ScriptResource { script_type } => {
+                let mut g = world_ptr.write();
+
+                let ref_ = self.walk_path_mut(
+                    script_type
+                        .resource_mut(&mut g)
+                        .ok_or_else(|| ReflectionError::InvalidBaseReference {
+                            base: self.base.to_string(),
+                            reason: "Given resource does not exist in this world".to_owned(),
+                        })?
+                        .into_inner(),
+                )?;
+                Ok(f(ref_))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::{HashMap, HashSet};
+
+    use super::*;
+
+    #[derive(Reflect, Default)]
+    struct Holder {
+        map: HashMap<String, u32>,
+        set: HashSet<u32>,
+        shape: Shape,
+    }
+
+    #[derive(Reflect, Default)]
+    enum Shape {
+        #[default]
+        Empty,
+        Circle(f32),
+        Rect {
+            w: f32,
+            h: f32,
+        },
+    }
+
+    fn holder(shape: Shape) -> Holder {
+        Holder {
+            map: [("a".to_owned(), 1)].into_iter().collect(),
+            set: [3].into_iter().collect(),
+            shape,
         }
     }
+
+    /// Creates a path from the given elements, the base is never accessed when walking the path directly
+    fn path(elements: impl IntoIterator<Item = ReflectionPathElement>) -> ReflectionPath {
+        let base = ReflectBase::Owned {
+            val: Arc::new(RwLock::new(Box::new(()))),
+        };
+        elements
+            .into_iter()
+            .fold(ReflectionPath::new(base), |path, element| {
+                path.new_sub(element)
+            })
+    }
+
+    fn field(name: &'static str) -> ReflectionPathElement {
+        ReflectionPathElement::FieldAccess(name.into())
+    }
+
+    fn key(key: impl PartialReflect) -> ReflectionPathElement {
+        ReflectionPathElement::MapKeyAccess(Arc::new(key))
+    }
+
+    #[test]
+    fn test_map_key_access() {
+        let mut holder = holder(Shape::Empty);
+
+        let value = path([field("map"), key("a".to_owned())]);
+        assert_eq!(
+            value.walk_path(&holder).unwrap().downcast_ref::<u32>(),
+            Some(&1)
+        );
+
+        *value
+            .walk_path_mut(&mut holder)
+            .unwrap()
+            .downcast_mut::<u32>()
+            .unwrap() = 5;
+        assert_eq!(holder.map["a"], 5);
+
+        // missing keys and keys of the wrong type
+        assert!(path([field("map"), key("b".to_owned())])
+            .walk_path(&holder)
+            .is_err());
+        assert!(path([field("map"), key(1u32)]).walk_path(&holder).is_err());
+        assert!(path([field("shape"), key(1u32)])
+            .walk_path(&holder)
+            .is_err());
+
+        let entry_key = path([field("map"), ReflectionPathElement::EntryKeyAccess(0)]);
+        assert_eq!(
+            entry_key
+                .walk_path(&holder)
+                .unwrap()
+                .downcast_ref::<String>(),
+            Some(&"a".to_owned())
+        );
+        assert!(entry_key.walk_path_mut(&mut holder).is_err());
+    }
+
+    #[test]
+    fn test_set_membership() {
+        let mut holder = holder(Shape::Empty);
+
+        let element = path([field("set"), key(3u32)]);
+        assert_eq!(
+            element.walk_path(&holder).unwrap().downcast_ref::<u32>(),
+            Some(&3)
+        );
+        assert!(element.walk_path_mut(&mut holder).is_err());
+        assert!(path([field("set"), key(4u32)]).walk_path(&holder).is_err());
+
+        let entry = path([field("set"), ReflectionPathElement::EntryKeyAccess(0)]);
+        assert_eq!(
+            entry.walk_path(&holder).unwrap().downcast_ref::<u32>(),
+            Some(&3)
+        );
+        assert!(
+            path([field("set"), ReflectionPathElement::EntryKeyAccess(1)])
+                .walk_path(&holder)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_enum_variants() {
+        let mut circle = holder(Shape::Circle(2.0));
+        let radius = path([field("shape"), ReflectionPathElement::IndexAccess(0)]);
+        assert_eq!(
+            radius.walk_path(&circle).unwrap().downcast_ref::<f32>(),
+            Some(&2.0)
+        );
+        *radius
+            .walk_path_mut(&mut circle)
+            .unwrap()
+            .downcast_mut::<f32>()
+            .unwrap() = 3.0;
+        assert!(matches!(circle.shape, Shape::Circle(r) if r == 3.0));
+
+        // fields only exist on the active variant
+        match path([field("shape"), field("w")]).walk_path(&circle) {
+            Err(ReflectionError::InvalidReflectionPath { msg, .. }) => {
+                assert!(msg.contains("Circle"), "{msg}")
+            }
+            _ => panic!("expected an invalid path"),
+        }
+
+        let mut rect = holder(Shape::Rect { w: 1.0, h: 2.0 });
+        assert_eq!(
+            path([field("shape"), field("h")])
+                .walk_path(&rect)
+                .unwrap()
+                .downcast_ref::<f32>(),
+            Some(&2.0)
+        );
+        // struct variant fields can also be accessed by position
+        *path([field("shape"), ReflectionPathElement::IndexAccess(0)])
+            .walk_path_mut(&mut rect)
+            .unwrap()
+            .downcast_mut::<f32>()
+            .unwrap() = 4.0;
+        assert!(matches!(rect.shape, Shape::Rect { w, .. } if w == 4.0));
+
+        let empty = holder(Shape::Empty);
+        assert!(
+            path([field("shape"), ReflectionPathElement::IndexAccess(0)])
+                .walk_path(&empty)
+                .is_err()
+        );
+    }
 }
@@ -146,12 +146,13 @@ impl AssetLoader for LuaLoader {
         Ok(LuaFile { bytes })
     }
 
-    #[cfg(feature = "teal")]
     fn extensions(&self) -> &[&str] {
-        &["lua", "tl"]
-    }
-    #[cfg(not(feature = "teal"))]
-    fn extensions(&self) -> &[&str] {
-        &["lua"]
+        &[
+            "lua",
+            #[cfg(feature = "teal")]
+            "tl",
+            #[cfg(feature = "luau")]
+            "luau",
+        ]
     }
 }
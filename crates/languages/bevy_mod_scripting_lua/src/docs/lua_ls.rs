@@ -0,0 +1,267 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use tealr::{
+    type_parts_to_str, ExportedFunction, FunctionRepresentation, MapRepresentation, NamePart,
+    RecordGenerator, Type, TypeGenerator, TypeWalker,
+};
+
+use super::{documentation, generic_names, is_identifier, name_to_str};
+
+/// Renders a definition file with annotations understood by the Lua Language Server (LuaLS/EmmyLua).
+///
+/// Every record becomes a `---@class`, enums become string `---@alias`es and globals are declared with their `---@type`.
+pub(crate) fn generate(name: &str, tw: &TypeWalker) -> String {
+    let mut out = format!("---@meta {name}\n-- Generated by bevy_mod_scripting, do not edit\n");
+
+    for tg in &tw.given_types {
+        match tg {
+            TypeGenerator::Record(rg) => write_record(&mut out, rg),
+            TypeGenerator::Enum(eg) => {
+                out.push('\n');
+                write_doc(&mut out, &eg.type_doc);
+                let variants = eg
+                    .variants
+                    .iter()
+                    .map(|v| format!("\"{}\"", name_to_str(v)))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                let _ = writeln!(
+                    out,
+                    "---@alias {} {variants}",
+                    type_parts_to_str(eg.name.clone())
+                );
+            }
+        }
+    }
+
+    for global in &tw.global_instances_off {
+        out.push('\n');
+        write_doc(&mut out, &global.doc);
+        let _ = writeln!(
+            out,
+            "---@type {}\n{} = nil",
+            lua_ls_type(&global.ty),
+            global.name
+        );
+    }
+
+    out
+}
+
+fn write_record(out: &mut String, rg: &RecordGenerator) {
+    let class_name = type_parts_to_str(rg.type_name.clone());
+    // the table the functions are declared on, without any generic parameters
+    let table = class_name
+        .split('<')
+        .next()
+        .unwrap_or(&class_name)
+        .to_owned();
+    let class_generics = rg
+        .type_name
+        .iter()
+        .filter_map(|part| match part {
+            NamePart::Type(ty) if ty.type_kind.is_generic() => Some(ty.name.to_string()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    out.push('\n');
+    write_doc(out, &rg.type_doc);
+    let _ = writeln!(out, "---@class {class_name}");
+
+    for field in rg.fields.iter().chain(&rg.static_fields) {
+        let name = name_to_str(&field.name);
+        let name = if is_identifier(&name) {
+            name
+        } else {
+            format!("[\"{name}\"]")
+        };
+        let doc = documentation(rg, &field.name)
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let field = format!("---@field {name} {} {doc}", lua_ls_type(&field.ty));
+        let _ = writeln!(out, "{}", field.trim_end());
+    }
+
+    for function in rg
+        .meta_method
+        .iter()
+        .chain(&rg.meta_method_mut)
+        .chain(&rg.meta_function)
+        .chain(&rg.meta_function_mut)
+    {
+        write_operator(out, function);
+    }
+
+    let _ = writeln!(out, "local {table} = {{}}");
+
+    let methods = rg.methods.iter().chain(&rg.mut_methods).map(|f| (f, true));
+    let functions = rg
+        .functions
+        .iter()
+        .chain(&rg.mut_functions)
+        .map(|f| (f, false));
+    for (function, is_method) in methods.chain(functions) {
+        out.push('\n');
+        write_doc(out, documentation(rg, &function.name));
+        write_function(out, &table, &class_generics, function, is_method);
+    }
+}
+
+fn write_function(
+    out: &mut String,
+    table: &str,
+    class_generics: &BTreeSet<String>,
+    function: &ExportedFunction,
+    is_method: bool,
+) {
+    let generics = function
+        .params
+        .iter()
+        .map(|p| &p.ty)
+        .chain(&function.returns)
+        .flat_map(generic_names)
+        .filter(|g| !class_generics.contains(g))
+        .collect::<BTreeSet<_>>();
+    if !generics.is_empty() {
+        let _ = writeln!(
+            out,
+            "---@generic {}",
+            generics.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    let mut params = Vec::default();
+    for (i, param) in function
+        .params
+        .iter()
+        .filter(|p| p.param_name.as_ref().map(|n| n.0.as_ref()) != Some("self"))
+        .enumerate()
+    {
+        let name = match &param.param_name {
+            Some(name) if is_identifier(name.0.as_ref()) => name.to_string(),
+            _ => format!("p{}", i + 1),
+        };
+        let _ = writeln!(out, "---@param {name} {}", lua_ls_type(&param.ty));
+        params.push(name);
+    }
+    for ty in &function.returns {
+        let _ = writeln!(out, "---@return {}", lua_ls_type(ty));
+    }
+
+    let name = name_to_str(&function.name);
+    let params = params.join(", ");
+    match (is_identifier(&name), is_method) {
+        (true, true) => {
+            let _ = writeln!(out, "function {table}:{name}({params}) end");
+        }
+        (true, false) => {
+            let _ = writeln!(out, "function {table}.{name}({params}) end");
+        }
+        (false, true) => {
+            let params = ["self".to_owned(), params]
+                .into_iter()
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(out, "{table}[\"{name}\"] = function({params}) end");
+        }
+        (false, false) => {
+            let _ = writeln!(out, "{table}[\"{name}\"] = function({params}) end");
+        }
+    }
+}
+
+/// Annotates the operators LuaLS understands, other meta methods are left out
+fn write_operator(out: &mut String, function: &ExportedFunction) {
+    const UNARY: &[&str] = &["unm", "bnot", "len"];
+    const BINARY: &[&str] = &[
+        "add", "sub", "mul", "div", "mod", "pow", "idiv", "band", "bor", "bxor", "shl", "shr",
+        "concat",
+    ];
+
+    let name = name_to_str(&function.name);
+    let Some(operator) = name.strip_prefix("__") else {
+        return;
+    };
+    let returns = function
+        .returns
+        .first()
+        .map(lua_ls_type)
+        .unwrap_or_else(|| "nil".to_owned());
+
+    if UNARY.contains(&operator) {
+        let _ = writeln!(out, "---@operator {operator}: {returns}");
+    } else if BINARY.contains(&operator) {
+        // both meta methods and meta functions receive the value itself first
+        if let Some(operand) = function.params.get(1) {
+            let _ = writeln!(
+                out,
+                "---@operator {operator}({}): {returns}",
+                lua_ls_type(&operand.ty)
+            );
+        }
+    } else if operator == "call" {
+        let params = function
+            .params
+            .iter()
+            .skip(1)
+            .map(|p| lua_ls_type(&p.ty))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "---@operator call({params}): {returns}");
+    }
+}
+
+fn write_doc(out: &mut String, doc: &str) {
+    for line in doc.lines() {
+        let _ = writeln!(out, "---{line}");
+    }
+}
+
+/// Converts a type into its LuaLS representation
+fn lua_ls_type(ty: &Type) -> String {
+    match ty {
+        Type::Single(single) => single.name.to_string(),
+        Type::Array(inner) => match inner.as_ref() {
+            Type::Single(_) | Type::Array(_) | Type::Map(_) | Type::Tuple(_) => {
+                format!("{}[]", lua_ls_type(inner))
+            }
+            _ => format!("({})[]", lua_ls_type(inner)),
+        },
+        Type::Map(MapRepresentation { key, value }) => {
+            format!("table<{}, {}>", lua_ls_type(key), lua_ls_type(value))
+        }
+        Type::Or(types) => types.iter().map(lua_ls_type).collect::<Vec<_>>().join("|"),
+        Type::Tuple(types) => format!(
+            "[{}]",
+            types.iter().map(lua_ls_type).collect::<Vec<_>>().join(", ")
+        ),
+        Type::Function(FunctionRepresentation { params, returns }) => {
+            let params = params
+                .iter()
+                .enumerate()
+                .map(|(i, p)| match &p.param_name {
+                    Some(name) if is_identifier(name.0.as_ref()) => {
+                        format!("{name}: {}", lua_ls_type(&p.ty))
+                    }
+                    _ => format!("p{}: {}", i + 1, lua_ls_type(&p.ty)),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            match returns.as_slice() {
+                [] => format!("fun({params})"),
+                returns => format!(
+                    "fun({params}): {}",
+                    returns
+                        .iter()
+                        .map(lua_ls_type)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use tealr::{
+    type_parts_to_str, ExportedFunction, FunctionParam, FunctionRepresentation, KindOfType,
+    MapRepresentation, RecordGenerator, Type, TypeGenerator, TypeWalker,
+};
+
+use super::{documentation, generic_names, is_identifier, name_to_str};
+
+/// Renders a Luau definition file which can be loaded by Luau's type checker and language server.
+///
+/// Records become `declare class`es, enums become string type aliases and globals are `declare`d with their types.
+pub(crate) fn generate(tw: &TypeWalker) -> String {
+    let mut out = String::from("-- Generated by bevy_mod_scripting, do not edit\n");
+
+    for tg in &tw.given_types {
+        match tg {
+            TypeGenerator::Record(rg) => write_record(&mut out, rg),
+            TypeGenerator::Enum(eg) => {
+                out.push('\n');
+                write_doc(&mut out, "", &eg.type_doc);
+                let variants = eg
+                    .variants
+                    .iter()
+                    .map(|v| format!("\"{}\"", name_to_str(v)))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                let _ = writeln!(
+                    out,
+                    "type {} = {variants}",
+                    type_parts_to_str(eg.name.clone())
+                );
+            }
+        }
+    }
+
+    for global in &tw.global_instances_off {
+        out.push('\n');
+        write_doc(&mut out, "", &global.doc);
+        let _ = writeln!(out, "declare {}: {}", global.name, luau_type(&global.ty));
+    }
+
+    out
+}
+
+fn write_record(out: &mut String, rg: &RecordGenerator) {
+    let class_name = type_parts_to_str(rg.type_name.clone());
+    // classes cannot be generic
+    let class_name = class_name.split('<').next().unwrap_or(&class_name);
+
+    out.push('\n');
+    write_doc(out, "", &rg.type_doc);
+    let _ = writeln!(out, "declare class {class_name}");
+
+    for field in rg.fields.iter().chain(&rg.static_fields) {
+        write_doc(out, "\t", documentation(rg, &field.name));
+        let _ = writeln!(
+            out,
+            "\t{}: {}",
+            property_name(&name_to_str(&field.name)),
+            luau_type(&field.ty)
+        );
+    }
+
+    let methods = rg.methods.iter().chain(&rg.mut_methods);
+    let meta_methods = rg.meta_method.iter().chain(&rg.meta_method_mut);
+    for function in methods.chain(meta_methods) {
+        let params = function
+            .params
+            .iter()
+            .filter(|p| p.param_name.as_ref().map(|n| n.0.as_ref()) != Some("self"));
+        write_method(out, rg, class_name, function, params);
+    }
+
+    // meta functions receive the value itself first, which makes them methods as far as Luau is concerned
+    for function in rg.meta_function.iter().chain(&rg.meta_function_mut) {
+        write_method(
+            out,
+            rg,
+            class_name,
+            function,
+            function.params.iter().skip(1),
+        );
+    }
+
+    for function in rg.functions.iter().chain(&rg.mut_functions) {
+        write_doc(out, "\t", documentation(rg, &function.name));
+        let generics = generics(
+            function
+                .params
+                .iter()
+                .map(|p| &p.ty)
+                .chain(&function.returns),
+        );
+        let ty = Type::Function(FunctionRepresentation {
+            params: function.params.clone(),
+            returns: function.returns.clone(),
+        });
+        let _ = writeln!(
+            out,
+            "\t{}: {generics}{}",
+            property_name(&name_to_str(&function.name)),
+            luau_type(&ty)
+        );
+    }
+
+    out.push_str("end\n");
+}
+
+fn write_method<'a>(
+    out: &mut String,
+    rg: &RecordGenerator,
+    class_name: &str,
+    function: &ExportedFunction,
+    params: impl Iterator<Item = &'a FunctionParam>,
+) {
+    write_doc(out, "\t", documentation(rg, &function.name));
+
+    let name = name_to_str(&function.name);
+    let params = params.collect::<Vec<_>>();
+    let generics = generics(params.iter().map(|p| &p.ty).chain(&function.returns));
+    let returns = returns(&function.returns);
+
+    if is_identifier(&name) {
+        let params = std::iter::once("self".to_owned())
+            .chain(named_params(&params))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "\tfunction {name}{generics}({params}): {returns}");
+    } else {
+        let params = std::iter::once(format!("self: {class_name}"))
+            .chain(named_params(&params))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "\t[\"{name}\"]: {generics}({params}) -> {returns}");
+    }
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: &str) {
+    for line in doc.lines() {
+        let _ = writeln!(out, "{indent}--- {line}");
+    }
+}
+
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_owned()
+    } else {
+        format!("[\"{name}\"]")
+    }
+}
+
+fn named_params<'a>(params: &'a [&FunctionParam]) -> impl Iterator<Item = String> + 'a {
+    params.iter().enumerate().map(|(i, p)| match &p.param_name {
+        Some(name) if is_identifier(name.0.as_ref()) => format!("{name}: {}", luau_type(&p.ty)),
+        _ => format!("p{}: {}", i + 1, luau_type(&p.ty)),
+    })
+}
+
+/// The generic parameter list of a function declaration using the given types, or an empty string
+fn generics<'a>(types: impl Iterator<Item = &'a Type>) -> String {
+    let names = types.flat_map(generic_names).collect::<BTreeSet<_>>();
+    if names.is_empty() {
+        String::default()
+    } else {
+        format!("<{}>", names.into_iter().collect::<Vec<_>>().join(", "))
+    }
+}
+
+fn returns(returns: &[Type]) -> String {
+    match returns {
+        [] => "()".to_owned(),
+        [ty] => luau_type(ty),
+        _ => format!(
+            "({})",
+            returns.iter().map(luau_type).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Converts a type into its Luau representation
+fn luau_type(ty: &Type) -> String {
+    match ty {
+        Type::Single(single) => match (&single.kind, single.name.0.as_ref()) {
+            (KindOfType::Builtin, "integer") => "number".to_owned(),
+            (KindOfType::Builtin, "userdata") => "any".to_owned(),
+            (KindOfType::Builtin, "table") => "{ [any]: any }".to_owned(),
+            (KindOfType::Builtin, "function") => "(...any) -> ...any".to_owned(),
+            (_, name) => name.to_owned(),
+        },
+        Type::Array(inner) => format!("{{ {} }}", luau_type(inner)),
+        Type::Map(MapRepresentation { key, value }) => {
+            format!("{{ [{}]: {} }}", luau_type(key), luau_type(value))
+        }
+        Type::Or(types) => format!(
+            "({})",
+            types.iter().map(luau_type).collect::<Vec<_>>().join(" | ")
+        ),
+        // Luau has no tuple types, teal's tuples are tables
+        Type::Tuple(_) => "{ any }".to_owned(),
+        Type::Function(FunctionRepresentation { params, returns: r }) => {
+            let params = params.iter().collect::<Vec<_>>();
+            format!(
+                "({}) -> {}",
+                named_params(&params).collect::<Vec<_>>().join(", "),
+                returns(r)
+            )
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use tealr::{
+    type_parts_to_str, ExportedFunction, Field, FunctionRepresentation, MapRepresentation,
+    RecordGenerator, Type, TypeGenerator, TypeWalker,
+};
+
+use super::{documentation, name_to_str};
+
+/// Renders a single markdown page documenting every global, type and extra page of the walker.
+///
+/// Types are written using teal's syntax.
+pub(crate) fn generate(name: &str, tw: &TypeWalker) -> String {
+    let mut out = format!("# {name}\n");
+
+    if !tw.global_instances_off.is_empty() {
+        out.push_str("\n## Globals\n\n");
+        for global in &tw.global_instances_off {
+            let _ = writeln!(out, "- `{}: {}`", global.name, teal_type(&global.ty));
+            write_doc(&mut out, &global.doc);
+        }
+    }
+
+    let mut records = Vec::default();
+    let mut enums = Vec::default();
+    for tg in &tw.given_types {
+        match tg {
+            TypeGenerator::Record(rg) => records.push(rg.as_ref()),
+            TypeGenerator::Enum(eg) => enums.push(eg),
+        }
+    }
+
+    if !records.is_empty() {
+        out.push_str("\n## Types\n");
+        for rg in records {
+            write_record(&mut out, rg);
+        }
+    }
+
+    if !enums.is_empty() {
+        out.push_str("\n## Enums\n");
+        for eg in enums {
+            let _ = writeln!(out, "\n### `{}`\n", type_parts_to_str(eg.name.clone()));
+            if !eg.type_doc.is_empty() {
+                let _ = writeln!(out, "{}\n", eg.type_doc);
+            }
+            let variants = eg
+                .variants
+                .iter()
+                .map(|v| format!("`\"{}\"`", name_to_str(v)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(out, "Variants: {variants}");
+        }
+    }
+
+    for page in &tw.extra_page {
+        let _ = writeln!(out, "\n## {}\n\n{}", page.name, page.content);
+    }
+
+    out
+}
+
+fn write_record(out: &mut String, rg: &RecordGenerator) {
+    let type_name = type_parts_to_str(rg.type_name.clone());
+    let _ = writeln!(out, "\n### `{type_name}`\n");
+    if !rg.type_doc.is_empty() {
+        let _ = writeln!(out, "{}\n", rg.type_doc);
+    }
+
+    let fields = rg
+        .fields
+        .iter()
+        .chain(&rg.static_fields)
+        .collect::<Vec<_>>();
+    if !fields.is_empty() {
+        out.push_str("#### Fields\n\n");
+        for field in fields {
+            write_field(out, rg, field);
+        }
+        out.push('\n');
+    }
+
+    let sections: [(&str, &str, Vec<&ExportedFunction>); 3] = [
+        (
+            "Methods",
+            ":",
+            rg.methods.iter().chain(&rg.mut_methods).collect(),
+        ),
+        (
+            "Functions",
+            ".",
+            rg.functions.iter().chain(&rg.mut_functions).collect(),
+        ),
+        (
+            "Meta methods",
+            ":",
+            rg.meta_method
+                .iter()
+                .chain(&rg.meta_method_mut)
+                .chain(&rg.meta_function)
+                .chain(&rg.meta_function_mut)
+                .collect(),
+        ),
+    ];
+    for (title, separator, functions) in sections {
+        if functions.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "#### {title}\n");
+        for function in functions {
+            let _ = writeln!(
+                out,
+                "- `{type_name}{separator}{}`",
+                function_signature(function)
+            );
+            write_doc(out, documentation(rg, &function.name));
+        }
+        out.push('\n');
+    }
+}
+
+fn write_field(out: &mut String, rg: &RecordGenerator, field: &Field) {
+    let _ = writeln!(
+        out,
+        "- `{}: {}`",
+        name_to_str(&field.name),
+        teal_type(&field.ty)
+    );
+    write_doc(out, documentation(rg, &field.name));
+}
+
+/// Writes documentation as the indented continuation of a list item
+fn write_doc(out: &mut String, doc: &str) {
+    for line in doc.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let _ = writeln!(out, "  {line}");
+    }
+}
+
+/// The signature of a function, without the `self` parameter of methods
+fn function_signature(function: &ExportedFunction) -> String {
+    let params = function
+        .params
+        .iter()
+        .filter(|p| p.param_name.as_ref().map(|n| n.0.as_ref()) != Some("self"))
+        .enumerate()
+        .map(|(i, p)| match &p.param_name {
+            Some(name) => format!("{name}: {}", teal_type(&p.ty)),
+            None => format!("p{}: {}", i + 1, teal_type(&p.ty)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{}({params}){}",
+        name_to_str(&function.name),
+        returns(&function.returns)
+    )
+}
+
+fn returns(returns: &[Type]) -> String {
+    match returns {
+        [] => String::default(),
+        [ty] => format!(": {}", teal_type(ty)),
+        _ => format!(
+            ": ({})",
+            returns.iter().map(teal_type).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Converts a type into its teal representation
+fn teal_type(ty: &Type) -> String {
+    match ty {
+        Type::Single(single) => single.name.to_string(),
+        Type::Array(inner) => format!("{{{}}}", teal_type(inner)),
+        Type::Map(MapRepresentation { key, value }) => {
+            format!("{{{} : {}}}", teal_type(key), teal_type(value))
+        }
+        Type::Or(types) => types.iter().map(teal_type).collect::<Vec<_>>().join(" | "),
+        Type::Tuple(types) => format!(
+            "({})",
+            types.iter().map(teal_type).collect::<Vec<_>>().join(", ")
+        ),
+        Type::Function(FunctionRepresentation { params, returns: r }) => format!(
+            "function({}){}",
+            params
+                .iter()
+                .map(|p| teal_type(&p.ty))
+                .collect::<Vec<_>>()
+                .join(", "),
+            returns(r)
+        ),
+    }
+}
@@ -0,0 +1,262 @@
+use core::str;
+#[cfg(feature = "teal")]
+use std::fs;
+use std::{borrow::Cow, fs::File, io::Write, ops::Deref, path::Path};
+
+#[cfg(feature = "teal")]
+use bevy::asset::io::file::FileAssetReader;
+use bevy_mod_scripting_core::prelude::*;
+use tealr::{
+    FunctionRepresentation, MapRepresentation, NameContainer, RecordGenerator, Type, TypeGenerator,
+    TypeWalker,
+};
+
+mod lua_ls;
+#[cfg(feature = "luau")]
+mod luau;
+mod markdown;
+
+pub type TypeWalkerBuilder = fn(TypeWalker) -> TypeWalker;
+
+#[cfg(feature = "teal")]
+static DEFAULT_TEAL_CONFIG: &str = r#"
+return {
+    global_env_def="types/types",
+    build_dir="build/"
+}
+"#;
+
+struct Fragment {
+    builder: TypeWalkerBuilder,
+}
+
+pub struct LuaDocFragment {
+    name: &'static str,
+    walker: Vec<Fragment>,
+}
+
+/// A piece of lua documentation,
+/// Each piece is combined into one large markdown documentation page, a json file describing every type, a single teal declaration file
+/// and a definition file annotated for the Lua Language Server. A Luau definition file is also generated if the `luau` feature is enabled.
+/// With the `teal` feature enabled, the declaration file is also copied to `assets/scripts/types/types.d.tl`, except when checking the documentation with `DocGenerator::check`
+impl LuaDocFragment {
+    pub fn new(name: &'static str, f: TypeWalkerBuilder) -> Self {
+        Self {
+            name,
+            walker: vec![Fragment { builder: f }],
+        }
+    }
+}
+
+impl DocFragment for LuaDocFragment {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn merge(mut self, o: Self) -> Self {
+        self.walker.extend(o.walker);
+        self
+    }
+
+    fn gen_docs(self, output_dir: &Path) -> Result<(), ScriptError> {
+        #[allow(unused_variables)]
+        let definitions = self.generate(output_dir)?;
+
+        #[cfg(feature = "teal")]
+        sync_teal_definitions(&definitions)?;
+
+        Ok(())
+    }
+
+    /// Leaves out the teal declarations and config in `assets/scripts`
+    fn gen_checked_docs(self, output_dir: &Path) -> Result<(), ScriptError> {
+        self.generate(output_dir).map(|_| ())
+    }
+}
+
+impl LuaDocFragment {
+    /// Generates the documentation into the output directory, returns the teal declarations
+    fn generate(self, output_dir: &Path) -> Result<String, ScriptError> {
+        let docs_name = self.name().to_owned();
+
+        // build the type walker
+        let mut tw = self
+            .walker
+            .into_iter()
+            .fold(TypeWalker::new(), |a, v| (v.builder)(a));
+
+        // fixes bug in tealr which causes syntax errors in teal due to duplicate fields (from having both getters and setters)
+        tw.given_types.iter_mut().for_each(|tg| {
+            if let TypeGenerator::Record(rg) = tg {
+                rg.fields
+                    .sort_by(|f1, f2| f1.name.deref().cmp(f2.name.deref()));
+                rg.fields.dedup_by(|a, b| a.name == b.name);
+                rg.static_fields
+                    .sort_by(|f1, f2| f1.name.deref().cmp(f2.name.deref()));
+                rg.static_fields.dedup_by(|a, b| a.name == b.name);
+            }
+        });
+
+        // these are generated before names are escaped, so they can still look up their documentation
+        let markdown = markdown::generate(&docs_name, &tw);
+        let lua_ls = lua_ls::generate(&docs_name, &tw);
+        #[cfg(feature = "luau")]
+        let luau = luau::generate(&tw);
+
+        tw.given_types.iter_mut().for_each(|tg| {
+            if let TypeGenerator::Record(rg) = tg {
+                for field in rg.fields.iter_mut().chain(rg.static_fields.iter_mut()) {
+                    escape_name(&mut field.name);
+                }
+                for func in rg
+                    .functions
+                    .iter_mut()
+                    .chain(rg.mut_functions.iter_mut())
+                    .chain(rg.methods.iter_mut())
+                    .chain(rg.mut_methods.iter_mut())
+                {
+                    escape_name(&mut func.name);
+                }
+            }
+        });
+
+        // going through a `Value` sorts the keys of the documentation maps, which keeps the output stable between runs
+        let json = serde_json::to_value(&tw)
+            .and_then(|json| serde_json::to_string_pretty(&json))
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+
+        // tealr deprecated `generate_global` when generating `.d.tl` files moved into the external `tealr_doc_gen` tool,
+        // which reads the json above. The method still works and is the only way to generate the declarations
+        // without having that tool installed
+        #[allow(deprecated)]
+        let definitions = tw
+            .generate_global(&docs_name)
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+
+        for (extension, contents) in [
+            ("json", &json),
+            ("md", &markdown),
+            ("d.tl", &definitions),
+            ("lua", &lua_ls),
+        ] {
+            write_file(
+                &output_dir.join(format!("{docs_name}.{extension}")),
+                contents,
+            )?;
+        }
+
+        #[cfg(feature = "luau")]
+        write_file(&output_dir.join(format!("{docs_name}.d.luau")), &luau)?;
+
+        Ok(definitions)
+    }
+}
+
+/// Copies the declarations to `assets/scripts/types/types.d.tl`, and creates a `tlconfig.lua` file next to them if there is none
+#[cfg(feature = "teal")]
+fn sync_teal_definitions(definitions: &str) -> Result<(), ScriptError> {
+    let script_asset_path = &FileAssetReader::get_base_path()
+        .join("assets")
+        .join("scripts");
+
+    // now manage the definition (d.tl) file
+    let definition_directory = script_asset_path.join("types");
+    fs::create_dir_all(&definition_directory).map_err(|e| {
+        ScriptError::DocGenError(format!(
+            "Could not create `{}` directories: {e}",
+            &definition_directory.display()
+        ))
+    })?;
+    write_file(&definition_directory.join("types.d.tl"), definitions)?;
+
+    // finally create a tlconfig.lua file if doesn't exist
+    // we do this to avoid problems with varying teal configurations
+    // keep em settings consistent everywhere
+    let tl_config_path = script_asset_path.join("tlconfig.lua");
+    if !tl_config_path.exists() {
+        write_file(&tl_config_path, DEFAULT_TEAL_CONFIG)?;
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), ScriptError> {
+    File::create(path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.flush()
+        })
+        .map_err(|e| ScriptError::DocGenError(format!("Could not write `{}`: {e}", path.display())))
+}
+
+/// Retrieves the documentation of a field or function of the record, or an empty string
+fn documentation<'a>(rg: &'a RecordGenerator, name: &NameContainer) -> &'a str {
+    rg.documentation.get(name).map(String::as_str).unwrap_or("")
+}
+
+fn name_to_str(name: &NameContainer) -> String {
+    String::from_utf8_lossy(name).into_owned()
+}
+
+/// Checks if the name can be used as is in Lua code
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !LUA_KEYWORDS.contains(&name)
+}
+
+/// Collects the names of all generic types used by the type
+fn generic_names(ty: &Type) -> Vec<String> {
+    match ty {
+        Type::Single(single) if single.kind.is_generic() => vec![single.name.to_string()],
+        Type::Single(_) => Vec::default(),
+        Type::Array(inner) => generic_names(inner),
+        Type::Map(MapRepresentation { key, value }) => {
+            let mut names = generic_names(key);
+            names.extend(generic_names(value));
+            names
+        }
+        Type::Or(types) | Type::Tuple(types) => types.iter().flat_map(generic_names).collect(),
+        Type::Function(FunctionRepresentation { params, returns }) => params
+            .iter()
+            .map(|p| &p.ty)
+            .chain(returns)
+            .flat_map(generic_names)
+            .collect(),
+    }
+}
+
+/// List of Lua reserved keywords
+const LUA_KEYWORDS: &[&str] = &[
+    "false", "true", "nil", // Values
+    "and", "not", "or", // Operators
+    "if", "then", "else", "elseif", "end", // If-Else
+    "for", "in", "break", "do", "repeat", "until", "while", "goto", // Loops
+    "function", "return", // Funcs
+    "local",  // Declarations
+];
+
+/// Escapes a name of a table field, if that table field is a reserved keyword (including teal's `record`).
+///
+/// ## Background
+///
+/// String keys in a Lua table are allowed to be anything, even reserved
+/// keywords. By default when tealr generates the type definition for a table
+/// field, the string it generates is `{name} : {type}`. This causes a syntax
+/// error when writing a bare keyword, since `nil : {type}` is considered trying
+/// to add a type to the *value* nil (which is invalid).
+///
+/// To get around this tealr allows us to escape table fields using the
+/// `["{name}"] : {value}` syntax. This function detects if a name is one of the
+/// Lua reserved words and fixes it if so.
+fn escape_name(raw: &mut NameContainer) {
+    let Ok(name) = str::from_utf8(raw) else {
+        return;
+    };
+    if LUA_KEYWORDS.contains(&name) || name == "record" {
+        let mapped = format!("[\"{name}\"]");
+        *raw = NameContainer::from(Cow::Owned(mapped));
+    }
+}
@@ -12,6 +12,8 @@ use tealr::mlu::mlua::{prelude::*, Function};
 
 pub mod assets;
 pub mod docs;
+#[cfg(feature = "teal_lint")]
+mod lint;
 pub mod util;
 pub use tealr;
 pub mod prelude {
@@ -102,6 +104,16 @@ impl<A: LuaArg> ScriptHost for LuaScriptHost<A> {
             );
     }
 
+    #[cfg(feature = "teal_lint")]
+    fn lint_script(
+        &mut self,
+        script: &[u8],
+        script_data: &ScriptData,
+        _: &mut APIProviders<Self>,
+    ) -> Vec<ScriptError> {
+        lint::lint(script, script_data.name)
+    }
+
     fn load_script(
         &mut self,
         script: &[u8],
@@ -0,0 +1,52 @@
+use std::{env, fs, process, process::Command};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use bevy_mod_scripting_core::prelude::*;
+
+/// Type checks the script with `tl check` against the teal declarations in `assets/scripts`, see `types/types.d.tl`.
+///
+/// The script is checked as plain Lua, so only misuses of the declared API such as unknown fields
+/// or wrong argument types are reported. Returns no findings if `tl` cannot be run.
+pub(crate) fn lint(script: &[u8], name: &str) -> Vec<ScriptError> {
+    let scripts_dir = FileAssetReader::get_base_path()
+        .join("assets")
+        .join("scripts");
+    let file_name = format!("bevy_mod_scripting_lint_{}.lua", process::id());
+    let temp_file_path = env::temp_dir().join(&file_name);
+    if let Err(e) = fs::write(&temp_file_path, script) {
+        warn!("Could not lint script `{name}`: {e}");
+        return Vec::default();
+    }
+
+    let output = Command::new("tl")
+        .arg("check")
+        .arg(&temp_file_path)
+        .current_dir(scripts_dir)
+        .output();
+    let _ = fs::remove_file(&temp_file_path);
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            warn!("Could not run `tl check` to lint script `{name}`: {e}");
+            return Vec::default();
+        }
+    };
+
+    // findings are reported as `<file>:<line>:<column>: <message>`
+    let output = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, location) = line.split_once(&file_name)?;
+            let mut parts = location.strip_prefix(':')?.splitn(3, ':');
+            let line = parts.next()?.parse().ok()?;
+            let column = parts.next()?.parse().ok()?;
+            Some(ScriptError::LintWarning {
+                script: name.to_owned(),
+                line,
+                column,
+                msg: parts.next()?.trim().to_owned(),
+            })
+        })
+        .collect()
+}
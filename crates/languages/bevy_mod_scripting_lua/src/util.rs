@@ -130,6 +130,20 @@ macro_rules! __cfg_feature_luajit52 {
     ( $( $tok:tt )* ) => {};
 }
 
+#[cfg(any(feature = "lua52", feature = "luajit52"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_feature_any_lua52_luajit52 {
+    ( $( $tok:tt )* ) => { $( $tok )* }
+}
+
+#[cfg(not(any(feature = "lua52", feature = "luajit52")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_feature_any_lua52_luajit52 {
+    ( $( $tok:tt )* ) => {};
+}
+
 #[cfg(any(
     feature = "lua52",
     feature = "lua53",
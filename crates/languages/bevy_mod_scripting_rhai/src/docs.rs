@@ -1,17 +1,334 @@
+use std::{collections::HashMap, fmt::Write, fs, path::Path};
+
 use bevy_mod_scripting_core::prelude::*;
+use rhai::Engine;
+use serde_json::Value;
+
+/// Registers an API with an engine, usually by calling `attach_api` of the documented provider
+pub type RhaiEngineBuilder = fn(&mut Engine) -> Result<(), ScriptError>;
+
+/// A piece of Rhai documentation.
+///
+/// Each piece registers its API with a fresh engine, from which a `.d.rhai` definition file for Rhai's language server
+/// and a json file containing the metadata of every registered function are generated.
+/// Functions of the standard packages are not included.
+pub struct RhaiDocFragment {
+    name: &'static str,
+    builders: Vec<RhaiEngineBuilder>,
+}
 
-pub struct RhaiDocFragment;
+impl RhaiDocFragment {
+    pub fn new(name: &'static str, f: RhaiEngineBuilder) -> Self {
+        Self {
+            name,
+            builders: vec![f],
+        }
+    }
+}
 
 impl DocFragment for RhaiDocFragment {
-    fn merge(self, _o: Self) -> Self {
-        todo!()
+    fn merge(mut self, o: Self) -> Self {
+        self.builders.extend(o.builders);
+        self
     }
 
-    fn gen_docs(self) -> Result<(), ScriptError> {
-        todo!()
+    fn gen_docs(self, output_dir: &Path) -> Result<(), ScriptError> {
+        let mut engine = Engine::new();
+        for builder in self.builders {
+            builder(&mut engine)?;
+        }
+
+        let mut metadata = engine
+            .gen_fn_metadata_to_json(false)
+            .and_then(|json| serde_json::from_str(&json))
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+        stable_metadata(&mut metadata);
+
+        fs::write(
+            output_dir.join(format!("{}.d.rhai", self.name)),
+            definitions(&metadata),
+        )
+        .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+
+        let json = serde_json::to_string_pretty(&metadata)
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+        fs::write(output_dir.join(format!("{}.json", self.name)), json)
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+
+        Ok(())
     }
 
     fn name(&self) -> &'static str {
-        todo!()
+        self.name
+    }
+}
+
+/// Removes the function hashes, which are seeded differently by every process, and sorts the functions,
+/// which are otherwise ordered by their hashes. This keeps the metadata the same between runs.
+fn stable_metadata(json: &mut Value) {
+    match json {
+        Value::Object(map) => {
+            map.remove("baseHash");
+            map.remove("fullHash");
+            map.values_mut().for_each(stable_metadata);
+            if let Some(Value::Array(functions)) = map.get_mut("functions") {
+                functions.sort_by_cached_key(|function| function.to_string());
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(stable_metadata),
+        _ => {}
+    }
+}
+
+/// Generates a definition file for Rhai's language server from the function metadata of an engine
+fn definitions(metadata: &Value) -> String {
+    let custom_types = metadata["customTypes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|ty| Some((ty["typeName"].as_str()?, ty["displayName"].as_str()?)))
+        .collect::<HashMap<_, _>>();
+
+    let mut out = String::from("module static;\n\n");
+    write_module(&mut out, metadata, &custom_types);
+    out
+}
+
+fn write_module(out: &mut String, module: &Value, custom_types: &HashMap<&str, &str>) {
+    let mut first = true;
+    let mut separate = |out: &mut String| {
+        if !std::mem::take(&mut first) {
+            out.push_str("\n\n");
+        }
+    };
+
+    for (name, submodule) in module["modules"].as_object().into_iter().flatten() {
+        separate(out);
+        let _ = writeln!(out, "module {name} {{");
+        write_module(out, submodule, custom_types);
+        out.push_str("\n}");
+    }
+
+    for function in module["functions"].as_array().into_iter().flatten() {
+        if function["access"] == "private" {
+            continue;
+        }
+        separate(out);
+        write_function(out, function, custom_types);
+    }
+}
+
+fn write_function(out: &mut String, function: &Value, custom_types: &HashMap<&str, &str>) {
+    for comment in function["docComments"].as_array().into_iter().flatten() {
+        let _ = writeln!(out, "{}", comment.as_str().unwrap_or_default());
+    }
+
+    let name = function["name"].as_str().unwrap_or_default();
+    let operator = !name.contains('$') && !name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if operator {
+        out.push_str("op ");
+    } else {
+        out.push_str("fn ");
+    }
+    if let Some(name) = name.strip_prefix("get$") {
+        let _ = write!(out, "get {name}(");
+    } else if let Some(name) = name.strip_prefix("set$") {
+        let _ = write!(out, "set {name}(");
+    } else {
+        let _ = write!(out, "{name}(");
+    }
+
+    // functions registered without type information, such as raw functions, list neither their parameters nor their return type
+    let params = function["params"].as_array().map(Vec::as_slice);
+    let num_params = function["numParams"].as_u64().unwrap_or_default() as usize;
+    for i in 0..num_params {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        let param = params.and_then(|params| params.get(i));
+        let ty = param
+            .and_then(|param| param["type"].as_str())
+            .map_or_else(|| "?".to_owned(), |ty| script_type_name(ty, custom_types));
+        if operator {
+            out.push_str(&ty);
+        } else {
+            let name = param
+                .and_then(|param| param["name"].as_str())
+                .unwrap_or("_");
+            let _ = write!(out, "{name}: {ty}");
+        }
+    }
+
+    let returns = match function["returnType"].as_str() {
+        Some(ty) => script_type_name(ty, custom_types),
+        None if params.is_none() && num_params > 0 => "?".to_owned(),
+        None => "()".to_owned(),
+    };
+    let _ = write!(out, ") -> {returns};");
+}
+
+/// Converts the Rust type name of a parameter or return type into the name scripts know the type by.
+///
+/// Paths are removed, registered types use their display name and fallible functions return the type of their value,
+/// e.g. `core::result::Result<alloc::string::String,alloc::boxed::Box<rhai::EvalAltResult>>` becomes `String`.
+fn script_type_name(ty: &str, custom_types: &HashMap<&str, &str>) -> String {
+    let ty = ty.trim();
+    let ty = ty
+        .strip_prefix("&mut ")
+        .or_else(|| ty.strip_prefix('&'))
+        .unwrap_or(ty)
+        .trim();
+    if let Some(name) = custom_types.get(ty) {
+        return (*name).to_owned();
+    }
+
+    let (path, args) = match ty.split_once('<') {
+        Some((path, args)) => (path, generic_args(args.strip_suffix('>').unwrap_or(args))),
+        None => (ty, Vec::default()),
+    };
+    let args = args
+        .into_iter()
+        .map(|arg| script_type_name(arg, custom_types))
+        .collect::<Vec<_>>();
+    let name = path.rsplit("::").next().unwrap_or(path);
+
+    match name {
+        "Result" | "RhaiResultOf" if !args.is_empty() => args[0].clone(),
+        "Dynamic" | "RhaiResult" => "?".to_owned(),
+        "i64" | "INT" => "int".to_owned(),
+        "f64" | "FLOAT" => "float".to_owned(),
+        "str" | "String" | "ImmutableString" | "SmartString" => "String".to_owned(),
+        "Vec" if args == ["?"] => "Array".to_owned(),
+        "BTreeMap" if args.last().is_some_and(|value| value == "?") => "Map".to_owned(),
+        _ if args.is_empty() => name.to_owned(),
+        _ => format!("{name}<{}>", args.join(", ")),
+    }
+}
+
+/// Splits the arguments of a generic type at the commas which are not nested in other generic types or tuples
+fn generic_args(args: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut split = Vec::default();
+    for (i, c) in args.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(&args[start..]);
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhai::EvalAltResult;
+    use serde_json::json;
+
+    #[derive(Clone)]
+    struct Thing;
+
+    fn metadata(engine: &Engine) -> Value {
+        let mut metadata = serde_json::from_str(&engine.gen_fn_metadata_to_json(false).unwrap())
+            .expect("Invalid metadata");
+        stable_metadata(&mut metadata);
+        metadata
+    }
+
+    #[test]
+    fn test_definitions() {
+        let mut engine = Engine::new();
+        engine
+            .register_type_with_name::<Thing>("Thing")
+            .register_fn("make", || -> Result<Thing, Box<EvalAltResult>> {
+                Ok(Thing)
+            })
+            .register_fn("size", |_: &mut Thing| -> Result<i64, Box<EvalAltResult>> {
+                Ok(1)
+            })
+            .register_fn("clear", |_: &mut Thing| ())
+            .register_fn("==", |_: &mut Thing, _: Thing| true)
+            .register_get("name", |_: &mut Thing| String::from("thing"));
+
+        let definitions = definitions(&metadata(&engine));
+
+        assert!(definitions.starts_with("module static;\n\n"));
+        for definition in [
+            "op ==(Thing, Thing) -> bool;",
+            "fn clear(_: Thing) -> ();",
+            "fn get name(_: Thing) -> string;",
+            "fn make() -> Thing;",
+            "fn size(_: Thing) -> int;",
+        ] {
+            assert!(
+                definitions.contains(definition),
+                "Expected `{definition}` in:\n{definitions}"
+            );
+        }
+        assert!(!definitions.contains("EvalAltResult"));
+    }
+
+    #[test]
+    fn test_script_type_name() {
+        let custom_types = HashMap::from_iter([("my_crate::Thing", "Thing")]);
+
+        for (ty, expected) in [
+            ("&mut my_crate::Thing", "Thing"),
+            ("rhai::types::dynamic::Dynamic", "?"),
+            ("core::result::Result<(),alloc::boxed::Box<rhai::types::error::EvalAltResult>>", "()"),
+            (
+                "core::result::Result<alloc::vec::Vec<rhai::types::dynamic::Dynamic>,alloc::boxed::Box<rhai::types::error::EvalAltResult>>",
+                "Array",
+            ),
+            (
+                "core::option::Option<core::result::Result<my_crate::Thing,alloc::string::String>>",
+                "Option<Thing>",
+            ),
+            ("std::collections::HashMap<i64,(f64,bool)>", "HashMap<int, (f64,bool)>"),
+        ] {
+            assert_eq!(script_type_name(ty, &custom_types), expected, "{ty}");
+        }
+    }
+
+    #[test]
+    fn test_stable_metadata() {
+        let mut metadata = json!({
+            "functions": [
+                { "name": "b", "baseHash": 1, "fullHash": 2 },
+                { "name": "a", "baseHash": 3, "fullHash": 4 },
+            ],
+            "modules": {
+                "m": { "functions": [{ "name": "c", "baseHash": 5, "fullHash": 6 }] }
+            }
+        });
+
+        stable_metadata(&mut metadata);
+
+        assert_eq!(
+            metadata,
+            json!({
+                "functions": [{ "name": "a" }, { "name": "b" }],
+                "modules": { "m": { "functions": [{ "name": "c" }] } }
+            })
+        );
+    }
+
+    #[test]
+    fn test_stable_metadata_same_between_engines() {
+        let engine = || {
+            let mut engine = Engine::new();
+            for name in ["one", "two", "three", "four"] {
+                engine.register_fn(name, |x: i64| x);
+            }
+            engine
+        };
+
+        assert_eq!(metadata(&engine()), metadata(&engine()));
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
     assets::{RhaiFile, RhaiLoader},
     docs::RhaiDocFragment,
+    settings::RhaiSettings,
 };
 use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
 use bevy_mod_scripting_core::{prelude::*, systems::*, world::WorldPointerGuard};
@@ -9,17 +10,33 @@ use std::marker::PhantomData;
 
 pub mod assets;
 pub mod docs;
+#[cfg(feature = "lint")]
+mod lint;
+pub mod settings;
 pub use rhai;
 pub mod prelude {
     pub use crate::{
         assets::{RhaiFile, RhaiLoader},
         docs::RhaiDocFragment,
+        settings::RhaiSettings,
         RhaiContext, RhaiEvent, RhaiScriptHost,
     };
     pub use rhai;
     pub use rhai::{Engine, FuncArgs};
 }
 
+/// Variables available to every script without being declared
+pub(crate) const SCRIPT_VARIABLES: [&str; 3] = ["state", "world", "entity"];
+
+/// A scope declaring the variables available to every script, for compiling scripts with strict variables
+pub(crate) fn declared_variables() -> Scope<'static> {
+    let mut scope = Scope::new();
+    for variable in SCRIPT_VARIABLES {
+        scope.push(variable, ());
+    }
+    scope
+}
+
 #[derive(Resource)]
 pub struct RhaiScriptHost<A: FuncArgs + Send> {
     pub engine: Engine,
@@ -31,9 +48,7 @@ impl<A: FuncArgs + Send> Default for RhaiScriptHost<A> {
     fn default() -> Self {
         let mut e = Engine::new();
         // prevent shadowing of `state`,`world` and `entity` in variable in scripts
-        e.on_def_var(|_, info, _| {
-            Ok(info.name() != "state" && info.name() != "world" && info.name() != "entity")
-        });
+        e.on_def_var(|_, info, _| Ok(!SCRIPT_VARIABLES.contains(&info.name())));
 
         Self {
             engine: e,
@@ -47,6 +62,13 @@ pub struct RhaiContext {
     pub scope: Scope<'static>,
 }
 
+/// Retrieves the id of the script which called the native function with the given context.
+///
+/// Returns `None` if the call did not originate from a script hook run by the script host.
+pub fn calling_script_id(ctx: &NativeCallContext) -> Option<u32> {
+    ctx.tag().and_then(|tag| tag.clone().try_cast::<u32>())
+}
+
 #[derive(Clone, Event)]
 /// A Rhai Hook. The result of creating this event will be
 /// a call to the lua script with the hook_name and the given arguments
@@ -96,7 +118,14 @@ impl<A: FuncArgs + Send + Clone + Sync + 'static> ScriptHost for RhaiScriptHost<
             // setup engine
             .add_systems(
                 Startup,
-                |mut providers: ResMut<APIProviders<Self>>, mut host: ResMut<Self>| {
+                |mut providers: ResMut<APIProviders<Self>>,
+                 mut host: ResMut<Self>,
+                 settings: Option<Res<RhaiSettings>>| {
+                    if let Some(settings) = settings {
+                        settings
+                            .apply(&mut host.engine)
+                            .expect("Error in applying rhai settings");
+                    }
                     providers
                         .attach_all(&mut host.engine)
                         .expect("Error in adding api's for rhai");
@@ -113,6 +142,19 @@ impl<A: FuncArgs + Send + Clone + Sync + 'static> ScriptHost for RhaiScriptHost<
         providers.setup_all(script_data, ctx)
     }
 
+    #[cfg(feature = "lint")]
+    fn lint_script(
+        &mut self,
+        script: &[u8],
+        script_data: &ScriptData,
+        _: &mut APIProviders<Self>,
+    ) -> Vec<ScriptError> {
+        match std::str::from_utf8(script) {
+            Ok(script) => lint::lint(&mut self.engine, script, script_data.name),
+            Err(_) => Vec::default(),
+        }
+    }
+
     fn load_script(
         &mut self,
         script: &[u8],
@@ -120,9 +162,11 @@ impl<A: FuncArgs + Send + Clone + Sync + 'static> ScriptHost for RhaiScriptHost<
         _: &mut APIProviders<Self>,
     ) -> Result<Self::ScriptContext, ScriptError> {
         let mut scope = Scope::new();
+        // the variables set up by the host and API providers are declared in case of strict variables
         let mut ast = self
             .engine
-            .compile(
+            .compile_with_scope(
+                &declared_variables(),
                 std::str::from_utf8(script).map_err(|e| ScriptError::FailedToLoad {
                     script: script_data.name.to_owned(),
                     msg: e.to_string(),
@@ -164,7 +208,9 @@ impl<A: FuncArgs + Send + Clone + Sync + 'static> ScriptHost for RhaiScriptHost<
                     continue;
                 };
 
-                match self.engine.call_fn(
+                // the tag identifies the calling script to native functions
+                match self.engine.call_fn_with_options(
+                    CallFnOptions::new().with_tag(fd.sid),
                     &mut ctx.scope,
                     &ctx.ast,
                     &event.hook_name,
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy_mod_scripting_core::prelude::*;
+use rhai::{ASTNode, Engine, Expr, FnCallExpr, ParseErrorType, Position, Stmt};
+
+use crate::declared_variables;
+
+/// Functions handled by the compiler itself, these are never registered with the engine
+const KEYWORD_FUNCTIONS: [&str; 10] = [
+    "print",
+    "debug",
+    "type_of",
+    "Fn",
+    "call",
+    "curry",
+    "is_shared",
+    "is_def_fn",
+    "is_def_var",
+    "eval",
+];
+
+/// Checks the script for undefined variables and calls to functions which are not registered with the engine,
+/// either by name or by number of arguments.
+///
+/// Scripts which do not compile are skipped, the syntax error is reported when loading the script.
+pub(crate) fn lint(engine: &mut Engine, script: &str, name: &str) -> Vec<ScriptError> {
+    let mut warnings = Vec::default();
+
+    // with strict variables enabled in the settings undefined variables are reported when loading the script
+    let strict_variables = engine.strict_variables();
+    engine.set_strict_variables(true);
+    let strict = engine.compile_with_scope(&declared_variables(), script);
+    engine.set_strict_variables(false);
+    let ast = engine.compile_with_scope(&declared_variables(), script);
+    engine.set_strict_variables(strict_variables);
+
+    let Ok(ast) = ast else {
+        return warnings;
+    };
+    if let Err(e) = strict {
+        match e.err_type() {
+            ParseErrorType::VariableUndefined(variable) if !strict_variables => {
+                warnings.push(warning(
+                    name,
+                    e.position(),
+                    format!("Undefined variable `{variable}`"),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let mut native = HashMap::<String, HashSet<usize>>::default();
+    for (function, arity) in engine.collect_fn_metadata(
+        None,
+        |info| {
+            info.namespace
+                .is_empty()
+                .then(|| (info.metadata.name.to_string(), info.metadata.num_params))
+        },
+        true,
+    ) {
+        native.entry(function).or_default().insert(arity);
+    }
+
+    let mut scripted = HashMap::<&str, HashSet<usize>>::default();
+    let mut variables = HashSet::<String>::default();
+    for function in ast.iter_functions() {
+        scripted
+            .entry(function.name)
+            .or_default()
+            .insert(function.params.len());
+        variables.extend(function.params.iter().map(|p| p.to_string()));
+    }
+    ast.walk(&mut |path| {
+        if let Some(ASTNode::Stmt(Stmt::Var(var, ..))) = path.last() {
+            variables.insert(var.0.name.to_string());
+        }
+        true
+    });
+
+    ast.walk(&mut |path| {
+        let (call, is_method, position) = match path.last() {
+            Some(ASTNode::Expr(Expr::FnCall(call, position)))
+            | Some(ASTNode::Stmt(Stmt::FnCall(call, position))) => (call, false, *position),
+            Some(ASTNode::Expr(Expr::MethodCall(call, position))) => (call, true, *position),
+            _ => return true,
+        };
+        if let Some(msg) = check_call(call, is_method, &native, &scripted, &variables) {
+            warnings.push(warning(name, position, msg));
+        }
+        true
+    });
+
+    warnings
+}
+
+/// Returns a description of what is wrong with the call, if anything
+fn check_call(
+    call: &FnCallExpr,
+    is_method: bool,
+    native: &HashMap<String, HashSet<usize>>,
+    scripted: &HashMap<&str, HashSet<usize>>,
+    variables: &HashSet<String>,
+) -> Option<String> {
+    let name = call.name.as_str();
+    // operators, functions from imported modules and function pointers stored in variables cannot be checked
+    if call.is_qualified()
+        || call.op_token.is_some()
+        || !name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        || KEYWORD_FUNCTIONS.contains(&name)
+        || (!is_method && variables.contains(name))
+    {
+        return None;
+    }
+
+    // methods receive the object they are called on as the first argument, unless defined in the script
+    let args = call.args.len();
+    let native_args = if is_method { args + 1 } else { args };
+    let native_arities = native.get(name);
+    let scripted_arities = scripted.get(name);
+    if native_arities.is_some_and(|a| a.contains(&native_args))
+        || scripted_arities.is_some_and(|a| a.contains(&args))
+    {
+        return None;
+    }
+
+    if native_arities.is_none() && scripted_arities.is_none() {
+        let kind = if is_method { "method" } else { "function" };
+        return Some(format!("Call to unknown {kind} `{name}`"));
+    }
+
+    let mut expected = native_arities
+        .into_iter()
+        .flatten()
+        .map(|a| if is_method { a.saturating_sub(1) } else { *a })
+        .chain(scripted_arities.into_iter().flatten().copied())
+        .collect::<Vec<_>>();
+    expected.sort_unstable();
+    expected.dedup();
+    Some(format!(
+        "`{name}` called with {args} argument(s), expected {}",
+        expected
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" or ")
+    ))
+}
+
+fn warning(script: &str, position: Position, msg: String) -> ScriptError {
+    ScriptError::LintWarning {
+        script: script.to_owned(),
+        line: position.line().unwrap_or_default(),
+        column: position.position().unwrap_or_default(),
+        msg,
+    }
+}
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use bevy_mod_scripting_core::prelude::*;
+use rhai::{Engine, OptimizationLevel};
+
+use crate::SCRIPT_VARIABLES;
+
+type EngineSetup = Box<dyn Fn(&mut Engine) + Send + Sync>;
+
+/// Configures the engine of the [`crate::RhaiScriptHost`].
+///
+/// Insert this resource before the app starts, the settings are applied on startup before any APIs are attached:
+/// ```rust,ignore
+/// app.insert_resource(
+///     RhaiSettings::default()
+///         .with_strict_variables(true)
+///         .with_max_call_levels(32)
+///         .with_disabled_symbol("eval")
+///         .with_custom_operator("#", 160)
+///         .with_engine_setup(|engine| {
+///             engine.register_fn("#", |a: i64, b: i64| a * 10 + b);
+///         }),
+/// );
+/// ```
+#[derive(Resource)]
+pub struct RhaiSettings {
+    /// The optimization level scripts are compiled with, `Simple` by default
+    pub optimization_level: OptimizationLevel,
+    /// If enabled, scripts using variables which were never declared fail to load.
+    /// `world`, `entity` and `state` are always declared
+    pub strict_variables: bool,
+    /// The maximum depth of nested function calls, Rhai's default if `None`
+    pub max_call_levels: Option<usize>,
+    /// Keywords and operators scripts may not use, e.g. `eval` or `while`
+    pub disabled_symbols: Vec<String>,
+    /// Custom operators along with their precedence, the functions implementing them must be registered separately
+    pub custom_operators: Vec<(String, u8)>,
+    /// If enabled, scripts may not declare variables shadowing `world`, `entity` or `state`. Enabled by default
+    pub protect_script_variables: bool,
+    /// Applied last, in order
+    engine_setups: Vec<EngineSetup>,
+}
+
+impl Default for RhaiSettings {
+    fn default() -> Self {
+        Self {
+            optimization_level: OptimizationLevel::Simple,
+            strict_variables: false,
+            max_call_levels: None,
+            disabled_symbols: Default::default(),
+            custom_operators: Default::default(),
+            protect_script_variables: true,
+            engine_setups: Default::default(),
+        }
+    }
+}
+
+impl RhaiSettings {
+    pub fn with_optimization_level(mut self, optimization_level: OptimizationLevel) -> Self {
+        self.optimization_level = optimization_level;
+        self
+    }
+
+    pub fn with_strict_variables(mut self, strict_variables: bool) -> Self {
+        self.strict_variables = strict_variables;
+        self
+    }
+
+    pub fn with_max_call_levels(mut self, max_call_levels: usize) -> Self {
+        self.max_call_levels = Some(max_call_levels);
+        self
+    }
+
+    pub fn with_disabled_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.disabled_symbols.push(symbol.into());
+        self
+    }
+
+    pub fn with_custom_operator(mut self, keyword: impl Into<String>, precedence: u8) -> Self {
+        self.custom_operators.push((keyword.into(), precedence));
+        self
+    }
+
+    pub fn with_protected_script_variables(mut self, protect_script_variables: bool) -> Self {
+        self.protect_script_variables = protect_script_variables;
+        self
+    }
+
+    /// Adds a function configuring anything else about the engine, e.g. custom syntax or functions implementing custom operators
+    pub fn with_engine_setup(
+        mut self,
+        setup: impl Fn(&mut Engine) + Send + Sync + 'static,
+    ) -> Self {
+        self.engine_setups.push(Box::new(setup));
+        self
+    }
+
+    /// Applies the settings to the engine
+    #[allow(deprecated)]
+    pub fn apply(&self, engine: &mut Engine) -> Result<(), ScriptError> {
+        engine
+            .set_optimization_level(self.optimization_level)
+            .set_strict_variables(self.strict_variables);
+
+        if let Some(max_call_levels) = self.max_call_levels {
+            engine.set_max_call_levels(max_call_levels);
+        }
+
+        for symbol in &self.disabled_symbols {
+            engine.disable_symbol(symbol.as_str());
+        }
+
+        for (keyword, precedence) in &self.custom_operators {
+            engine
+                .register_custom_operator(keyword, *precedence)
+                .map_err(|e| {
+                    ScriptError::Other(format!("Invalid custom operator `{keyword}`: {e}"))
+                })?;
+        }
+
+        if self.protect_script_variables {
+            engine.on_def_var(|_, info, _| Ok(!SCRIPT_VARIABLES.contains(&info.name())));
+        } else {
+            engine.on_def_var(|_, _, _| Ok(true));
+        }
+
+        for setup in &self.engine_setups {
+            setup(engine);
+        }
+
+        Ok(())
+    }
+}
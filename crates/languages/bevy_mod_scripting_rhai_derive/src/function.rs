@@ -0,0 +1,375 @@
+#![allow(clippy::manual_unwrap_or_default)] // from darling
+
+use std::collections::HashMap;
+
+use bevy_mod_scripting_common::input::{IdentifierRenamingVisitor, SimpleType};
+use darling::{util::Flag, FromAttributes};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    spanned::Spanned, visit_mut::VisitMut, Block, FnArg, Meta, Pat, PatIdent, PatType, Path,
+    ReturnType, TraitItemFn, Type,
+};
+
+use crate::{
+    types::{rhai_path, to_dynamic, RhaiArg, Unpacked},
+    PROXY_PREFIX, RAW_OUT_ALIAS, SELF_ALIAS,
+};
+
+/// The attributes of functions in the `functions[..]` list, shared with the Lua proxies.
+///
+/// Only the attributes affecting how the function is called are used, the rest are ignored
+#[derive(Debug, FromAttributes)]
+#[darling(attributes(lua), allow_unknown_fields)]
+pub struct FunctionAttributes {
+    /// Raw functions receive the Lua context and are not generated
+    pub raw: Flag,
+
+    /// Functions implementing one of the operators Rhai supports are registered as that operator
+    #[darling(default)]
+    pub metamethod: Option<Ident>,
+
+    pub skip: Flag,
+
+    pub output: Option<Meta>,
+
+    /// If passed will call the function through the given trait
+    pub as_trait: Option<Path>,
+}
+
+impl FunctionAttributes {
+    /// Checks if the output is marked as a proxy with `output(proxy)`
+    pub fn is_proxy_output(&self) -> bool {
+        self.output
+            .as_ref()
+            .and_then(|meta| meta.require_list().ok())
+            .is_some_and(|list| list.tokens.to_string() == "proxy")
+    }
+}
+
+/// Keywords reserved by Rhai which cannot be used as function names
+const RESERVED_KEYWORDS: &[&str] = &[
+    "var",
+    "static",
+    "shared",
+    "goto",
+    "exit",
+    "match",
+    "case",
+    "public",
+    "protected",
+    "new",
+    "use",
+    "with",
+    "module",
+    "package",
+    "super",
+    "thread",
+    "spawn",
+    "go",
+    "await",
+    "async",
+    "sync",
+    "yield",
+    "default",
+    "void",
+    "null",
+    "nil",
+];
+
+/// Where a function is registered with Rhai
+pub enum Registration {
+    /// A method or operator of the proxy type, registered with its `TypeBuilder`
+    Type(String),
+    /// A function without a receiver, registered in the static module named after the type
+    Static(String),
+}
+
+/// How the closure registered for a name calls the proxied function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallVariant {
+    /// Calls the function as is
+    Direct,
+    /// Negates the boolean output of the function, used for `!=`
+    Negated,
+    /// Takes the two arguments in reverse order, used for `>` and `>=`
+    Swapped,
+}
+
+/// A function from the `functions[..]` list which Rhai can call
+pub struct Function {
+    pub name: Ident,
+    pub attrs: FunctionAttributes,
+    pub receiver: Option<RhaiArg>,
+    pub args: Vec<RhaiArg>,
+    pub output: SimpleType,
+    pub default: Option<Block>,
+    pub is_unsafe: bool,
+    pub span: Span,
+}
+
+impl Function {
+    /// Parses a function, returns `None` if it should not be generated for Rhai,
+    /// i.e. because it's skipped, raw or has arguments or outputs Rhai cannot represent
+    pub fn new(proxied_type_path: &Path, function: TraitItemFn) -> darling::Result<Option<Self>> {
+        let attrs = FunctionAttributes::from_attributes(&function.attrs)?;
+        if attrs.skip.is_present() || attrs.raw.is_present() {
+            return Ok(None);
+        }
+
+        let type_map = HashMap::from_iter([(
+            proxied_type_path.segments.last().unwrap().ident.clone(),
+            None,
+        )]);
+
+        let mut receiver = None;
+        let mut args = Vec::default();
+        for arg in &function.sig.inputs {
+            let converted = match arg {
+                FnArg::Receiver(receiver) => {
+                    SimpleType::new_from_fn_arg(PROXY_PREFIX, arg, proxied_type_path, &type_map)
+                        .ok()
+                        .and_then(|type_| {
+                            RhaiArg::new(Ident::new(SELF_ALIAS, receiver.span()), &type_)
+                        })
+                }
+                FnArg::Typed(PatType { attrs, pat, ty, .. }) => {
+                    let Pat::Ident(PatIdent { ident, .. }) = pat.as_ref() else {
+                        return Err(darling::Error::custom("Unsupported parameter pattern"));
+                    };
+                    let is_proxy = attrs.iter().any(|a| a.path().is_ident("proxy"));
+                    simple_type(ty, proxied_type_path, is_proxy)
+                        .and_then(|type_| RhaiArg::new(ident.clone(), &type_))
+                }
+            };
+
+            let Some(converted) = converted else {
+                return Ok(None);
+            };
+            match arg {
+                FnArg::Receiver(_) => receiver = Some(converted),
+                FnArg::Typed(_) => args.push(converted),
+            }
+        }
+
+        let is_proxy = attrs.is_proxy_output();
+        let output = match &function.sig.output {
+            ReturnType::Default => Some(SimpleType::Unit),
+            ReturnType::Type(_, ty) => simple_type(ty, proxied_type_path, is_proxy),
+        };
+        let Some(output) = output
+            .filter(|output| to_dynamic(&format_ident!("{RAW_OUT_ALIAS}"), output, true).is_some())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            span: function.sig.span(),
+            name: function.sig.ident,
+            is_unsafe: function.sig.unsafety.is_some(),
+            attrs,
+            receiver,
+            args,
+            output,
+            default: function.default,
+        }))
+    }
+
+    /// The operators Rhai supports, keyed by the Lua metamethod implementing them
+    fn operator(&self) -> Option<&'static str> {
+        let metamethod = self.attrs.metamethod.as_ref()?.to_string();
+        Some(match metamethod.as_str() {
+            "Add" => "+",
+            "Sub" => "-",
+            "Mul" => "*",
+            "Div" => "/",
+            "Mod" => "%",
+            "Unm" => "-",
+            "Eq" => "==",
+            "Lt" => "<",
+            "Le" => "<=",
+            "ToString" => "to_string",
+            _ => return None,
+        })
+    }
+
+    /// Returns the names the function is registered under.
+    ///
+    /// Rhai does not derive operators from one another so `!=` and `to_debug` are registered alongside `==` and `to_string`,
+    /// and `>` and `>=` alongside `<` and `<=` with their arguments swapped.
+    /// `new` is reserved in Rhai, so constructors are registered as a global function named after the type, i.e. `Vec3(1.0, 2.0, 3.0)`,
+    /// other reserved names are suffixed with an underscore
+    pub fn registrations(&self, script_name: &str) -> Vec<(Registration, CallVariant)> {
+        let direct = |registration| vec![(registration, CallVariant::Direct)];
+        match self.operator() {
+            Some("==") => vec![
+                (Registration::Type("==".to_owned()), CallVariant::Direct),
+                (Registration::Type("!=".to_owned()), CallVariant::Negated),
+            ],
+            Some("<") => vec![
+                (Registration::Type("<".to_owned()), CallVariant::Direct),
+                (Registration::Type(">".to_owned()), CallVariant::Swapped),
+            ],
+            Some("<=") => vec![
+                (Registration::Type("<=".to_owned()), CallVariant::Direct),
+                (Registration::Type(">=".to_owned()), CallVariant::Swapped),
+            ],
+            Some("to_string") => vec![
+                (
+                    Registration::Type("to_string".to_owned()),
+                    CallVariant::Direct,
+                ),
+                (
+                    Registration::Type("to_debug".to_owned()),
+                    CallVariant::Direct,
+                ),
+            ],
+            Some(operator) => direct(Registration::Type(operator.to_owned())),
+            None if self.receiver.is_some() => direct(Registration::Type(self.name.to_string())),
+            None if self.name == "new" => direct(Registration::Type(script_name.to_owned())),
+            None if RESERVED_KEYWORDS.contains(&self.name.to_string().as_str()) => {
+                direct(Registration::Static(format!("{}_", self.name)))
+            }
+            None => direct(Registration::Static(self.name.to_string())),
+        }
+    }
+
+    /// Generates the closure registered with Rhai, converting the arguments, calling the proxied function and converting its output.
+    ///
+    /// Receivers are taken by mutable reference, which allows the function to be called as a method in Rhai,
+    /// unless the arguments are swapped, since Rhai only passes the first argument by reference
+    pub fn generate_closure(
+        &self,
+        proxied_type_path: &Path,
+        proxy_ident: &Ident,
+        variant: CallVariant,
+    ) -> TokenStream {
+        let rhai = rhai_path();
+        let out = format_ident!("{RAW_OUT_ALIAS}", span = self.span);
+        let result = quote!(Result<#rhai::Dynamic, Box<#rhai::EvalAltResult>>);
+
+        let swapped = variant == CallVariant::Swapped;
+        let mut params = self
+            .receiver
+            .iter()
+            .map(|receiver| {
+                let name = &receiver.name;
+                if swapped {
+                    quote_spanned!(self.span=> #name: #proxy_ident)
+                } else {
+                    quote_spanned!(self.span=> #name: &mut #proxy_ident)
+                }
+            })
+            .chain(self.args.iter().map(|arg| {
+                let name = &arg.name;
+                let rhai_type = &arg.rhai_type;
+                quote_spanned!(self.span=> mut #name: #rhai_type)
+            }))
+            .collect::<Vec<_>>();
+        if swapped {
+            params.reverse();
+        }
+
+        let args = self.receiver.iter().chain(self.args.iter());
+        let unpacked = args
+            .clone()
+            .filter_map(|arg| match &arg.unpacked {
+                Unpacked::Value(value) => {
+                    let name = &arg.name;
+                    Some(quote_spanned!(self.span=> let mut #name = #value;))
+                }
+                Unpacked::Borrowed { .. } => None,
+            })
+            .collect::<TokenStream>();
+
+        let call = self.generate_call(proxied_type_path);
+        let negation = (variant == CallVariant::Negated).then(|| quote!(let #out = !#out;));
+        // outputs which fail the conversion were filtered out when parsing the function
+        let converted = to_dynamic(&out, &self.output, true).unwrap_or_default();
+        let body = quote_spanned! {self.span=>
+            #call
+            #negation
+            Ok::<_, Box<#rhai::EvalAltResult>>(#converted)
+        };
+
+        // borrowed proxies are only borrowed for the duration of the call, via `val` or `val_mut`
+        let body = args.rev().fold(body, |body, arg| match arg.unpacked {
+            Unpacked::Borrowed { mutable } => {
+                let name = &arg.name;
+                let method = if mutable {
+                    quote!(val_mut)
+                } else {
+                    quote!(val)
+                };
+                quote_spanned! {self.span=>
+                    #name.#method(|mut #name| -> #result { #body })?
+                }
+            }
+            Unpacked::Value(_) => body,
+        });
+
+        let body = if self.is_unsafe {
+            quote!(unsafe { #body })
+        } else {
+            body
+        };
+
+        quote_spanned! {self.span=>
+            |#(#params),*| -> #result {
+                #unpacked
+                #body
+            }
+        }
+    }
+
+    /// Generates the statement calling the proxied function with the unpacked arguments and storing the output
+    fn generate_call(&self, proxied_type_path: &Path) -> TokenStream {
+        let out = format_ident!("{RAW_OUT_ALIAS}", span = self.span);
+        match &self.default {
+            Some(body) => {
+                let stmts = body.stmts.iter().cloned().map(|mut s| {
+                    IdentifierRenamingVisitor {
+                        target: "self",
+                        replacement: SELF_ALIAS,
+                    }
+                    .visit_stmt_mut(&mut s);
+                    s
+                });
+                quote_spanned! {self.span=>
+                    let #out = (|| {
+                        #(#stmts)*
+                    })();
+                }
+            }
+            None => {
+                let name = &self.name;
+                let path = match &self.attrs.as_trait {
+                    Some(trait_path) => quote_spanned!(self.span=> #trait_path::#name),
+                    None => quote_spanned!(self.span=> #proxied_type_path::#name),
+                };
+                let args = self
+                    .receiver
+                    .iter()
+                    .chain(self.args.iter())
+                    .map(|arg| &arg.name);
+                quote_spanned! {self.span=>
+                    let #out = #path(#(#args),*);
+                }
+            }
+        }
+    }
+}
+
+/// Converts a type in a function signature or field into a [`SimpleType`], with either every type or only `Self` proxied
+pub fn simple_type(type_: &Type, proxied_type_path: &Path, is_proxy: bool) -> Option<SimpleType> {
+    if is_proxy {
+        SimpleType::new_from_contextual_type_proxy_all(PROXY_PREFIX, type_, proxied_type_path)
+    } else {
+        let type_map = HashMap::from_iter([(
+            proxied_type_path.segments.last().unwrap().ident.clone(),
+            None,
+        )]);
+        SimpleType::new_from_contextual_type(PROXY_PREFIX, type_, proxied_type_path, &type_map)
+    }
+    .ok()
+}
@@ -1,13 +1,246 @@
+use bevy_mod_scripting_common::input::*;
+use darling::{FromAttributes, FromDeriveInput};
+use function::{simple_type, Function, FunctionAttributes, Registration};
 use proc_macro::TokenStream;
+use quote::*;
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Field, Ident, Index, Path, Variant};
+use types::{rhai_path, to_dynamic, RhaiArg, Unpacked};
 
-#[proc_macro]
-pub fn impl_lua_newtype(tokens: TokenStream) -> TokenStream {
-    // let newtype = parse_macro_input!(tokens as Newtype);
+pub(crate) mod function;
+pub(crate) mod types;
 
-    // implementor
-    //     .generate(newtype)
-    //     .map_err(|e| e.to_compile_error())
-    //     .unwrap_or_else(core::convert::identity)
-    //     .into()
-    tokens
+const SELF_ALIAS: &str = "_self";
+const RAW_OUT_ALIAS: &str = "__proxied_out";
+const PROXY_PREFIX: &str = "Rhai";
+
+/// Generates the getter and setter registrations for a field, or nothing if Rhai cannot represent the field's type.
+///
+/// Fields of proxies referencing values in the world are themselves references into the world, other fields are cloned,
+/// Rhai writes modified values back through the setter when assigning to nested fields.
+/// Like with the Lua proxies, fields which are proxies themselves are marked with `#[lua(output(proxy))]`
+fn generate_field_accessors(
+    idx: usize,
+    field: &Field,
+    attrs: &FunctionAttributes,
+    proxied_type_path: &Path,
+    proxy_ident: &Ident,
+) -> Option<proc_macro2::TokenStream> {
+    let rhai = rhai_path();
+    let (name, member) = match &field.ident {
+        Some(ident) => (ident.to_string(), ident.to_token_stream()),
+        None => (format!("_{idx}"), Index::from(idx).to_token_stream()),
+    };
+    let type_ = simple_type(&field.ty, proxied_type_path, attrs.is_proxy_output())?;
+    let self_ = format_ident!("{SELF_ALIAS}");
+    let value = format_ident!("{RAW_OUT_ALIAS}");
+
+    let getter_body = match &type_ {
+        SimpleType::ProxyType(ProxyType {
+            proxy_ident: field_proxy,
+            ..
+        }) if field.ident.is_some() => quote! {
+            let #value = match #self_ {
+                #proxy_ident::Ref(ref_) => #field_proxy::new_ref(bevy_script_api::ValueIndex::index(
+                    ref_,
+                    std::borrow::Cow::Borrowed(#name),
+                )),
+                _ => #field_proxy::new(#self_.val(|#self_| #self_.#member.clone())?),
+            };
+            Ok(#rhai::Dynamic::from(#value))
+        },
+        _ => {
+            let converted = to_dynamic(&value, &type_, false)?;
+            quote! {
+                let #value = #self_.val(|#self_| #self_.#member.clone())?;
+                Ok(#converted)
+            }
+        }
+    };
+
+    // getters and setters must share a type, so the setter receives a `Dynamic` and casts it to the argument type
+    let setter_arg = RhaiArg::new(value.clone(), &type_)?;
+    let setter_type = &setter_arg.rhai_type;
+    let setter_value = match setter_arg.unpacked {
+        Unpacked::Value(value) => value,
+        Unpacked::Borrowed { .. } => return None,
+    };
+    let field_type = field.ty.to_token_stream().to_string();
+
+    Some(quote_spanned! {field.span()=>
+        .with_get_set(
+            #name,
+            |#self_: &mut #proxy_ident| -> Result<#rhai::Dynamic, Box<#rhai::EvalAltResult>> {
+                #getter_body
+            },
+            |#self_: &mut #proxy_ident, #value: #rhai::Dynamic| -> Result<(), Box<#rhai::EvalAltResult>> {
+                let type_name = #value.type_name();
+                let mut #value = #value.try_cast::<#setter_type>().ok_or_else(|| {
+                    Box::new(#rhai::EvalAltResult::ErrorMismatchDataType(
+                        #field_type.to_owned(),
+                        type_name.to_owned(),
+                        #rhai::Position::NONE,
+                    ))
+                })?;
+                let #value = #setter_value;
+                #self_.val_mut(|#self_| #self_.#member = #value)?;
+                Ok(())
+            },
+        )
+    })
+}
+
+#[proc_macro_derive(RhaiProxy, attributes(lua, proxy))]
+pub fn impl_rhai_proxy(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    let meta: ProxyInput = match ProxyInput::from_derive_input(&derive_input) {
+        Ok(v) => v,
+        Err(e) => return darling::Error::write_errors(e).into(),
+    };
+    if meta.proxy_name.is_some() {
+        return syn::Error::new(
+            derive_input.span(),
+            "The `name` attribute is not supported for rhai proxies",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let rhai = rhai_path();
+    let proxied_type_path: syn::Path = meta.remote.unwrap_or(meta.ident.clone().into());
+    let proxied_type_str = proxied_type_path.segments.last().unwrap().ident.to_string();
+    let proxy_type_ident = format_ident!("{PROXY_PREFIX}{}", &meta.ident);
+    let script_name = meta.ident.to_string();
+
+    // optional clone extensions
+    let opt_with_clone = meta
+        .derive
+        .clone
+        .is_present()
+        .then_some(quote_spanned! {derive_input.span()=>with Clone})
+        .unwrap_or_default();
+
+    let mismatch_error = quote! {
+        Box::new(#rhai::EvalAltResult::ErrorMismatchDataType(
+            #proxied_type_str.to_owned(),
+            new_val.type_name().to_owned(),
+            #rhai::Position::NONE,
+        ))
+    };
+
+    let opt_from_rhai_proxy = meta.derive.clone.is_present().then_some(
+        quote_spanned!{derive_input.span()=>
+            impl bevy_script_api::rhai::FromRhaiProxy for #proxied_type_path {
+                fn from_rhai_proxy(new_val: #rhai::Dynamic) -> Result<Self, Box<#rhai::EvalAltResult>> {
+                    if new_val.is::<#proxy_type_ident>() {
+                        Ok(new_val.cast::<#proxy_type_ident>().inner()?)
+                    } else {
+                        Err(#mismatch_error)
+                    }
+                }
+            }
+        }
+    ).unwrap_or_default();
+
+    let mut errors = darling::Error::accumulator();
+    let fields = match meta.data {
+        darling::ast::Data::<Variant, Field>::Struct(fields) => fields
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, field)| {
+                // field attributes are parsed like those of functions, as the Lua proxies turn fields into getter and setter functions
+                let attrs = errors.handle(FunctionAttributes::from_attributes(&field.attrs))?;
+                generate_field_accessors(idx, field, &attrs, &proxied_type_path, &proxy_type_ident)
+            })
+            .collect::<Vec<_>>(),
+        _ => panic!("Enums or Unions are not supported"),
+    };
+
+    let functions = meta
+        .functions
+        .0
+        .into_iter()
+        .filter_map(|function| {
+            errors
+                .handle_in(|| Function::new(&proxied_type_path, function))
+                .flatten()
+        })
+        .collect::<Vec<_>>();
+
+    // stop if any errors so far
+    if let Err(e) = errors.finish() {
+        return e.write_errors().into();
+    }
+
+    let mut methods = Vec::default();
+    let mut statics = Vec::default();
+    for function in &functions {
+        for (registration, variant) in function.registrations(&script_name) {
+            let closure = function.generate_closure(&proxied_type_path, &proxy_type_ident, variant);
+            match registration {
+                Registration::Type(name) => methods.push(quote_spanned! {function.span=>
+                    .with_fn(#name, #closure)
+                }),
+                Registration::Static(name) => statics.push(quote_spanned! {function.span=>
+                    module.set_native_fn(#name, #closure);
+                }),
+            }
+        }
+    }
+
+    quote_spanned! {derive_input.span()=>
+
+        bevy_script_api::make_script_wrapper!(#proxied_type_path as #proxy_type_ident #opt_with_clone);
+
+        #opt_from_rhai_proxy
+
+        #[automatically_derived]
+        #[allow(unused_parens, unused_braces, unused_mut, unused_variables, deprecated)]
+        #[allow(clippy::all)]
+        impl #rhai::CustomType for #proxy_type_ident {
+            fn build(mut builder: #rhai::TypeBuilder<Self>) {
+                builder
+                    .with_name(#script_name)
+                    #(#fields)*
+                    #(#methods)*;
+            }
+        }
+
+        #[automatically_derived]
+        #[allow(unused_parens, unused_braces, unused_mut, unused_variables, deprecated)]
+        #[allow(clippy::all)]
+        impl bevy_script_api::rhai::RhaiProxyStatics for #proxy_type_ident {
+            const NAME: &'static str = #script_name;
+
+            fn statics() -> #rhai::Module {
+                let mut module = #rhai::Module::new();
+                #(#statics)*
+                module
+            }
+        }
+
+        #[allow(clippy::all, unused_variables)]
+        impl bevy_script_api::rhai::RhaiProxyable for #proxied_type_path {
+            fn ref_to_rhai(self_: bevy_script_api::script_ref::ReflectReference) -> Result<#rhai::Dynamic, Box<#rhai::EvalAltResult>> {
+                Ok(#rhai::Dynamic::from(#proxy_type_ident::new_ref(self_)))
+            }
+
+            fn apply_rhai(self_: &mut bevy_script_api::script_ref::ReflectReference, new_val: #rhai::Dynamic) -> Result<(), Box<#rhai::EvalAltResult>> {
+                if new_val.is::<#proxy_type_ident>() {
+                    new_val.cast::<#proxy_type_ident>().apply_self_to_base(self_)?;
+                    Ok(())
+                } else {
+                    Err(#mismatch_error)
+                }
+            }
+        }
+
+        #[allow(clippy::all, unused_variables)]
+        impl bevy_script_api::rhai::ToRhaiProxy for #proxied_type_path {
+            fn to_rhai_proxy(self) -> Result<#rhai::Dynamic, Box<#rhai::EvalAltResult>> {
+                Ok(#rhai::Dynamic::from(#proxy_type_ident::new(self)))
+            }
+        }
+    }
+    .into()
 }
@@ -0,0 +1,228 @@
+//! Conversions between the types in proxied function signatures and the types Rhai passes to and receives from native functions.
+use bevy_mod_scripting_common::input::{
+    DuoPath, ProxyType, Reference, SimpleType, StdTypeIdent, UnitPath,
+};
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Expr, ExprLit, Lit, Type, TypeArray};
+
+/// The primitive types Rhai has native representations for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    Int,
+    Float,
+    Bool,
+    String,
+}
+
+impl Primitive {
+    pub fn from_type(type_: &Type) -> Option<Self> {
+        let Type::Path(path) = type_ else {
+            return None;
+        };
+        if path.qself.is_some() {
+            return None;
+        }
+        let ident = path.path.segments.last()?.ident.to_string();
+        match ident.as_str() {
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+            | "u128" | "usize"
+                if path.path.segments.len() == 1 =>
+            {
+                Some(Self::Int)
+            }
+            "f32" | "f64" if path.path.segments.len() == 1 => Some(Self::Float),
+            "bool" if path.path.segments.len() == 1 => Some(Self::Bool),
+            "String" => Some(Self::String),
+            _ => None,
+        }
+    }
+
+    /// The type of the native function parameter receiving this primitive
+    fn rhai_type(self) -> TokenStream {
+        let rhai = rhai_path();
+        match self {
+            Primitive::Int => quote!(#rhai::INT),
+            Primitive::Float => quote!(#rhai::FLOAT),
+            Primitive::Bool => quote!(bool),
+            Primitive::String => quote!(#rhai::ImmutableString),
+        }
+    }
+}
+
+pub fn rhai_path() -> TokenStream {
+    quote!(bevy_mod_scripting_rhai::rhai)
+}
+
+/// An error returned from a native function
+pub fn runtime_error(message: TokenStream) -> TokenStream {
+    let rhai = rhai_path();
+    quote!(Box::new(#rhai::EvalAltResult::ErrorRuntime(
+        (#message).into(),
+        #rhai::Position::NONE
+    )))
+}
+
+/// How a converted argument is made available to the proxied function
+pub enum Unpacked {
+    /// The argument is converted into the value passed to the function
+    Value(TokenStream),
+    /// The argument is a proxy the function borrows from for the duration of the call, mutably or not
+    Borrowed { mutable: bool },
+}
+
+/// An argument of a native function
+pub struct RhaiArg {
+    pub name: Ident,
+    /// The type of the parameter of the native function
+    pub rhai_type: TokenStream,
+    pub unpacked: Unpacked,
+}
+
+impl RhaiArg {
+    /// Converts an argument of the proxied function, returns `None` if Rhai cannot pass values of its type
+    pub fn new(name: Ident, type_: &SimpleType) -> Option<Self> {
+        let rhai = rhai_path();
+        let (rhai_type, unpacked) = match type_ {
+            SimpleType::ProxyType(ProxyType { proxy_ident, .. }) => (
+                proxy_ident.to_token_stream(),
+                Unpacked::Value(quote!(#name.inner()?)),
+            ),
+            SimpleType::Reference(Reference {
+                mutability, inner, ..
+            }) => match inner.as_ref() {
+                SimpleType::ProxyType(ProxyType { proxy_ident, .. }) => (
+                    proxy_ident.to_token_stream(),
+                    Unpacked::Borrowed {
+                        mutable: mutability.is_some(),
+                    },
+                ),
+                _ => return None,
+            },
+            SimpleType::Type(type_) => match Primitive::from_type(type_) {
+                Some(Primitive::String) => (
+                    Primitive::String.rhai_type(),
+                    Unpacked::Value(quote!(#name.to_string())),
+                ),
+                Some(Primitive::Bool) => (quote!(bool), Unpacked::Value(quote!(#name))),
+                Some(primitive) => (
+                    primitive.rhai_type(),
+                    Unpacked::Value(quote!(#name as #type_)),
+                ),
+                None => {
+                    let TypeArray { elem, len, .. } = match type_ {
+                        Type::Array(array) => array,
+                        _ => return None,
+                    };
+                    Primitive::from_type(elem)?;
+                    let message =
+                        format!("Expected an array of {} elements", len.to_token_stream());
+                    let error = runtime_error(quote!(#message));
+                    (
+                        quote!(#rhai::Array),
+                        Unpacked::Value(quote!(
+                            <#type_>::try_from(
+                                #name
+                                    .into_iter()
+                                    .map(<#elem as bevy_script_api::rhai::FromRhaiProxy>::from_rhai_proxy)
+                                    .collect::<Result<Vec<_>, _>>()?,
+                            )
+                            .map_err(|_| #error)?
+                        )),
+                    )
+                }
+            },
+            SimpleType::UnitPath(UnitPath {
+                std_type_ident: Some(StdTypeIdent::Option),
+                inner,
+                ..
+            }) => match inner.as_ref() {
+                SimpleType::Type(inner) if Primitive::from_type(inner).is_some() => (
+                    quote!(#rhai::Dynamic),
+                    Unpacked::Value(quote!(
+                        <Option<#inner> as bevy_script_api::rhai::FromRhaiProxy>::from_rhai_proxy(#name)?
+                    )),
+                ),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(Self {
+            name,
+            rhai_type,
+            unpacked,
+        })
+    }
+}
+
+/// Generates an expression converting the value of the given type stored in `value` into a `Dynamic`.
+///
+/// Outer results are unwrapped with errors returned from the native function, returns `None` if Rhai cannot receive values of the type
+pub fn to_dynamic(value: &Ident, type_: &SimpleType, outer: bool) -> Option<TokenStream> {
+    let rhai = rhai_path();
+    Some(match type_ {
+        SimpleType::Unit => quote!(#rhai::Dynamic::UNIT),
+        SimpleType::ProxyType(ProxyType { proxy_ident, .. }) => {
+            quote!(#rhai::Dynamic::from(#proxy_ident::new(#value)))
+        }
+        SimpleType::Type(type_) => match Primitive::from_type(type_) {
+            Some(Primitive::Int) => quote!(#rhai::Dynamic::from(#value as #rhai::INT)),
+            Some(Primitive::Float) => quote!(#rhai::Dynamic::from(#value as #rhai::FLOAT)),
+            Some(_) => quote!(#rhai::Dynamic::from(#value)),
+            None => match type_ {
+                Type::Array(TypeArray { elem, len, .. }) if array_len(len).is_some() => {
+                    let elem = SimpleType::Type(elem.as_ref().clone());
+                    let elem = to_dynamic(value, &elem, false)?;
+                    quote!(#rhai::Dynamic::from_array(
+                        #value.into_iter().map(|#value| #elem).collect()
+                    ))
+                }
+                _ => return None,
+            },
+        },
+        SimpleType::UnitPath(UnitPath {
+            std_type_ident: Some(StdTypeIdent::Option),
+            inner,
+            ..
+        }) => {
+            let inner = to_dynamic(value, inner, false)?;
+            quote!(match #value {
+                Some(#value) => #inner,
+                None => #rhai::Dynamic::UNIT,
+            })
+        }
+        SimpleType::UnitPath(UnitPath {
+            std_type_ident: Some(StdTypeIdent::Vec),
+            inner,
+            ..
+        }) => {
+            let inner = to_dynamic(value, inner, false)?;
+            quote!(#rhai::Dynamic::from_array(
+                #value.into_iter().map(|#value| #inner).collect()
+            ))
+        }
+        SimpleType::DuoPath(DuoPath {
+            std_type_ident: Some(StdTypeIdent::Result),
+            left,
+            ..
+        }) if outer => {
+            let left = to_dynamic(value, left, false)?;
+            let error = runtime_error(quote!(e.to_string()));
+            quote!({
+                let #value = #value.map_err(|e| #error)?;
+                #left
+            })
+        }
+        _ => return None,
+    })
+}
+
+fn array_len(len: &Expr) -> Option<usize> {
+    match len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(len), ..
+        }) => len.base10_parse().ok(),
+        _ => None,
+    }
+}
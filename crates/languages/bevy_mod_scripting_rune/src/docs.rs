@@ -1,17 +1,537 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    fs::File,
+    io::Write,
+    path::Path,
+};
+
 use bevy_mod_scripting_core::prelude::*;
+use rune::{
+    compile::Named,
+    module::{Function, InstallWith, InstanceFunction},
+    runtime::{self, FullTypeOf, MaybeTypeOf, Object, TypeOf, Value},
+    ContextError, Hash, Module,
+};
+use serde::Serialize;
+
+/// A piece of Rune documentation.
+///
+/// The documentation is taken from [`DocumentedModule`]s, which record the documentation and signature of each item as it is installed.
+/// Each piece is combined into a single markdown page listing every module, function and type, and a json file containing the same information.
+#[derive(Debug)]
+pub struct RuneDocFragment {
+    name: &'static str,
+    modules: BTreeMap<String, ModuleItems>,
+    /// The names of the types documented by the modules, by their hash
+    type_names: HashMap<Hash, String>,
+}
+
+/// The items installed into a module, along with their documentation
+#[derive(Debug, Default, Clone)]
+struct ModuleItems {
+    docs: String,
+    functions: Vec<FunctionItem>,
+    types: BTreeMap<String, TypeItem>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct TypeItem {
+    docs: String,
+    methods: Vec<FunctionItem>,
+}
+
+/// A documented function, its types are named once all modules are known
+#[derive(Debug, Clone)]
+struct FunctionItem {
+    name: String,
+    /// Pairs of argument names and types, excluding the receiver of methods. `None` types accept any value
+    args: Vec<(String, Option<FullTypeOf>)>,
+    returns: Option<FullTypeOf>,
+    docs: String,
+}
+
+/// The argument types of a function, implemented for the argument tuples of Rune's [`Function`] trait
+pub trait SignatureArgs {
+    fn types() -> Vec<Option<FullTypeOf>>;
+}
+
+macro_rules! impl_signature_args {
+    ($($ty:ident),*) => {
+        impl<$($ty: MaybeTypeOf,)*> SignatureArgs for ($($ty,)*) {
+            fn types() -> Vec<Option<FullTypeOf>> {
+                vec![$($ty::maybe_type_of()),*]
+            }
+        }
+    };
+}
+
+impl_signature_args!();
+impl_signature_args!(A);
+impl_signature_args!(A, B);
+impl_signature_args!(A, B, C);
+impl_signature_args!(A, B, C, D);
+impl_signature_args!(A, B, C, D, E);
+impl_signature_args!(A, B, C, D, E, F);
+impl_signature_args!(A, B, C, D, E, F, G);
+impl_signature_args!(A, B, C, D, E, F, G, H);
+
+impl FunctionItem {
+    fn new<A: SignatureArgs, R: MaybeTypeOf>(
+        name: &str,
+        arg_names: &[&str],
+        skip: usize,
+        docs: &str,
+    ) -> Self {
+        let types = A::types();
+        assert_eq!(
+            arg_names.len() + skip,
+            types.len(),
+            "Expected a name for every argument of `{name}`"
+        );
+        Self {
+            name: name.to_owned(),
+            args: arg_names
+                .iter()
+                .map(|name| (*name).to_owned())
+                .zip(types.into_iter().skip(skip))
+                .collect(),
+            returns: R::maybe_type_of(),
+            docs: docs.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Docs {
+    name: &'static str,
+    modules: BTreeMap<String, ModuleDoc>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModuleDoc {
+    docs: String,
+    functions: Vec<FunctionDoc>,
+    types: BTreeMap<String, TypeDoc>,
+}
+
+#[derive(Debug, Serialize)]
+struct TypeDoc {
+    docs: String,
+    methods: Vec<FunctionDoc>,
+}
+
+#[derive(Debug, Serialize)]
+struct FunctionDoc {
+    name: String,
+    /// Pairs of argument names and types, excluding the receiver of methods
+    args: Vec<(String, String)>,
+    /// The return type, `None` if the function returns `()`
+    returns: Option<String>,
+    docs: String,
+}
+
+impl FunctionDoc {
+    fn signature(&self, receiver: bool) -> String {
+        let args = receiver
+            .then(|| "self".to_owned())
+            .into_iter()
+            .chain(self.args.iter().map(|(name, ty)| format!("{name}: {ty}")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &self.returns {
+            Some(returns) => format!("{}({args}) -> {returns}", self.name),
+            None => format!("{}({args})", self.name),
+        }
+    }
+}
+
+/// Names the types of Rune's standard library and the types documented by the modules
+struct TypeNames(HashMap<Hash, String>);
+
+impl TypeNames {
+    fn new(documented: &HashMap<Hash, String>) -> Self {
+        fn builtin<T: TypeOf + ?Sized>() -> (Hash, String) {
+            (T::type_hash(), T::type_info().to_string())
+        }
+
+        let mut names = HashMap::from_iter([
+            builtin::<bool>(),
+            builtin::<u8>(),
+            builtin::<char>(),
+            builtin::<i64>(),
+            builtin::<f64>(),
+            builtin::<String>(),
+            builtin::<[u8]>(),
+            builtin::<Vec<Value>>(),
+            builtin::<Object>(),
+            builtin::<Option<Value>>(),
+            builtin::<Result<Value, Value>>(),
+            builtin::<runtime::Iterator>(),
+            builtin::<runtime::Function>(),
+        ]);
+        names.extend(documented.clone());
+        Self(names)
+    }
+
+    /// Names the type, values of types which are not known to the documentation are described as `any`
+    fn name(&self, ty: Option<FullTypeOf>) -> String {
+        ty.and_then(|ty| self.0.get(&ty.hash))
+            .cloned()
+            .unwrap_or_else(|| "any".to_owned())
+    }
+
+    fn function(&self, function: &FunctionItem) -> FunctionDoc {
+        FunctionDoc {
+            name: function.name.clone(),
+            args: function
+                .args
+                .iter()
+                .map(|(name, ty)| (name.clone(), self.name(*ty)))
+                .collect(),
+            // unit shares its type with tuples, functions returning tuples are rare enough to accept that they are documented as returning nothing
+            returns: match function.returns {
+                Some(ty) if ty.hash == <()>::type_hash() => None,
+                returns => Some(self.name(returns)),
+            },
+            docs: function.docs.clone(),
+        }
+    }
+}
+
+/// A Rune [`Module`] which documents the items installed into it.
+///
+/// Every function and type is installed together with its documentation, so the docs generated from it list exactly the items scripts can use.
+/// The types of arguments and return values are taken from the signatures of the installed functions,
+/// only the names of the arguments are given:
+///
+/// ```
+/// # use bevy_mod_scripting_rune::prelude::*;
+/// let mut module = DocumentedModule::with_crate("fancy", "Fancy printing.").unwrap();
+/// module
+///     .function("print", &["msg"], "Prints the message with sparkles.", |msg: &str| {
+///         println!("✨ {msg} ✨")
+///     })
+///     .unwrap();
+///
+/// let docs = RuneDocFragment::new("FancyAPI").with_module(&module);
+/// let mut context = rune::Context::new();
+/// context.install(module.into_module()).unwrap();
+/// ```
+pub struct DocumentedModule {
+    module: Module,
+    path: String,
+    items: ModuleItems,
+    type_names: HashMap<Hash, String>,
+}
+
+impl DocumentedModule {
+    /// Creates a module scripts import by the given crate name, e.g. `use bevy::world;`
+    pub fn with_crate(name: &str, docs: &str) -> Result<Self, ContextError> {
+        Ok(Self {
+            module: Module::with_crate(name)?,
+            path: name.to_owned(),
+            items: ModuleItems {
+                docs: docs.to_owned(),
+                ..Default::default()
+            },
+            type_names: Default::default(),
+        })
+    }
+
+    /// Installs a function, given the names of its arguments
+    pub fn function<F, A, K>(
+        &mut self,
+        name: &'static str,
+        args: &[&str],
+        docs: &str,
+        f: F,
+    ) -> Result<&mut Self, ContextError>
+    where
+        F: Function<A, K>,
+        F::Return: MaybeTypeOf,
+        A: SignatureArgs,
+    {
+        let function = FunctionItem::new::<A, F::Return>(name, args, 0, docs);
+        self.module
+            .raw_function(name, move |stack, args| f.fn_call(stack, args))
+            .build()?;
+        self.items.functions.push(function);
+        Ok(self)
+    }
+
+    /// Installs a type, documented under the name Rune gives it
+    pub fn ty<T>(&mut self, docs: &str) -> Result<&mut Self, ContextError>
+    where
+        T: TypeOf + Named + InstallWith,
+    {
+        self.module.ty::<T>()?;
+        let name = T::BASE_NAME.to_string();
+        self.type_names.insert(T::type_hash(), name.clone());
+        self.items.types.entry(name).or_default().docs = docs.to_owned();
+        Ok(self)
+    }
+
+    /// Installs a method of the type of its first argument, given the names of the arguments following the receiver
+    pub fn method<F, A, K>(
+        &mut self,
+        name: &'static str,
+        args: &[&str],
+        docs: &str,
+        f: F,
+    ) -> Result<&mut Self, ContextError>
+    where
+        F: InstanceFunction<A, K>,
+        F::Return: MaybeTypeOf,
+        A: SignatureArgs,
+    {
+        let method = FunctionItem::new::<A, F::Return>(name, args, 1, docs);
+        self.module
+            .raw_function(name, move |stack, args| {
+                InstanceFunction::fn_call(&f, stack, args)
+            })
+            .build_associated::<F::Instance>()?;
+        let ty = self
+            .type_names
+            .get(&F::Instance::type_hash())
+            .cloned()
+            .unwrap_or_else(|| F::Instance::type_info().to_string());
+        self.items.types.entry(ty).or_default().methods.push(method);
+        Ok(self)
+    }
+
+    /// The underlying module, for items which are not listed in the docs such as protocol implementations
+    pub fn module_mut(&mut self) -> &mut Module {
+        &mut self.module
+    }
+
+    pub fn into_module(self) -> Module {
+        self.module
+    }
+}
+
+impl RuneDocFragment {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            modules: Default::default(),
+            type_names: Default::default(),
+        }
+    }
+
+    /// Adds the documentation of the items installed into the given module
+    pub fn with_module(self, module: &DocumentedModule) -> Self {
+        let name = self.name;
+        self.merge(Self {
+            name,
+            modules: BTreeMap::from_iter([(module.path.clone(), module.items.clone())]),
+            type_names: module.type_names.clone(),
+        })
+    }
+
+    /// Documents the `script` module which the script host installs into every script
+    fn builtin(self) -> Self {
+        let script = ModuleItems {
+            docs: "Functions available to every script.".to_owned(),
+            functions: vec![FunctionItem::new::<(), Object>(
+                "state",
+                &[],
+                0,
+                "Returns an object which persists between calls into the same script. The state is reset when the script is reloaded.",
+            )],
+            ..Default::default()
+        };
+        let name = self.name;
+        self.merge(Self {
+            name,
+            modules: BTreeMap::from_iter([("script".to_owned(), script)]),
+            type_names: Default::default(),
+        })
+    }
+
+    /// Names the types of every item, types documented by any of the merged modules are known
+    fn resolve(&self) -> Docs {
+        let names = TypeNames::new(&self.type_names);
+        Docs {
+            name: self.name,
+            modules: self
+                .modules
+                .iter()
+                .map(|(path, module)| {
+                    let module = ModuleDoc {
+                        docs: module.docs.clone(),
+                        functions: module.functions.iter().map(|f| names.function(f)).collect(),
+                        types: module
+                            .types
+                            .iter()
+                            .map(|(name, ty)| {
+                                let ty = TypeDoc {
+                                    docs: ty.docs.clone(),
+                                    methods: ty.methods.iter().map(|m| names.function(m)).collect(),
+                                };
+                                (name.clone(), ty)
+                            })
+                            .collect(),
+                    };
+                    (path.clone(), module)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Docs {
+    fn markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.name);
+        for (path, module) in &self.modules {
+            let _ = writeln!(out, "\n## `{path}`\n");
+            if !module.docs.is_empty() {
+                let _ = writeln!(out, "{}\n", module.docs);
+            }
+
+            if !module.functions.is_empty() {
+                let _ = writeln!(out, "### Functions\n");
+                for function in &module.functions {
+                    let _ = writeln!(
+                        out,
+                        "- `{path}::{}`  \n  {}",
+                        function.signature(false),
+                        function.docs
+                    );
+                }
+                out.push('\n');
+            }
 
-pub struct RuneDocFragment;
+            if !module.types.is_empty() {
+                let _ = writeln!(out, "### Types\n");
+                for (name, ty) in &module.types {
+                    let _ = writeln!(out, "#### `{path}::{name}`\n");
+                    if !ty.docs.is_empty() {
+                        let _ = writeln!(out, "{}\n", ty.docs);
+                    }
+                    for method in &ty.methods {
+                        let _ =
+                            writeln!(out, "- `{}`  \n  {}", method.signature(true), method.docs);
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+}
 
 impl DocFragment for RuneDocFragment {
-    fn merge(self, _o: Self) -> Self {
-        todo!()
+    fn merge(mut self, o: Self) -> Self {
+        self.type_names.extend(o.type_names);
+        for (path, module) in o.modules {
+            let merged = self.modules.entry(path).or_default();
+            if merged.docs.is_empty() {
+                merged.docs = module.docs;
+            }
+            merged.functions.extend(module.functions);
+            for (name, ty) in module.types {
+                let merged = merged.types.entry(name).or_default();
+                if merged.docs.is_empty() {
+                    merged.docs = ty.docs;
+                }
+                merged.methods.extend(ty.methods);
+            }
+        }
+        self
     }
 
-    fn gen_docs(self) -> Result<(), ScriptError> {
-        todo!()
+    fn gen_docs(self, output_dir: &Path) -> Result<(), ScriptError> {
+        let docs = self.builtin().resolve();
+
+        let json = serde_json::to_string_pretty(&docs)
+            .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+
+        for (extension, contents) in [("json", json), ("md", docs.markdown())] {
+            File::create(output_dir.join(format!("{}.{extension}", docs.name)))
+                .and_then(|mut file| {
+                    file.write_all(contents.as_bytes())?;
+                    file.flush()
+                })
+                .map_err(|e| ScriptError::DocGenError(e.to_string()))?;
+        }
+
+        Ok(())
     }
 
     fn name(&self) -> &'static str {
-        todo!()
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rune::{runtime::VmResult, Any};
+
+    use super::*;
+
+    #[derive(Any, Clone)]
+    struct Counter(i64);
+
+    fn signatures(docs: &Docs, path: &str, ty: Option<&str>) -> Vec<String> {
+        let module = &docs.modules[path];
+        match ty {
+            Some(ty) => module.types[ty]
+                .methods
+                .iter()
+                .map(|m| m.signature(true))
+                .collect(),
+            None => module
+                .functions
+                .iter()
+                .map(|f| f.signature(false))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_signatures_from_functions() {
+        let mut module = DocumentedModule::with_crate("counters", "").unwrap();
+        // the type is documented after a function returning it, its name is still known
+        module
+            .function("new", &["start"], "", |start: i64| Counter(start))
+            .unwrap()
+            .function("log", &["msg", "times"], "", |_: &str, _: u8| ())
+            .unwrap()
+            .function("parse", &["value"], "", |value: Value| VmResult::Ok(value))
+            .unwrap()
+            .ty::<Counter>("")
+            .unwrap()
+            .method("add", &["amount"], "", |this: &mut Counter, amount: i64| {
+                this.0 += amount;
+                this.0 > 0
+            })
+            .unwrap()
+            .method("get", &[], "", |this: &Counter| Some(this.0))
+            .unwrap();
+
+        let docs = RuneDocFragment::new("Counters")
+            .with_module(&module)
+            .resolve();
+
+        assert_eq!(
+            signatures(&docs, "counters", None),
+            vec![
+                "new(start: i64) -> Counter",
+                "log(msg: String, times: u8)",
+                "parse(value: any) -> any",
+            ]
+        );
+        assert_eq!(
+            signatures(&docs, "counters", Some("Counter")),
+            vec!["add(self, amount: i64) -> bool", "get(self) -> Option"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected a name for every argument of `log`")]
+    fn test_missing_argument_names() {
+        let mut module = DocumentedModule::with_crate("counters", "").unwrap();
+        let _ = module.function("log", &["msg"], "", |_: &str, _: u8| ());
     }
 }
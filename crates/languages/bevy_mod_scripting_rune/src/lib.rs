@@ -1,4 +1,9 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Weak},
+};
 
 use bevy::prelude::*;
 use bevy_mod_scripting_core::{
@@ -8,8 +13,10 @@ use bevy_mod_scripting_core::{
 };
 use prelude::{RuneDocFragment, RuneFile, RuneLoader};
 use rune::{
-    runtime::{Args, RuntimeContext, VmError, VmResult},
-    Context, Diagnostics, Source, Sources, Unit, Vm,
+    ast::Spanned,
+    diagnostics::Diagnostic,
+    runtime::{Args, Object, RuntimeContext, Value, VmError, VmResult},
+    Context, ContextError, Diagnostics, Module, Source, Sources, Unit, Vm,
 };
 
 mod assets;
@@ -18,8 +25,8 @@ mod docs;
 pub mod prelude {
     pub use crate::{
         assets::{RuneFile, RuneLoader},
-        docs::RuneDocFragment,
-        RuneArgs, RuneEvent, RuneScriptContext, RuneScriptHost,
+        docs::{DocumentedModule, RuneDocFragment},
+        RuneArgs, RuneEvent, RuneScriptContext, RuneScriptData, RuneScriptHost, RuneScriptState,
     };
     pub use rune::{self, runtime::Args, Context};
 }
@@ -49,39 +56,232 @@ impl<A: RuneArgs> ScriptEvent for RuneEvent<A> {
     }
 }
 
-/// A cached Rune Vm used to execute units.
-struct RuneVm(Vm);
+thread_local! {
+    /// The script currently handling events on this thread, see [`RuneScriptData::current`].
+    static CURRENT_SCRIPT: RefCell<Option<RuneScriptData>> = const { RefCell::new(None) };
+}
+
+/// Data about the script which is currently handling events.
+///
+/// Units are compiled against a context shared by every run of a script, so unlike the other hosts
+/// there is no per-script scope to inject values into. Native functions look up the calling script
+/// and the world via [`RuneScriptData::current`] instead.
+#[derive(Debug, Clone)]
+pub struct RuneScriptData {
+    pub sid: u32,
+    pub entity: Entity,
+    pub name: String,
+    /// Only valid while the script is running
+    pub world: WorldPointer,
+    /// The persistent state of the script, an object shared by every call into the script
+    pub state: Value,
+}
+
+impl RuneScriptData {
+    /// Returns the data of the script currently handling events on this thread,
+    /// or `None` if no script is running
+    pub fn current() -> Option<Self> {
+        CURRENT_SCRIPT.with(|current| current.borrow().clone())
+    }
+
+    /// Makes this the current script while running the given function,
+    /// which allows running units using the bevy API outside of the script host
+    pub fn scope<O>(self, f: impl FnOnce() -> O) -> O {
+        let previous = CURRENT_SCRIPT.with(|current| current.replace(Some(self)));
+        let out = f();
+        CURRENT_SCRIPT.with(|current| *current.borrow_mut() = previous);
+        out
+    }
+}
+
+/// A cached Rune Vm used to execute units, along with the persistent state of every script.
+struct RuneVm {
+    vm: Vm,
+    /// States keyed by the address of their [`RuneScriptState`] handle,
+    /// the weak reference keeps the address from being reused while the entry exists
+    states: HashMap<usize, (Weak<()>, Value)>,
+}
 
 impl Default for RuneVm {
     fn default() -> Self {
-        Self(Vm::new(
-            Arc::new(RuntimeContext::default()),
-            Arc::new(Unit::default()),
-        ))
+        Self {
+            vm: Vm::new(
+                Arc::new(RuntimeContext::default()),
+                Arc::new(Unit::default()),
+            ),
+            states: Default::default(),
+        }
+    }
+}
+
+impl RuneVm {
+    /// Retrieves the state belonging to the given handle, creating an empty object for new handles
+    fn state(&mut self, handle: &RuneScriptState) -> Value {
+        let (_, state) = self
+            .states
+            .entry(Arc::as_ptr(&handle.0) as usize)
+            .or_insert_with(|| {
+                (
+                    Arc::downgrade(&handle.0),
+                    Value::try_from(Object::new()).expect("Failed to allocate script state"),
+                )
+            });
+        state.clone()
+    }
+
+    /// Drops the states of scripts which were unloaded or reloaded
+    fn clear_stale_states(&mut self) {
+        self.states
+            .retain(|_, (handle, _)| handle.strong_count() > 0);
     }
 }
 
+/// Handle to the persistent state of a loaded script.
+///
+/// Rune values cannot leave the thread they were created on, so the state itself is stored with the cached Vm.
+/// Every time a script is loaded it gets a new handle and so starts with an empty state,
+/// the state of a script which was reloaded or removed is dropped on the next call into any script.
+#[derive(Debug, Default)]
+pub struct RuneScriptState(Arc<()>);
+
 /// Script context for a rune script.
 pub struct RuneScriptContext {
     pub unit: Arc<Unit>,
     pub runtime_context: Arc<RuntimeContext>,
+    /// Scripts access their state via `script::state()`
+    pub state: RuneScriptState,
+}
+
+/// The `script` module available to every script.
+///
+/// `script::state()` returns an object which persists between calls into the same script, e.g.:
+/// ```rune
+/// pub fn on_update() {
+///     let state = script::state();
+///     state["frames"] = state.get("frames").unwrap_or(0) + 1;
+/// }
+/// ```
+fn script_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("script")?;
+    module
+        .function("state", || match RuneScriptData::current() {
+            Some(script) => VmResult::Ok(script.state),
+            None => VmResult::panic("`script::state` can only be called from a running script"),
+        })
+        .build()?;
+    Ok(module)
 }
 
 #[derive(Resource)]
 /// Rune script host. Enables Rune scripting.
 pub struct RuneScriptHost<A: RuneArgs> {
+    /// The script compiled by `lint_script`, which is always followed by loading the same script, so it is only compiled once
+    linted: Option<(u32, Result<RuneScriptContext, ScriptError>)>,
     _ph: PhantomData<A>,
 }
 
 impl<A: RuneArgs> Default for RuneScriptHost<A> {
     fn default() -> Self {
         Self {
+            linted: None,
             _ph: Default::default(),
         }
     }
 }
 
 impl<A: RuneArgs> RuneScriptHost<A> {
+    /// Compiles the script against a context with all APIs attached.
+    /// Returns the loaded script, or the errors which prevented it from compiling, along with the compiler's warnings
+    fn compile(
+        script: &[u8],
+        script_data: &ScriptData,
+        providers: &mut APIProviders<Self>,
+    ) -> (Result<RuneScriptContext, ScriptError>, Vec<ScriptError>) {
+        let mut context = match rune_modules::default_context()
+            .and_then(|mut context| context.install(script_module()?).map(|_| context))
+        {
+            Ok(context) => context,
+            Err(e) => return (Err(ScriptError::new_other(e)), Vec::default()),
+        };
+
+        // Rune requires that we tell it what modules and types we'll be using before
+        // it compiles a file.
+        providers.attach_all(&mut context).unwrap();
+
+        let mut diagnostics = Diagnostics::new();
+
+        let mut sources = Sources::new();
+        let source = Source::new(
+            script_data.name,
+            std::str::from_utf8(script).expect("Slice is not UTF-8"),
+        )
+        .map_err(|msg| msg.to_string())
+        .and_then(|source| sources.insert(source).map_err(|msg| msg.to_string()));
+        if let Err(msg) = source {
+            let error = ScriptError::FailedToLoad {
+                script: script_data.name.into(),
+                msg,
+            };
+            return (Err(error), Vec::default());
+        }
+
+        let unit = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_diagnostics(&mut diagnostics)
+            .build();
+
+        // warnings do not prevent the script from running
+        let warnings = diagnostics
+            .diagnostics()
+            .iter()
+            .filter_map(|diagnostic| match diagnostic {
+                Diagnostic::Warning(warning) => Some(warning),
+                _ => None,
+            })
+            .map(|warning| {
+                let (line, column) = sources
+                    .get(warning.source_id())
+                    .map(|source| source.pos_to_utf8_linecol(warning.span().start.into_usize()))
+                    .unwrap_or_default();
+                ScriptError::LintWarning {
+                    script: script_data.name.to_owned(),
+                    line: line + 1,
+                    column: column + 1,
+                    msg: warning.to_string(),
+                }
+            })
+            .collect();
+
+        if diagnostics.has_error() {
+            let mut writer = rune::termcolor::Buffer::no_color();
+
+            diagnostics
+                .emit(&mut writer, &sources)
+                .expect("Failed to write diagnostics to buffer");
+
+            let error = ScriptError::SyntaxError {
+                script: script_data.name.into(),
+                msg: std::str::from_utf8(writer.as_slice())
+                    .expect("Slice was not UTF-8")
+                    .to_owned(),
+            };
+            return (Err(error), warnings);
+        }
+
+        let unit = unit.expect("Failed to build Rune unit.");
+
+        let runtime_ctx = context
+            .runtime()
+            .expect("Failed to create Rune runtime context.");
+
+        let loaded = RuneScriptContext {
+            unit: Arc::new(unit),
+            runtime_context: Arc::new(runtime_ctx),
+            state: RuneScriptState::default(),
+        };
+        (Ok(loaded), warnings)
+    }
+
     /// Helper function to handle errors from a Rune virtual machine.
     ///
     #[cold]
@@ -144,67 +344,28 @@ impl<A: RuneArgs> ScriptHost for RuneScriptHost<A> {
             );
     }
 
+    fn lint_script(
+        &mut self,
+        script: &[u8],
+        script_data: &ScriptData,
+        providers: &mut APIProviders<Self>,
+    ) -> Vec<ScriptError> {
+        // errors are reported when loading the script
+        let (loaded, warnings) = Self::compile(script, script_data, providers);
+        self.linted = Some((script_data.sid, loaded));
+        warnings
+    }
+
     fn load_script(
         &mut self,
         script: &[u8],
         script_data: &ScriptData,
         providers: &mut APIProviders<Self>,
     ) -> Result<Self::ScriptContext, ScriptError> {
-        let mut context = rune_modules::default_context().map_err(ScriptError::new_other)?;
-
-        // Rune requires that we tell it what modules and types we'll be using before
-        // it compiles a file.
-        providers.attach_all(&mut context).unwrap();
-
-        let mut diagnostics = Diagnostics::new();
-
-        let mut sources = Sources::new();
-        sources
-            .insert(
-                Source::new(
-                    script_data.name,
-                    std::str::from_utf8(script).expect("Slice is not UTF-8"),
-                )
-                .map_err(|msg| ScriptError::FailedToLoad {
-                    script: script_data.name.into(),
-                    msg: msg.to_string(),
-                })?,
-            )
-            .map_err(|msg| ScriptError::FailedToLoad {
-                script: script_data.name.into(),
-                msg: msg.to_string(),
-            })?;
-
-        let result = rune::prepare(&mut sources)
-            .with_context(&context)
-            .with_diagnostics(&mut diagnostics)
-            .build();
-
-        if !diagnostics.is_empty() {
-            let mut writer = rune::termcolor::Buffer::no_color();
-
-            diagnostics
-                .emit(&mut writer, &sources)
-                .expect("Failed to write diagnostics to buffer");
-
-            return Err(ScriptError::SyntaxError {
-                script: script_data.name.into(),
-                msg: std::str::from_utf8(writer.as_slice())
-                    .expect("Slice was not UTF-8")
-                    .to_owned(),
-            });
+        match self.linted.take() {
+            Some((sid, loaded)) if sid == script_data.sid => loaded,
+            _ => Self::compile(script, script_data, providers).0,
         }
-
-        let unit = result.expect("Failed to build Rune unit.");
-
-        let runtime_ctx = context
-            .runtime()
-            .expect("Failed to create Rune runtime context.");
-
-        Ok(RuneScriptContext {
-            unit: Arc::new(unit),
-            runtime_context: Arc::new(runtime_ctx),
-        })
     }
 
     fn setup_script(
@@ -224,7 +385,8 @@ impl<A: RuneArgs> ScriptHost for RuneScriptHost<A> {
         providers: &mut APIProviders<Self>,
     ) {
         // Grab the cached Vm.
-        let RuneVm(mut vm) = world.remove_non_send_resource::<RuneVm>().unwrap(/* invariant */);
+        let mut cached = world.remove_non_send_resource::<RuneVm>().unwrap(/* invariant */);
+        cached.clear_stale_states();
 
         {
             // Safety:
@@ -238,34 +400,45 @@ impl<A: RuneArgs> ScriptHost for RuneScriptHost<A> {
                     .setup_runtime_all(world.clone(), &script_data, ctx)
                     .expect("Could not setup script runtime");
 
-                for event in events {
-                    if !event.recipients().is_recipient(&script_data) {
-                        continue;
-                    }
+                let current = RuneScriptData {
+                    sid: script_data.sid,
+                    entity: script_data.entity,
+                    name: script_data.name.to_owned(),
+                    world: world.clone(),
+                    state: cached.state(&ctx.state),
+                };
+                let vm = &mut cached.vm;
+
+                current.scope(|| {
+                    for event in events {
+                        if !event.recipients().is_recipient(&script_data) {
+                            continue;
+                        }
+
+                        // Swap out the old context and old unit with the new ones.
+                        *vm.context_mut() = Arc::clone(&ctx.runtime_context);
+                        *vm.unit_mut() = Arc::clone(&ctx.unit);
 
-                    // Swap out the old context and old unit with the new ones.
-                    *vm.context_mut() = Arc::clone(&ctx.runtime_context);
-                    *vm.unit_mut() = Arc::clone(&ctx.unit);
+                        let mut exec =
+                            match vm.execute([event.hook_name.as_str()], event.args.clone()) {
+                                Ok(exec) => exec,
+                                Err(error) => {
+                                    Self::handle_rune_error(world.clone(), error, &script_data);
+                                    continue;
+                                }
+                            };
 
-                    let mut exec = match vm.execute([event.hook_name.as_str()], event.args.clone())
-                    {
-                        Ok(exec) => exec,
-                        Err(error) => {
+                        if let VmResult::Err(error) = exec.complete() {
                             Self::handle_rune_error(world.clone(), error, &script_data);
-                            continue;
                         }
-                    };
-
-                    if let VmResult::Err(error) = exec.complete() {
-                        Self::handle_rune_error(world.clone(), error, &script_data);
                     }
-                }
+                });
             });
 
             // explictly release the pointer to world.
             drop(world);
         }
 
-        world.insert_non_send_resource(RuneVm(vm));
+        world.insert_non_send_resource(cached);
     }
 }
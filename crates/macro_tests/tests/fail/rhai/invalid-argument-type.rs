@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::api::*;
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(functions[
+    r#"
+    #[lua(kind="Method")]
+    fn my_fn(&self, arg: String);
+    "#,
+])]
+pub struct MyStruct;
+
+impl MyStruct {
+    pub fn my_fn(&self, _: usize) {}
+}
+
+pub fn main() {}
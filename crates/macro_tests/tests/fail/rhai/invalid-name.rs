@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::api::*;
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(name = "MyProxy", functions[])]
+pub struct MyStruct;
+
+pub fn main() {}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::api::*;
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(functions[
+    r#"
+    #[lua(kind="Function")]
+    fn my_fn((a, b): (usize, usize)) -> usize;
+    "#,
+])]
+pub struct MyStruct;
+
+impl MyStruct {
+    pub fn my_fn((a, b): (usize, usize)) -> usize {
+        a + b
+    }
+}
+
+pub fn main() {}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::{api::*, rhai::rhai::Engine};
+use bevy_script_api::rhai::RegisterRhaiProxy;
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(derive(clone), functions[])]
+pub struct Inner {
+    value: f32,
+}
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(derive(clone), functions[
+    r#"
+    #[lua(kind="Function", output(proxy))]
+    fn new(name: String, value: f32) -> Self {
+        MyStruct { name, inner: Inner { value } }
+    }
+    "#,
+])]
+pub struct MyStruct {
+    // mentions a proxy, but does not mark the field as one
+    #[lua(doc = "the name of the proxy")]
+    name: String,
+    #[lua(output(proxy))]
+    inner: Inner,
+}
+
+pub fn main() {
+    let mut engine = Engine::new();
+    engine.register_rhai_proxy::<RhaiInner>();
+    engine.register_rhai_proxy::<RhaiMyStruct>();
+
+    assert_eq!(
+        engine
+            .eval::<String>(r#"let s = MyStruct("a", 1.0); s.name += "b"; s.name"#)
+            .unwrap(),
+        "ab"
+    );
+    assert_eq!(
+        engine
+            .eval::<f64>(r#"let s = MyStruct("a", 1.0); s.inner.value = 2.0; s.inner.value"#)
+            .unwrap(),
+        2.0
+    );
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::{api::*, rhai::rhai::Engine};
+use bevy_script_api::rhai::RegisterRhaiProxy;
+
+#[derive(RhaiProxy, Reflect, Clone)]
+#[proxy(derive(clone), functions[
+    r#"
+    #[lua(kind="Function", output(proxy))]
+    fn new(value: f32) -> Self;
+    "#,
+
+    r#"
+    #[lua(kind="MetaMethod", metamethod="Eq")]
+    fn eq(&self, #[proxy] other: &Self) -> bool {
+        _self.value == other.value
+    }
+    "#,
+
+    r#"
+    #[lua(kind="MetaMethod", metamethod="Lt")]
+    fn lt(&self, #[proxy] other: &Self) -> bool {
+        _self.value < other.value
+    }
+    "#,
+
+    r#"
+    #[lua(kind="MetaMethod", metamethod="Le")]
+    fn le(&self, #[proxy] other: &Self) -> bool {
+        _self.value <= other.value
+    }
+    "#,
+])]
+pub struct MyStruct {
+    value: f32,
+}
+
+impl MyStruct {
+    pub fn new(value: f32) -> Self {
+        Self { value }
+    }
+}
+
+pub fn main() {
+    let mut engine = Engine::new();
+    engine.register_rhai_proxy::<RhaiMyStruct>();
+
+    let eval = |script: &str| engine.eval::<bool>(script).unwrap();
+    assert!(eval("MyStruct(1.0) == MyStruct(1.0)"));
+    assert!(eval("MyStruct(1.0) != MyStruct(2.0)"));
+    assert!(eval("MyStruct(1.0) < MyStruct(2.0)"));
+    assert!(eval("MyStruct(2.0) > MyStruct(1.0)"));
+    assert!(!eval("MyStruct(1.0) > MyStruct(2.0)"));
+    assert!(eval("MyStruct(1.0) <= MyStruct(1.0)"));
+    assert!(eval("MyStruct(1.0) >= MyStruct(1.0)"));
+    assert!(!eval("MyStruct(1.0) >= MyStruct(2.0)"));
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_mod_scripting::{core::event::ScriptLoaded, prelude::*};
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Counter {
+    pub value: f32,
+}
+
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, ScriptingPlugin))
+        .register_type::<Counter>()
+        .add_script_host::<LuaScriptHost<()>>(PostUpdate)
+        .add_script_handler::<LuaScriptHost<()>, 0, 0>(PostUpdate)
+        .add_api_provider::<LuaScriptHost<()>>(Box::new(LuaBevyAPIProvider))
+        .add_api_provider::<LuaScriptHost<()>>(Box::new(LuaCoreBevyAPIProvider))
+        // scripts can register systems under the "update" label, these run every frame in `Update`
+        .add_script_system_set::<LuaScriptHost<()>>(Update, "update")
+        .add_systems(Startup, setup)
+        .add_systems(Update, on_load)
+        .add_systems(
+            Update,
+            print_counters.after(ScriptSystemLabel::new("update")),
+        )
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Counter::default());
+    commands.spawn(Counter { value: 100.0 });
+
+    let path = "scripts/script_systems.lua";
+    let handle = asset_server.load(path);
+
+    commands.spawn(ScriptCollection::<LuaFile> {
+        scripts: vec![Script::new(path.into(), handle)],
+    });
+}
+
+/// scripts register their systems when they handle this event, this also happens on every hot reload
+fn on_load(mut loaded: EventReader<ScriptLoaded>, mut events: PriorityEventWriter<LuaEvent<()>>) {
+    for script in loaded.read() {
+        events.send(
+            LuaEvent {
+                hook_name: "on_load".into(),
+                args: (),
+                recipients: Recipients::ScriptID(script.sid),
+            },
+            0,
+        );
+    }
+}
+
+fn print_counters(counters: Query<(Entity, &Counter), Changed<Counter>>) {
+    for (entity, counter) in &counters {
+        info!("{entity}: {}", counter.value);
+    }
+}
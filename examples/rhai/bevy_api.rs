@@ -46,10 +46,12 @@ fn main() -> std::io::Result<()> {
         .register_foreign_rhai_type::<Option<bool>>()
         .register_foreign_rhai_type::<Vec<Option<bool>>>()
         .register_foreign_rhai_type::<Option<Vec<bool>>>()
+        .register_foreign_rhai_type::<Option<Vec3>>()
         // note the implementation for Option is there, but we must register `LuaProxyable` for it
         // this system set handles addition and removal of script contexts, we can safely use `CoreSet::PostUpdate`
         .add_script_host::<RhaiScriptHost<()>>(PostUpdate)
         .add_api_provider::<RhaiScriptHost<()>>(Box::new(RhaiBevyAPIProvider))
+        .add_api_provider::<RhaiScriptHost<()>>(Box::new(RhaiBevyTypesAPIProvider))
         .add_api_provider::<RhaiScriptHost<()>>(Box::new(MyAPIProvider))
         .add_systems(Update, |world: &mut World| {
             let entity = world
@@ -94,14 +96,13 @@ fn main() -> std::io::Result<()> {
                             comp.usize = 2;
                             print("comp.usize: after assigning to 2: " + comp.usize);
 
-                            // not supported yet (no Bevy proxies yet)
-                            // print("comp.option_vec3 before: " + comp.option_vec3);
-                            // comp.option_vec3 = Vec3::new(2,1,3);
-                            // print("comp.option_vec3 after: " + comp.option_vec3);
-                            
-                            // print("comp.option_vec3[0] before: " + comp.option_vec3[0]);
-                            // comp.option_vec3[1] = 5;
-                            // print("comp.option_vec3[0] after: " + comp.option_vec3[0]);
+                            print("comp.option_vec3 before: " + comp.option_vec3);
+                            comp.option_vec3 = Vec3(2.0, 1.0, 3.0);
+                            print("comp.option_vec3 after: " + comp.option_vec3);
+
+                            print("comp.option_vec3.y before: " + comp.option_vec3.y);
+                            comp.option_vec3.y = 5.0;
+                            print("comp.option_vec3.y after: " + comp.option_vec3.y);
 
                             print("=============");
 
@@ -0,0 +1,57 @@
+use bevy_mod_scripting::{api::rhai::bevy::RhaiBevyAPIProvider, prelude::*};
+use std::{env, path::Path, process::ExitCode};
+
+/// The API of our game, which scripts and their editors should know about
+pub struct MyAPIProvider;
+
+impl APIProvider for MyAPIProvider {
+    type APITarget = Engine;
+    type ScriptContext = RhaiContext;
+    type DocTarget = RhaiDocFragment;
+
+    fn attach_api(&mut self, engine: &mut Self::APITarget) -> Result<(), ScriptError> {
+        engine.register_fn("double", |x: i64| x * 2);
+        Ok(())
+    }
+
+    fn get_doc_fragment(&self) -> Option<Self::DocTarget> {
+        // rhai documentation is read back from an engine the API was attached to
+        Some(RhaiDocFragment::new("MyAPI", |engine| {
+            MyAPIProvider.attach_api(engine)
+        }))
+    }
+}
+
+/// Generates the documentation of the game's script API without running the game,
+/// like the `bevy_mod_scripting_doc_gen` binary does for the APIs bundled with this crate.
+///
+/// Run with `--check` in CI to fail when the committed documentation is out of date.
+fn main() -> ExitCode {
+    let mut generator = DocGenerator::default();
+    // providers of the same script host are merged into one set of files, named after the first provider's fragment,
+    // and generated into the given subdirectory of the output directory
+    generator
+        .add_api_provider::<RhaiScriptHost<()>>("rhai", Box::new(MyAPIProvider))
+        .add_api_provider::<RhaiScriptHost<()>>("rhai", Box::new(RhaiBevyAPIProvider));
+
+    let output_dir = Path::new("script_docs");
+    let result = if env::args().any(|arg| arg == "--check") {
+        generator.check(output_dir).map(|outdated| {
+            for file in &outdated {
+                eprintln!("Out of date: {}", file.display());
+            }
+            outdated.is_empty()
+        })
+    } else {
+        generator.generate(output_dir).map(|_| true)
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::app::AppExit;
+
+use bevy::prelude::*;
+use bevy_mod_scripting::{api::rune::bevy::RuneBevyAPIProvider, prelude::*};
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct MyComponent {
+    vec2: Vec2,
+    usize: usize,
+    f32: f32,
+    option_vec3: Option<Vec3>,
+    names: Vec<String>,
+}
+
+fn main() -> std::io::Result<()> {
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(ScriptingPlugin)
+        .register_type::<MyComponent>()
+        .add_script_host::<RuneScriptHost<()>>(PostUpdate)
+        .add_api_provider::<RuneScriptHost<()>>(Box::new(RuneBevyAPIProvider))
+        // writes markdown and json documentation of the API to `script_docs`
+        .update_documentation::<RuneScriptHost<()>>()
+        .add_systems(Update, |world: &mut World| {
+            let entity = world
+                .spawn(MyComponent {
+                    usize: 5,
+                    vec2: Vec2::new(1.0, 2.0),
+                    f32: 6.7,
+                    option_vec3: None,
+                    names: vec!["hello".to_owned(), "world".to_owned()],
+                })
+                .id();
+
+            // run script, `entity()` and `world()` in the script refer to the entity and world it runs on
+            world.resource_scope(|world, mut host: Mut<RuneScriptHost<()>>| {
+                host.run_one_shot(
+                    include_bytes!("../../assets/scripts/bevy_api.rune"),
+                    "bevy_api.rune",
+                    entity,
+                    world,
+                    RuneEvent {
+                        hook_name: "once".to_owned(),
+                        args: (),
+                        recipients: Recipients::All,
+                    },
+                )
+                .expect("Something went wrong in the script!");
+            });
+
+            world.send_event(AppExit::Success);
+        });
+
+    app.run();
+
+    Ok(())
+}
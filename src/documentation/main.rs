@@ -1,32 +1,134 @@
-use bevy::prelude::*;
+//! Generates documentation and definition files for the script APIs bundled with this crate, without running a game.
+//!
+//! ```text
+//! bevy_mod_scripting_doc_gen [--check] [--output <dir>] [lua|rhai|rune]...
+//! ```
+//!
+//! The documentation of each language is generated into its own subdirectory of the output directory,
+//! `script_docs` by default. All languages enabled via cargo features are generated if none are given.
+//! With `--check` nothing is written, instead the process fails if any of the generated files are missing or out of date.
+//!
+//! The providers are fixed, to document your own build a similar binary on top of `DocGenerator`, see the `documentation_gen_rhai` example.
 use bevy_mod_scripting::prelude::*;
-use std::env;
+use std::{env, path::PathBuf, process::ExitCode};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let mut app = App::new();
-    app.add_plugins(MinimalPlugins)
-        .add_plugins(ScriptingPlugin)
-        .add_plugins(AssetPlugin::default());
+static USAGE: &str =
+    "Usage: bevy_mod_scripting_doc_gen [--check] [--output <dir>] [lua|rhai|rune]...";
 
-    static INVALID_ARGUMENT_WARNING: &str = "Expected one of: 'lua','rhai' as arguments";
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut output_dir = DocumentationSettings::default().output_dir;
+    let mut languages = Vec::default();
 
-    let lang = args.get(1).expect(INVALID_ARGUMENT_WARNING);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--output" | "-o" => match args.next() {
+                Some(dir) => output_dir = PathBuf::from(dir),
+                None => {
+                    eprintln!("Expected a directory after `{arg}`\n{USAGE}");
+                    return ExitCode::from(2);
+                }
+            },
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => languages.push(arg),
+        }
+    }
 
-    match lang.as_str() {
-        "lua" => {
-            #[cfg(all(feature = "lua", feature = "lua_script_api"))]
-            app.add_script_host::<LuaScriptHost<()>>(PostUpdate)
-                .add_api_provider::<LuaScriptHost<()>>(Box::new(LuaCoreBevyAPIProvider))
-                .add_api_provider::<LuaScriptHost<()>>(Box::new(LuaBevyAPIProvider))
-                .update_documentation::<LuaScriptHost<()>>();
+    let mut generator = DocGenerator::default();
+    if languages.is_empty() {
+        languages = ["lua", "rhai", "rune"]
+            .into_iter()
+            .filter(|lang| is_enabled(lang))
+            .map(ToOwned::to_owned)
+            .collect();
+    }
+    for lang in &languages {
+        if !add_providers(&mut generator, lang) {
+            eprintln!(
+                "Cannot generate `{lang}` documentation, expected one of: 'lua','rhai','rune' with the `{lang}` and `{lang}_script_api` features enabled\n{USAGE}"
+            );
+            return ExitCode::from(2);
+        }
+    }
+
+    if check {
+        match generator.check(&output_dir) {
+            Ok(outdated) if outdated.is_empty() => {
+                println!("Documentation in `{}` is up to date", output_dir.display());
+                ExitCode::SUCCESS
+            }
+            Ok(outdated) => {
+                eprintln!(
+                    "Documentation in `{}` is out of date, re-run without `--check` to update it:",
+                    output_dir.display()
+                );
+                for file in outdated {
+                    eprintln!("  {}", file.display());
+                }
+                ExitCode::FAILURE
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }
+    } else {
+        match generator.generate(&output_dir) {
+            Ok(()) => {
+                println!("Documentation generated in `{}`", output_dir.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+fn is_enabled(lang: &str) -> bool {
+    match lang {
+        "lua" => cfg!(all(feature = "lua", feature = "lua_script_api")),
+        "rhai" => cfg!(all(feature = "rhai", feature = "rhai_script_api")),
+        "rune" => cfg!(all(feature = "rune", feature = "rune_script_api")),
+        _ => false,
+    }
+}
 
-            #[cfg(any(not(feature = "lua"), not(feature = "lua_script_api")))]
-            println!("Re-run with the following features enabled: `lua`,`lua_script_api`")
+/// Registers the providers of the given language, returns `false` if the language is not supported by this build
+#[allow(unused_variables)]
+fn add_providers(generator: &mut DocGenerator, lang: &str) -> bool {
+    match lang {
+        #[cfg(all(feature = "lua", feature = "lua_script_api"))]
+        "lua" => {
+            generator
+                .add_api_provider::<LuaScriptHost<()>>("lua", Box::new(LuaCoreBevyAPIProvider))
+                .add_api_provider::<LuaScriptHost<()>>("lua", Box::new(LuaBevyAPIProvider));
+            true
         }
+        #[cfg(all(feature = "rhai", feature = "rhai_script_api"))]
         "rhai" => {
-            println!("Rhai documentation generation is not supported yet");
+            generator
+                .add_api_provider::<RhaiScriptHost<()>>(
+                    "rhai",
+                    Box::new(bevy_mod_scripting::api::rhai::bevy::RhaiBevyAPIProvider),
+                )
+                .add_api_provider::<RhaiScriptHost<()>>("rhai", Box::new(RhaiBevyTypesAPIProvider));
+            true
+        }
+        #[cfg(all(feature = "rune", feature = "rune_script_api"))]
+        "rune" => {
+            generator.add_api_provider::<RuneScriptHost<()>>(
+                "rune",
+                Box::new(bevy_mod_scripting::api::rune::bevy::RuneBevyAPIProvider),
+            );
+            true
         }
-        _ => println!("{}", INVALID_ARGUMENT_WARNING),
+        _ => false,
     }
 }
@@ -0,0 +1,109 @@
+//! Checks scripts against the script APIs bundled with this crate, without running a game.
+//!
+//! ```text
+//! bevy_mod_scripting_lint <script>...
+//! ```
+//!
+//! The language of each script is picked by its extension, `.rhai` (which requires the `rhai_lint` feature), `.rune` or `.lua` (which requires the `teal_lint` feature and `tl` to be installed).
+//! Every finding is printed with its location, the process fails if any are found.
+use bevy::prelude::Entity;
+use bevy_mod_scripting::prelude::*;
+use std::{env, fs, path::PathBuf, process::ExitCode};
+
+static USAGE: &str = "Usage: bevy_mod_scripting_lint <script>...";
+
+fn main() -> ExitCode {
+    let mut scripts = Vec::default();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => scripts.push(PathBuf::from(arg)),
+        }
+    }
+
+    if scripts.is_empty() {
+        eprintln!("Expected at least one script\n{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    let mut findings = 0;
+    for path in &scripts {
+        let script = match fs::read(path) {
+            Ok(script) => script,
+            Err(e) => {
+                eprintln!("Could not read `{}`: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        };
+        let name = path.display().to_string();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+
+        let Some(warnings) = lint(&script, &name, extension) else {
+            eprintln!(
+                "Cannot lint `{name}`, expected one of: '.lua','.rhai','.rune' with the language and its `_script_api` feature enabled (and `rhai_lint` for Rhai, `teal_lint` for Lua)\n{USAGE}"
+            );
+            return ExitCode::from(2);
+        };
+        for warning in &warnings {
+            eprintln!("{warning}");
+        }
+        findings += warnings.len();
+    }
+
+    if findings == 0 {
+        println!("No problems found in {} script(s)", scripts.len());
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("Found {findings} problem(s)");
+        ExitCode::FAILURE
+    }
+}
+
+/// Lints the script with the providers of its language, returns `None` if the language is not supported by this build
+#[allow(unused_variables)]
+fn lint(script: &[u8], name: &str, extension: &str) -> Option<Vec<ScriptError>> {
+    let script_data = ScriptData {
+        sid: 0,
+        entity: Entity::PLACEHOLDER,
+        name,
+    };
+
+    match extension {
+        #[cfg(all(feature = "lua", feature = "lua_script_api", feature = "teal_lint"))]
+        "lua" => {
+            let mut providers = APIProviders::<LuaScriptHost<()>>::default();
+            providers.providers.push(Box::new(LuaCoreBevyAPIProvider));
+            providers.providers.push(Box::new(LuaBevyAPIProvider));
+            Some(LuaScriptHost::<()>::default().lint_script(script, &script_data, &mut providers))
+        }
+        #[cfg(all(feature = "rhai", feature = "rhai_script_api", feature = "rhai_lint"))]
+        "rhai" => {
+            let mut host = RhaiScriptHost::<()>::default();
+            let mut providers = APIProviders::<RhaiScriptHost<()>>::default();
+            providers.providers.push(Box::new(
+                bevy_mod_scripting::api::rhai::bevy::RhaiBevyAPIProvider,
+            ));
+            providers.providers.push(Box::new(RhaiBevyTypesAPIProvider));
+            // the script host attaches its APIs to the engine on startup
+            if let Err(e) = providers.attach_all(&mut host.engine) {
+                return Some(vec![e]);
+            }
+            Some(host.lint_script(script, &script_data, &mut providers))
+        }
+        #[cfg(all(feature = "rune", feature = "rune_script_api"))]
+        "rune" => {
+            let mut providers = APIProviders::<RuneScriptHost<()>>::default();
+            providers.providers.push(Box::new(
+                bevy_mod_scripting::api::rune::bevy::RuneBevyAPIProvider,
+            ));
+            Some(RuneScriptHost::<()>::default().lint_script(script, &script_data, &mut providers))
+        }
+        _ => None,
+    }
+}